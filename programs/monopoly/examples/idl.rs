@@ -0,0 +1,6 @@
+//! Print the program's IDL as JSON; `yarn idl` writes it to `idl/monopoly.json`
+
+fn main() {
+    let idl = monopoly::idl::generate();
+    println!("{}", serde_json::to_string_pretty(&idl).expect("IDL serializes"));
+}
@@ -1,45 +1,112 @@
+use std::cell::RefMut;
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::{
     account_info::AccountInfo,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
 };
 // Pubkey already imported above
 
-use crate::state::{Game, Player, Property, Card};
+use crate::board::{TileInfo, STANDARD_BOARD};
+use crate::state::{
+    Game, Player, Property, Card, TradeTerms, GameStatus, TurnPhase, Auction, Debt, DiceRound,
+    RandomnessKind, PendingAction, GameRules, Board, GameState, PlayerState, PropertyView, TileView,
+    MAX_PLAYERS, MAX_NAME_LEN, MAX_PLAYER_PROPERTIES, MAX_DECK_CARDS, BOARD_SIZE, BOARD_NONE, BOARD_STANDARD,
+};
+use crate::cursor::{Reader, Writer};
+use crate::error::MonopolyError;
+use crate::utils::{shuffle, RandomnessSource};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::{
+        Color, GameStatus, TileKind, TileType, RandomnessKind, TurnPhase, GameRules, BANK_HOUSES, BANK_HOTELS,
+        MAX_RENT_LEVELS, MAX_HOUSE_RENT_LEVELS,
+    };
+
+    /// Packs `value` into a program-owned account and runs `validate` over it.
+    fn validate_packed<T: Pack, R>(
+        value: &T,
+        validate: fn(&AccountInfo) -> Result<R, ProgramError>,
+    ) -> Result<R, ProgramError> {
+        let key = Pubkey::new_unique();
+        let owner = crate::id();
+        let mut lamports = 0;
+        let mut data = vec![0u8; T::LEN];
+        value.pack_into_slice(&mut data);
+        let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        validate(&account)
+    }
 
     #[test]
     fn test_game_account_pack() {
         use crate::board::create_board;
 
         // Create a test game state
-        let mut game = Game {
+        let game = Game {
             board: create_board(),
             players: vec![
                 Player {
                     name: String::from("Player 1"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 1500,
                     position: 0,
                     jail_turns: 0,
                     properties: vec![1, 3],
                     get_out_of_jail_cards: 1,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 Player {
                     name: String::from("Player 2"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 2000,
                     position: 5,
                     jail_turns: 2,
                     properties: vec![6, 8, 9],
                     get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
             ],
             current_player: 1,
             free_parking: 500,
             initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
         };
 
         // Create a GameAccount
@@ -104,6 +171,28 @@ mod tests {
                     current_player: 0,
                     free_parking: 0,
                     initialized: false,
+                    creator: Pubkey::default(),
+                    status: GameStatus::Started,
+                    max_players: 8,
+                    auction: None,
+                    debt: None,
+                    last_roll: 0,
+                    houses_remaining: BANK_HOUSES,
+                    hotels_remaining: BANK_HOTELS,
+                    doubles_rolled: 0,
+                    dice_round: None,
+                    rolled_dice: None,
+                    randomness: RandomnessKind::Seeded { seed: 0 },
+                    randomness_nonce: 0,
+                    last_dice: (0, 0),
+                    last_doubles: false,
+                    turn_phase: TurnPhase::AwaitingRoll,
+                    pending_action: None,
+                    rules: GameRules::default(),
+                    auction_queue: vec![],
+                    randomness_request: None,
+                    community_chest_deck: None,
+                    chance_deck: None,
                 },
             };
 
@@ -111,45 +200,69 @@ mod tests {
             uninitialized_account.pack_into_slice(&mut buffer);
 
             let unpacked = GameAccount::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.is_initialized, false);
+            assert!(!unpacked.is_initialized);
             assert_eq!(unpacked.game.players.len(), 0);
-            assert_eq!(unpacked.game.initialized, false);
+            assert!(!unpacked.game.initialized);
         }
 
         // Test case 3: Buffer too small
         {
             let mut small_buffer = vec![0u8; 10]; // Too small for game state
-            let result = std::panic::catch_unwind(|| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 game_account.pack_into_slice(&mut small_buffer);
-            });
+            }));
             assert!(result.is_err()); // Should panic on buffer too small
         }
 
         // Test case 4: Maximum values
         {
+            let mut board = create_board();
+            if let TileType::Property(property) = &mut board[1] {
+                property.owner = Some(0);
+                property.houses = u8::MAX;
+            }
             let max_game = Game {
-                board: vec![TileType::Property(Property {
-                    name: String::from("Max Property"),
-                    color: Color::DarkBlue,
-                    cost: u64::MAX,
-                    rent: vec![u64::MAX; 6],
-                    house_cost: u64::MAX,
-                    hotel_cost: u64::MAX,
-                    house_rent: vec![u64::MAX; 5],
-                    owner: None,
-                    houses: u8::MAX,
-                })],
+                board,
                 players: vec![Player {
                     name: String::from("Max Player"),
-                    cash: u32::MAX,
+                    wallet: Pubkey::default(),
+                    delegate: None,
+                    cash: u64::MAX,
                     position: u8::MAX,
                     jail_turns: u8::MAX,
-                    properties: vec![u8::MAX],
+                    properties: vec![1],
                     get_out_of_jail_cards: u8::MAX,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 }],
                 current_player: u8::MAX,
-                free_parking: u32::MAX,
+                free_parking: u64::MAX,
                 initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::AwaitingRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
             };
             let max_account = GameAccount {
                 is_initialized: true,
@@ -160,11 +273,238 @@ mod tests {
             max_account.pack_into_slice(&mut buffer);
 
             let unpacked = GameAccount::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.game.players[0].cash, u32::MAX);
+            assert_eq!(unpacked.game.players[0].cash, u64::MAX);
             assert_eq!(unpacked.game.players[0].position, u8::MAX);
-            assert_eq!(unpacked.game.board[0].price, u32::MAX);
-            assert_eq!(unpacked.game.board[0].houses, u8::MAX);
-            assert_eq!(unpacked.game.free_parking, u32::MAX);
+            assert!(matches!(&unpacked.game.board[1], TileType::Property(p) if p.houses == u8::MAX));
+            assert_eq!(unpacked.game.free_parking, u64::MAX);
+        }
+    }
+
+    #[test]
+    fn test_game_account_data() {
+        use crate::board::create_board;
+        use crate::state::{DiceRound, DicePhase, TileType};
+
+        let wallet = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let mut board = create_board();
+        if let TileType::Property(property) = &mut board[39] {
+            property.owner = Some(0);
+            property.houses = 4;
+        }
+        let game_account = GameAccount {
+            is_initialized: true,
+            game: Game {
+                board,
+                players: vec![Player {
+                    name: String::from("Player 1"),
+                    wallet,
+                    delegate: Some(delegate),
+                    cash: 1234,
+                    position: 39,
+                    jail_turns: 0,
+                    properties: vec![39],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: Some([5; 32]),
+                    dice_secret: None,
+                }],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: wallet,
+                status: GameStatus::Started,
+                max_players: 4,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: Some(DiceRound { phase: DicePhase::Commit, deadline_slot: 99 }),
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::AwaitingRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            },
+        };
+        let mut buffer = vec![0u8; GameAccount::LEN];
+        game_account.pack_into_slice(&mut buffer);
+
+        // Test case 1: The view reads the packed fields in place
+        {
+            let data: &GameAccountData = bytemuck::from_bytes(&buffer);
+            assert_eq!(data.players().len(), 1);
+            let player = &data.players()[0];
+            assert_eq!(&player.name[..player.name_len as usize], b"Player 1");
+            assert_eq!(player.wallet, wallet);
+            assert_eq!(player.delegate(), Some(delegate));
+            assert!(player.is_controlled_by(&delegate));
+            assert_eq!(player.cash(), 1234);
+            assert_eq!(player.dice_commitment(), Some([5; 32]));
+            assert_eq!(data.tiles[39].owner, 0);
+            assert_eq!(data.tiles[39].houses, 4);
+            assert_eq!(data.tiles[37].owner, u8::MAX);
+            assert_eq!(data.creator, wallet);
+            assert_eq!(data.status().unwrap(), GameStatus::Started);
+            assert_eq!(data.turn_phase().unwrap(), TurnPhase::AwaitingRoll);
+            assert_eq!(data.dice_round().unwrap(), game_account.game.dice_round);
+            assert!(!data.has_debt());
+            assert!(!data.has_rolled_dice());
+            assert_eq!(data.randomness().unwrap(), RandomnessKind::Seeded { seed: 0 });
+            assert_eq!(data.rules().unwrap(), GameRules::default());
+            let property = data.property(39).unwrap();
+            assert_eq!(property.owner, Some(0));
+            assert_eq!(property.houses, 4);
+            assert_eq!(property.cost, 400);
+            assert!(data.property(38).is_none());
+        }
+
+        // Test case 2: Writes through the view show up when the account is unpacked
+        {
+            let data: &mut GameAccountData = bytemuck::from_bytes_mut(&mut buffer);
+            let player = &mut data.players_mut()[0];
+            player.set_delegate(None);
+            player.set_cash(99);
+            player.set_dice_commitment(None);
+            player.push_property(1).unwrap();
+            data.set_dice_round(None);
+            data.set_rolled_dice(Some((2, 5)));
+            data.set_pending_action(Some(PendingAction::BuyProperty { property_index: 1 }));
+            data.set_debt(Some(Debt { debtor: 0, creditor: None, amount: 40 }));
+            data.set_randomness_nonce(7);
+            data.tiles[1].set_owner(Some(0));
+
+            let unpacked = GameAccount::unpack_from_slice(&buffer).unwrap();
+            assert_eq!(unpacked.game.players[0].delegate, None);
+            assert_eq!(unpacked.game.players[0].cash, 99);
+            assert_eq!(unpacked.game.players[0].dice_commitment, None);
+            assert_eq!(unpacked.game.players[0].properties, vec![39, 1]);
+            assert_eq!(unpacked.game.dice_round, None);
+            assert_eq!(unpacked.game.rolled_dice, Some((2, 5)));
+            assert_eq!(unpacked.game.pending_action, Some(PendingAction::BuyProperty { property_index: 1 }));
+            assert_eq!(unpacked.game.debt, Some(Debt { debtor: 0, creditor: None, amount: 40 }));
+            assert_eq!(unpacked.game.randomness_nonce, 7);
+            assert_eq!(unpacked.game.board[1].as_property().unwrap().owner, Some(0));
+        }
+
+        // Test case 3: Only initialized game accounts of the right size and owner are borrowed
+        {
+            let key = Pubkey::new_unique();
+            let program_id = crate::id();
+            let other_owner = Pubkey::new_unique();
+            let mut lamports = 0;
+            let mut data = buffer.clone();
+            let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, 0);
+            assert_eq!(load_game_account_mut(&account).unwrap().players()[0].cash(), 99);
+
+            let mut lamports = 0;
+            let mut data = buffer.clone();
+            let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &other_owner, false, 0);
+            assert_eq!(load_game_account_mut(&account).unwrap_err(), ProgramError::IncorrectProgramId);
+
+            let mut lamports = 0;
+            let mut data = vec![0u8; GameAccount::LEN - 1];
+            let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, 0);
+            assert_eq!(load_game_account_mut(&account).unwrap_err(), ProgramError::InvalidAccountData);
+
+            let mut lamports = 0;
+            let mut data = vec![0u8; GameAccount::LEN];
+            let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, 0);
+            assert_eq!(load_game_account_mut(&account).unwrap_err(), MonopolyError::InvalidAccountType.into());
+
+            let mut lamports = 0;
+            let mut data = vec![0u8; GameAccount::LEN];
+            GameAccount { is_initialized: false, game: game_account.game.clone() }.pack_into_slice(&mut data);
+            let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, 0);
+            assert_eq!(load_game_account_mut(&account).unwrap_err(), ProgramError::UninitializedAccount);
+
+            // A tile owned by a seat nobody has taken
+            let mut lamports = 0;
+            let mut data = buffer.clone();
+            bytemuck::from_bytes_mut::<GameAccountData>(&mut data).tiles[1].set_owner(Some(3));
+            let account = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program_id, false, 0);
+            assert_eq!(load_game_account_mut(&account).unwrap_err(), ProgramError::InvalidAccountData);
+        }
+    }
+
+    #[test]
+    fn test_account_header() {
+        let request = RandomnessRequest {
+            is_initialized: true,
+            game: Pubkey::new_unique(),
+            nonce: 3,
+            fulfilled: true,
+            output: [9; 32],
+        };
+        let mut packed = vec![0u8; RandomnessRequest::LEN];
+        request.pack_into_slice(&mut packed);
+
+        // Test case 1: Every account starts with its discriminator and the current version
+        {
+            assert_eq!(&packed[..8], b"MONORAND");
+            assert_eq!(packed[8], ACCOUNT_VERSION);
+            assert_eq!(packed[ACCOUNT_HEADER_LEN], 1);
+            assert_eq!(RandomnessRequest::unpack(&packed).unwrap(), request);
+
+            let kinds = [
+                AccountKind::Game,
+                AccountKind::Player,
+                AccountKind::Property,
+                AccountKind::CardDeck,
+                AccountKind::TradeOffer,
+                AccountKind::RandomnessRequest,
+            ];
+            for (i, kind) in kinds.iter().enumerate() {
+                let mut kind_packed = [0u8; AccountKind::LEN];
+                kind.pack_into_slice(&mut kind_packed);
+                assert_eq!(kind_packed[0], i as u8);
+                assert_eq!(&AccountKind::unpack_from_slice(&kind_packed).unwrap(), kind);
+                assert_eq!(kinds.iter().filter(|other| other.discriminator() == kind.discriminator()).count(), 1);
+            }
+            assert_eq!(AccountKind::unpack_from_slice(&[6]).unwrap_err(), ProgramError::InvalidAccountData);
+        }
+
+        // Test case 2: Another account type's data is refused
+        {
+            let mut other = packed.clone();
+            other[..8].copy_from_slice(&AccountKind::TradeOffer.discriminator());
+            let result = RandomnessRequest::unpack(&other);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidAccountType.into());
+        }
+
+        // Test case 3: Other layout versions are refused
+        {
+            let mut other = packed.clone();
+            other[8] = ACCOUNT_VERSION + 1;
+            let result = RandomnessRequest::unpack(&other);
+            assert_eq!(result.unwrap_err(), MonopolyError::UnsupportedAccountVersion.into());
+
+            // A version 1 account padded to the new size still has no header
+            let mut v1 = vec![0u8; RandomnessRequest::LEN];
+            v1[..RandomnessRequest::LEN - ACCOUNT_HEADER_LEN].copy_from_slice(&packed[ACCOUNT_HEADER_LEN..]);
+            let result = RandomnessRequest::unpack(&v1);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidAccountType.into());
+        }
+
+        // Test case 4: A freshly created account is blank and only initialization paths accept it
+        {
+            let fresh = vec![0u8; RandomnessRequest::LEN];
+            assert!(is_blank_account(&fresh));
+            assert!(!is_blank_account(&packed));
+            assert!(!is_blank_account(&fresh[..ACCOUNT_HEADER_LEN - 1]));
+            assert_eq!(RandomnessRequest::unpack_unchecked(&fresh).unwrap_err(), MonopolyError::InvalidAccountType.into());
+            assert_eq!(RandomnessRequest::unpack(&fresh).unwrap_err(), MonopolyError::InvalidAccountType.into());
         }
     }
 
@@ -178,11 +518,18 @@ mod tests {
                 is_initialized: true,
                 player: Player {
                     name: String::from("Test Player"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 1500,
                     position: 10,
                     jail_turns: 2,
                     properties: vec![1, 3, 5, 7],
                     get_out_of_jail_cards: 1,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 game: Pubkey::new_unique(),
             };
@@ -207,11 +554,18 @@ mod tests {
                 is_initialized: false,
                 player: Player {
                     name: String::from(""),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 0,
                     position: 0,
                     jail_turns: 0,
                     properties: vec![],
                     get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 game: Pubkey::new_unique(),
             };
@@ -220,7 +574,7 @@ mod tests {
             uninitialized_account.pack_into_slice(&mut buffer);
 
             let unpacked = PlayerAccount::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.is_initialized, false);
+            assert!(!unpacked.is_initialized);
             assert_eq!(unpacked.player.name, "");
             assert_eq!(unpacked.player.cash, 0);
             assert_eq!(unpacked.player.properties.len(), 0);
@@ -232,19 +586,26 @@ mod tests {
                 is_initialized: true,
                 player: Player {
                     name: String::from("Test Player"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 1500,
                     position: 0,
                     jail_turns: 0,
                     properties: vec![],
                     get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 game: Pubkey::new_unique(),
             };
 
             let mut small_buffer = vec![0u8; 10]; // Too small
-            let result = std::panic::catch_unwind(|| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 player_account.pack_into_slice(&mut small_buffer);
-            });
+            }));
             assert!(result.is_err());
         }
 
@@ -254,11 +615,18 @@ mod tests {
                 is_initialized: true,
                 player: Player {
                     name: String::from("Maximum Player Name Test"),
-                    cash: u32::MAX,
+                    wallet: Pubkey::default(),
+                    delegate: None,
+                    cash: u64::MAX,
                     position: u8::MAX,
                     jail_turns: u8::MAX,
-                    properties: (0..40).collect(), // Max properties
+                    properties: (0..MAX_PLAYER_PROPERTIES as u8).collect(), // Max properties
                     get_out_of_jail_cards: u8::MAX,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 game: Pubkey::new_unique(),
             };
@@ -267,11 +635,27 @@ mod tests {
             max_player_account.pack_into_slice(&mut buffer);
 
             let unpacked = PlayerAccount::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.player.cash, u32::MAX);
+            assert_eq!(unpacked.player.cash, u64::MAX);
             assert_eq!(unpacked.player.position, u8::MAX);
             assert_eq!(unpacked.player.jail_turns, u8::MAX);
             assert_eq!(unpacked.player.get_out_of_jail_cards, u8::MAX);
-            assert_eq!(unpacked.player.properties.len(), 40);
+            assert_eq!(unpacked.player.properties.len(), MAX_PLAYER_PROPERTIES);
+        }
+    }
+
+    fn sample_property(name: &str, cost: u64, houses: u8, owner: Option<u8>, mortgaged: bool) -> Property {
+        Property {
+            name: String::from(name),
+            color: Color::DarkBlue,
+            kind: TileKind::Street,
+            cost,
+            rent: vec![cost / 8],
+            house_cost: cost / 2,
+            hotel_cost: cost / 2,
+            house_rent: vec![cost / 2; 5],
+            owner,
+            houses,
+            mortgaged,
         }
     }
 
@@ -283,15 +667,7 @@ mod tests {
         {
             let property_account = PropertyAccount {
                 is_initialized: true,
-                property: Property {
-                    name: String::from("Boardwalk"),
-                    price: 400,
-                    rent: 50,
-                    house_cost: 200,
-                    houses: 3,
-                    owner: Some(Pubkey::new_unique()),
-                    mortgaged: false,
-                },
+                property: sample_property("Boardwalk", 400, 3, Some(2), false),
                 game: Pubkey::new_unique(),
             };
 
@@ -301,9 +677,10 @@ mod tests {
             let unpacked = PropertyAccount::unpack_from_slice(&buffer).unwrap();
             assert_eq!(unpacked.is_initialized, property_account.is_initialized);
             assert_eq!(unpacked.property.name, property_account.property.name);
-            assert_eq!(unpacked.property.price, property_account.property.price);
+            assert_eq!(unpacked.property.cost, property_account.property.cost);
             assert_eq!(unpacked.property.rent, property_account.property.rent);
             assert_eq!(unpacked.property.house_cost, property_account.property.house_cost);
+            assert_eq!(unpacked.property.house_rent, property_account.property.house_rent);
             assert_eq!(unpacked.property.houses, property_account.property.houses);
             assert_eq!(unpacked.property.owner, property_account.property.owner);
             assert_eq!(unpacked.property.mortgaged, property_account.property.mortgaged);
@@ -314,15 +691,7 @@ mod tests {
         {
             let uninitialized_account = PropertyAccount {
                 is_initialized: false,
-                property: Property {
-                    name: String::from(""),
-                    price: 0,
-                    rent: 0,
-                    house_cost: 0,
-                    houses: 0,
-                    owner: None,
-                    mortgaged: false,
-                },
+                property: sample_property("", 0, 0, None, false),
                 game: Pubkey::new_unique(),
             };
 
@@ -330,10 +699,10 @@ mod tests {
             uninitialized_account.pack_into_slice(&mut buffer);
 
             let unpacked = PropertyAccount::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.is_initialized, false);
+            assert!(!unpacked.is_initialized);
             assert_eq!(unpacked.property.name, "");
-            assert_eq!(unpacked.property.price, 0);
-            assert_eq!(unpacked.property.rent, 0);
+            assert_eq!(unpacked.property.cost, 0);
+            assert_eq!(unpacked.property.rent, vec![0]);
             assert_eq!(unpacked.property.owner, None);
         }
 
@@ -341,50 +710,38 @@ mod tests {
         {
             let property_account = PropertyAccount {
                 is_initialized: true,
-                property: Property {
-                    name: String::from("Test Property"),
-                    price: 200,
-                    rent: 10,
-                    house_cost: 100,
-                    houses: 0,
-                    owner: None,
-                    mortgaged: false,
-                },
+                property: sample_property("Test Property", 200, 0, None, false),
                 game: Pubkey::new_unique(),
             };
 
             let mut small_buffer = vec![0u8; 10]; // Too small
-            let result = std::panic::catch_unwind(|| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 property_account.pack_into_slice(&mut small_buffer);
-            });
+            }));
             assert!(result.is_err());
         }
 
         // Test case 4: Maximum values
         {
+            let mut property = sample_property(&"N".repeat(MAX_NAME_LEN), u64::MAX, u8::MAX, Some(MAX_PLAYERS - 1), true);
+            property.rent = vec![u64::MAX; MAX_RENT_LEVELS];
+            property.house_rent = vec![u64::MAX; MAX_HOUSE_RENT_LEVELS];
             let max_property_account = PropertyAccount {
                 is_initialized: true,
-                property: Property {
-                    name: String::from("Maximum Property Name Test"),
-                    price: u32::MAX,
-                    rent: u32::MAX,
-                    house_cost: u32::MAX,
-                    houses: u8::MAX,
-                    owner: Some(Pubkey::new_unique()),
-                    mortgaged: true,
-                },
+                property,
                 game: Pubkey::new_unique(),
             };
 
             let mut buffer = vec![0u8; PropertyAccount::LEN];
             max_property_account.pack_into_slice(&mut buffer);
+            let short = std::panic::catch_unwind(|| max_property_account.pack_into_slice(&mut vec![0u8; PropertyAccount::LEN - 1]));
+            assert!(short.is_err(), "PropertyAccount::LEN has slack");
 
             let unpacked = PropertyAccount::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.property.price, u32::MAX);
-            assert_eq!(unpacked.property.rent, u32::MAX);
-            assert_eq!(unpacked.property.house_cost, u32::MAX);
+            assert_eq!(unpacked.property.cost, u64::MAX);
+            assert_eq!(unpacked.property.house_rent, vec![u64::MAX; MAX_HOUSE_RENT_LEVELS]);
             assert_eq!(unpacked.property.houses, u8::MAX);
-            assert_eq!(unpacked.property.mortgaged, true);
+            assert!(unpacked.property.mortgaged);
             assert!(unpacked.property.owner.is_some());
         }
     }
@@ -398,17 +755,9 @@ mod tests {
             let card_deck = CardDeck {
                 is_initialized: true,
                 deck_type: DeckType::Chance,
-                cards: vec![
-                    Card {
-                        description: String::from("Advance to GO"),
-                        action: String::from("MOVE_TO:0"),
-                    },
-                    Card {
-                        description: String::from("Go to Jail"),
-                        action: String::from("GO_TO_JAIL"),
-                    },
-                ],
+                cards: vec![Card::Move(0), Card::GoToJail],
                 current_card_index: 1,
+                jail_card_held: false,
                 game: Pubkey::new_unique(),
             };
 
@@ -419,10 +768,7 @@ mod tests {
             assert_eq!(unpacked.is_initialized, card_deck.is_initialized);
             assert_eq!(unpacked.deck_type, card_deck.deck_type);
             assert_eq!(unpacked.cards.len(), card_deck.cards.len());
-            for (unpacked_card, original_card) in unpacked.cards.iter().zip(card_deck.cards.iter()) {
-                assert_eq!(unpacked_card.description, original_card.description);
-                assert_eq!(unpacked_card.action, original_card.action);
-            }
+            assert_eq!(unpacked.cards, card_deck.cards);
             assert_eq!(unpacked.current_card_index, card_deck.current_card_index);
             assert_eq!(unpacked.game, card_deck.game);
         }
@@ -434,6 +780,7 @@ mod tests {
                 deck_type: DeckType::CommunityChest,
                 cards: vec![],
                 current_card_index: 0,
+                jail_card_held: false,
                 game: Pubkey::new_unique(),
             };
 
@@ -441,7 +788,7 @@ mod tests {
             uninitialized_deck.pack_into_slice(&mut buffer);
 
             let unpacked = CardDeck::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.is_initialized, false);
+            assert!(!unpacked.is_initialized);
             assert_eq!(unpacked.cards.len(), 0);
             assert_eq!(unpacked.current_card_index, 0);
         }
@@ -451,41 +798,39 @@ mod tests {
             let card_deck = CardDeck {
                 is_initialized: true,
                 deck_type: DeckType::Chance,
-                cards: vec![Card {
-                    description: String::from("Test Card"),
-                    action: String::from("TEST_ACTION"),
-                }],
+                cards: vec![Card::PayMoney(15)],
                 current_card_index: 0,
+                jail_card_held: false,
                 game: Pubkey::new_unique(),
             };
 
             let mut small_buffer = vec![0u8; 10]; // Too small
-            let result = std::panic::catch_unwind(|| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 card_deck.pack_into_slice(&mut small_buffer);
-            });
+            }));
             assert!(result.is_err());
         }
 
         // Test case 4: Maximum values
         {
-            let max_cards: Vec<Card> = (0..16).map(|i| Card {
-                description: format!("Max Card {}", i),
-                action: format!("ACTION_{}", i),
-            }).collect();
+            let max_cards: Vec<Card> = (0..MAX_DECK_CARDS as u64).map(|i| Card::PayPerHouseAndHotel { house: i, hotel: u64::MAX }).collect();
 
             let max_card_deck = CardDeck {
                 is_initialized: true,
                 deck_type: DeckType::Chance,
                 cards: max_cards,
                 current_card_index: u8::MAX,
+                jail_card_held: false,
                 game: Pubkey::new_unique(),
             };
 
             let mut buffer = vec![0u8; CardDeck::LEN];
             max_card_deck.pack_into_slice(&mut buffer);
+            let short = std::panic::catch_unwind(|| max_card_deck.pack_into_slice(&mut vec![0u8; CardDeck::LEN - 1]));
+            assert!(short.is_err(), "CardDeck::LEN has slack");
 
             let unpacked = CardDeck::unpack_from_slice(&buffer).unwrap();
-            assert_eq!(unpacked.cards.len(), 16);
+            assert_eq!(unpacked.cards.len(), MAX_DECK_CARDS);
             assert_eq!(unpacked.current_card_index, u8::MAX);
         }
 
@@ -496,6 +841,7 @@ mod tests {
                 deck_type: DeckType::Chance,
                 cards: vec![],
                 current_card_index: 0,
+                jail_card_held: false,
                 game: Pubkey::new_unique(),
             };
 
@@ -509,6 +855,7 @@ mod tests {
                 deck_type: DeckType::CommunityChest,
                 cards: vec![],
                 current_card_index: 0,
+                jail_card_held: false,
                 game: Pubkey::new_unique(),
             };
 
@@ -518,6 +865,136 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_card_deck_round_trip() {
+        use crate::board::{create_chance_cards, create_community_chest};
+
+        let card_deck = CardDeck {
+            is_initialized: true,
+            cards: create_chance_cards(),
+            game: Pubkey::new_unique(),
+            deck_type: DeckType::Chance,
+            current_card_index: 7,
+            jail_card_held: true,
+        };
+
+        let mut buffer = vec![0u8; CardDeck::LEN];
+        card_deck.pack_into_slice(&mut buffer);
+        let unpacked = CardDeck::unpack_from_slice(&buffer).unwrap();
+
+        assert!(unpacked.is_initialized);
+        assert_eq!(unpacked.game, card_deck.game);
+        assert_eq!(unpacked.deck_type, DeckType::Chance);
+        assert_eq!(unpacked.cards, card_deck.cards);
+        assert_eq!(unpacked.current_card_index, 7);
+        assert!(unpacked.jail_card_held);
+        assert!(unpacked.cards.iter().any(|card| matches!(card, Card::GetOutOfJail)));
+        assert!(unpacked.cards.iter().any(|card| matches!(card, Card::GoToJail)));
+        assert!(unpacked.cards.iter().any(|card| matches!(card, Card::Move(5))));
+        assert!(unpacked.cards.iter().any(|card| matches!(card, Card::AdvanceToNearest(TileKind::Utility))));
+
+        // Every card kind survives a round trip through the deck layout
+        let card_deck = CardDeck {
+            is_initialized: true,
+            cards: create_community_chest(),
+            game: Pubkey::new_unique(),
+            deck_type: DeckType::CommunityChest,
+            current_card_index: 0,
+            jail_card_held: false,
+        };
+        let mut buffer = vec![0u8; CardDeck::LEN];
+        card_deck.pack_into_slice(&mut buffer);
+        let unpacked = CardDeck::unpack_from_slice(&buffer).unwrap();
+        assert_eq!(unpacked.cards, card_deck.cards);
+        assert_eq!(unpacked.deck_type, DeckType::CommunityChest);
+    }
+
+    #[test]
+    fn test_card_deck_draw_pile() {
+        use crate::board::create_chance_cards;
+        use crate::utils::SeededRandomness;
+
+        let source = SeededRandomness { seed: 3 };
+        let mut card_deck = CardDeck {
+            is_initialized: true,
+            cards: create_chance_cards(),
+            game: Pubkey::new_unique(),
+            deck_type: DeckType::Chance,
+            current_card_index: 0,
+            jail_card_held: false,
+        };
+        card_deck.shuffle(&source, 0);
+
+        // Test case 1: A full pass draws every card exactly once, in the shuffled order
+        let order = card_deck.cards.clone();
+        let drawn: Vec<Card> = std::iter::from_fn(|| card_deck.next_card()).collect();
+        assert_eq!(drawn, order);
+        assert_eq!(card_deck.current_card_index, 16);
+        assert!(card_deck.jail_card_held);
+        assert_eq!(card_deck.next_card(), None);
+
+        // Test case 2: A reshuffle keeps the held jail card out of the pile
+        card_deck.shuffle(&source, 1);
+        assert_eq!(card_deck.current_card_index, 0);
+        let drawn: Vec<Card> = std::iter::from_fn(|| card_deck.next_card()).collect();
+        assert_eq!(drawn.len(), 15);
+        assert!(!drawn.iter().any(|card| matches!(card, Card::GetOutOfJail)));
+
+        // Test case 3: Once returned, the jail card is drawn again after the next shuffle
+        card_deck.jail_card_held = false;
+        card_deck.shuffle(&source, 2);
+        let drawn: Vec<Card> = std::iter::from_fn(|| card_deck.next_card()).collect();
+        assert_eq!(drawn.len(), 16);
+        assert!(drawn.iter().any(|card| matches!(card, Card::GetOutOfJail)));
+
+        // Test case 4: The deck composition never changes
+        let sorted = |cards: &[Card]| -> Vec<String> {
+            let mut names: Vec<String> = cards.iter().map(|card| format!("{:?}", card)).collect();
+            names.sort();
+            names
+        };
+        assert_eq!(sorted(&card_deck.cards), sorted(&create_chance_cards()));
+    }
+
+    #[test]
+    fn test_trade_offer_pack() {
+        let offer = TradeOffer {
+            is_initialized: true,
+            game: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            counterparty: Pubkey::new_unique(),
+            offered: TradeTerms {
+                properties: vec![1, 3],
+                cash: 0,
+                jail_cards: 1,
+            },
+            requested: TradeTerms {
+                properties: vec![39],
+                cash: 150,
+                jail_cards: 0,
+            },
+        };
+
+        let mut buffer = vec![0u8; TradeOffer::LEN];
+        offer.pack_into_slice(&mut buffer);
+        assert_eq!(TradeOffer::unpack_from_slice(&buffer).unwrap(), offer);
+    }
+
+    #[test]
+    fn test_randomness_request_pack() {
+        let request = RandomnessRequest {
+            is_initialized: true,
+            game: Pubkey::new_unique(),
+            nonce: u64::MAX,
+            fulfilled: true,
+            output: [5; 32],
+        };
+
+        let mut buffer = vec![0u8; RandomnessRequest::LEN];
+        request.pack_into_slice(&mut buffer);
+        assert_eq!(RandomnessRequest::unpack_from_slice(&buffer).unwrap(), request);
+    }
+
     #[test]
     fn test_account_validation() {
         // Test GameAccount initialization
@@ -530,6 +1007,28 @@ mod tests {
                     current_player: 0,
                     free_parking: 0,
                     initialized: true,
+                    creator: Pubkey::default(),
+                    status: GameStatus::Started,
+                    max_players: 8,
+                    auction: None,
+                    debt: None,
+                    last_roll: 0,
+                    houses_remaining: BANK_HOUSES,
+                    hotels_remaining: BANK_HOTELS,
+                    doubles_rolled: 0,
+                    dice_round: None,
+                    rolled_dice: None,
+                    randomness: RandomnessKind::Seeded { seed: 0 },
+                    randomness_nonce: 0,
+                    last_dice: (0, 0),
+                    last_doubles: false,
+                    turn_phase: TurnPhase::AwaitingRoll,
+                    pending_action: None,
+                    rules: GameRules::default(),
+                    auction_queue: vec![],
+                    randomness_request: None,
+                    community_chest_deck: None,
+                    chance_deck: None,
                 },
             };
             assert!(game_account.is_initialized());
@@ -542,9 +1041,33 @@ mod tests {
                     current_player: 0,
                     free_parking: 0,
                     initialized: false,
+                    creator: Pubkey::default(),
+                    status: GameStatus::Started,
+                    max_players: 8,
+                    auction: None,
+                    debt: None,
+                    last_roll: 0,
+                    houses_remaining: BANK_HOUSES,
+                    hotels_remaining: BANK_HOTELS,
+                    doubles_rolled: 0,
+                    dice_round: None,
+                    rolled_dice: None,
+                    randomness: RandomnessKind::Seeded { seed: 0 },
+                    randomness_nonce: 0,
+                    last_dice: (0, 0),
+                    last_doubles: false,
+                    turn_phase: TurnPhase::AwaitingRoll,
+                    pending_action: None,
+                    rules: GameRules::default(),
+                    auction_queue: vec![],
+                    randomness_request: None,
+                    community_chest_deck: None,
+                    chance_deck: None,
                 },
             };
             assert!(!uninitialized_account.is_initialized());
+            assert!(validate_packed(&game_account, validate_game_account).is_ok());
+            assert!(validate_packed(&uninitialized_account, validate_game_account).is_err());
         }
 
         // Test PlayerAccount initialization
@@ -553,11 +1076,18 @@ mod tests {
                 is_initialized: true,
                 player: Player {
                     name: String::from("Test Player"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 1500,
                     position: 0,
                     jail_turns: 0,
                     properties: vec![],
                     get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 game: Pubkey::new_unique(),
             };
@@ -567,48 +1097,43 @@ mod tests {
                 is_initialized: false,
                 player: Player {
                     name: String::from(""),
+                    wallet: Pubkey::default(),
+                    delegate: None,
                     cash: 0,
                     position: 0,
                     jail_turns: 0,
                     properties: vec![],
                     get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
                 },
                 game: Pubkey::new_unique(),
             };
             assert!(!uninitialized_player.is_initialized());
+            assert!(validate_packed(&player_account, validate_player_account).is_ok());
+            assert!(validate_packed(&uninitialized_player, validate_player_account).is_err());
         }
 
         // Test PropertyAccount initialization
         {
             let property_account = PropertyAccount {
                 is_initialized: true,
-                property: Property {
-                    name: String::from("Test Property"),
-                    price: 200,
-                    rent: 10,
-                    house_cost: 100,
-                    houses: 0,
-                    owner: None,
-                    mortgaged: false,
-                },
+                property: sample_property("Test Property", 200, 0, None, false),
                 game: Pubkey::new_unique(),
             };
             assert!(property_account.is_initialized());
 
             let uninitialized_property = PropertyAccount {
                 is_initialized: false,
-                property: Property {
-                    name: String::from(""),
-                    price: 0,
-                    rent: 0,
-                    house_cost: 0,
-                    houses: 0,
-                    owner: None,
-                    mortgaged: false,
-                },
+                property: sample_property("", 0, 0, None, false),
                 game: Pubkey::new_unique(),
             };
             assert!(!uninitialized_property.is_initialized());
+            assert!(validate_packed(&property_account, validate_property_account).is_ok());
+            assert!(validate_packed(&uninitialized_property, validate_property_account).is_err());
         }
 
         // Test CardDeck initialization
@@ -618,6 +1143,8 @@ mod tests {
                 cards: vec![],
                 game: Pubkey::new_unique(),
                 deck_type: DeckType::Chance,
+                current_card_index: 0,
+                jail_card_held: false,
             };
             assert!(card_deck.is_initialized());
 
@@ -626,29 +1153,195 @@ mod tests {
                 cards: vec![],
                 game: Pubkey::new_unique(),
                 deck_type: DeckType::CommunityChest,
+                current_card_index: 0,
+                jail_card_held: false,
             };
             assert!(!uninitialized_deck.is_initialized());
+            assert!(validate_packed(&card_deck, validate_card_deck).is_ok());
+            assert!(validate_packed(&uninitialized_deck, validate_card_deck).is_err());
         }
+    }
+
+    mod fuzz {
+        use super::*;
+        use crate::board::{create_chance_cards, create_community_chest};
+        use proptest::prelude::*;
+
+        fn with_header(kind: AccountKind, body: &[u8]) -> Vec<u8> {
+            let mut data = vec![0u8; ACCOUNT_HEADER_LEN];
+            pack_header(&mut Writer::new(&mut data), kind);
+            data.extend_from_slice(body);
+            data
+        }
+
+        // With the right header, bad data can only ever be reported as such; it never panics
+        fn check_unpack<T: Pack>(kind: AccountKind, body: &[u8]) -> Result<(), TestCaseError> {
+            if let Err(err) = T::unpack_from_slice(&with_header(kind, body)) {
+                prop_assert_eq!(err, ProgramError::InvalidAccountData);
+            }
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn fuzz_unpack_arbitrary_bytes(body in proptest::collection::vec(any::<u8>(), 0..GameAccount::LEN + 64)) {
+                check_unpack::<GameAccount>(AccountKind::Game, &body)?;
+                check_unpack::<PlayerAccount>(AccountKind::Player, &body)?;
+                check_unpack::<PropertyAccount>(AccountKind::Property, &body)?;
+                check_unpack::<CardDeck>(AccountKind::CardDeck, &body)?;
+                check_unpack::<TradeOffer>(AccountKind::TradeOffer, &body)?;
+                check_unpack::<RandomnessRequest>(AccountKind::RandomnessRequest, &body)?;
+
+                // Raw bytes may fail the header check instead, but must not panic either
+                let _ = GameAccount::unpack_from_slice(&body);
+                let _ = CardDeck::unpack_from_slice(&body);
+                let _ = AccountKind::unpack_from_slice(&body);
+            }
+
+            #[test]
+            fn fuzz_card_deck_round_trip(
+                cards in proptest::collection::vec(
+                    proptest::sample::select([create_chance_cards(), create_community_chest()].concat()),
+                    0..=MAX_DECK_CARDS,
+                ),
+                current_card_index in any::<u8>(),
+                jail_card_held in any::<bool>(),
+            ) {
+                let deck = CardDeck {
+                    is_initialized: true,
+                    cards,
+                    game: Pubkey::new_unique(),
+                    deck_type: DeckType::Chance,
+                    current_card_index,
+                    jail_card_held,
+                };
+                let mut packed = vec![0u8; CardDeck::LEN];
+                deck.pack_into_slice(&mut packed);
+
+                let unpacked = CardDeck::unpack_from_slice(&packed).unwrap();
+                prop_assert_eq!(&unpacked.cards, &deck.cards);
+                prop_assert_eq!(unpacked.current_card_index, deck.current_card_index);
+                prop_assert_eq!(unpacked.jail_card_held, deck.jail_card_held);
+
+                // Header, flag, card count and data length, cards, game, deck type and pile state
+                let data_len = u32::from_le_bytes(packed[ACCOUNT_HEADER_LEN + 5..ACCOUNT_HEADER_LEN + 9].try_into().unwrap()) as usize;
+                let len = ACCOUNT_HEADER_LEN + 1 + 8 + data_len + 32 + 3;
+                for cut in 0..len {
+                    prop_assert_eq!(CardDeck::unpack_from_slice(&packed[..cut]).err(), Some(ProgramError::InvalidAccountData));
+                }
+
+                // A data length running past the end of the account is refused
+                packed[ACCOUNT_HEADER_LEN + 5..ACCOUNT_HEADER_LEN + 9].copy_from_slice(&u32::MAX.to_le_bytes());
+                prop_assert_eq!(CardDeck::unpack_from_slice(&packed).err(), Some(ProgramError::InvalidAccountData));
+            }
+
+            #[test]
+            fn fuzz_randomness_request_round_trip(nonce in any::<u64>(), fulfilled in any::<bool>(), output in any::<[u8; 32]>()) {
+                let request = RandomnessRequest { is_initialized: true, game: Pubkey::new_unique(), nonce, fulfilled, output };
+                let mut packed = vec![0u8; RandomnessRequest::LEN];
+                request.pack_into_slice(&mut packed);
+                prop_assert_eq!(RandomnessRequest::unpack_from_slice(&packed).unwrap(), request);
+                for cut in 0..RandomnessRequest::LEN {
+                    prop_assert_eq!(RandomnessRequest::unpack_from_slice(&packed[..cut]).err(), Some(ProgramError::InvalidAccountData));
+                }
+            }
+        }
+    }
+}
+
+/// Layout version this program reads and writes. Version 1 accounts had no header.
+pub const ACCOUNT_VERSION: u8 = 2;
+/// Bytes in front of every account: an 8-byte discriminator, then the layout version
+pub const ACCOUNT_HEADER_LEN: usize = 8 + 1;
+
+/// Kind of program account, named by the discriminator at the start of its data
+#[derive(Copy, Clone, PartialEq, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum AccountKind {
+    Game,
+    Player,
+    Property,
+    CardDeck,
+    TradeOffer,
+    RandomnessRequest,
+}
+
+impl AccountKind {
+    pub fn discriminator(&self) -> [u8; 8] {
+        match self {
+            AccountKind::Game => *b"MONOGAME",
+            AccountKind::Player => *b"MONOPLYR",
+            AccountKind::Property => *b"MONOPROP",
+            AccountKind::CardDeck => *b"MONODECK",
+            AccountKind::TradeOffer => *b"MONOTRAD",
+            AccountKind::RandomnessRequest => *b"MONORAND",
+        }
+    }
+
+    /// Size of the account under the current layout
+    pub fn account_len(&self) -> usize {
+        match self {
+            AccountKind::Game => GameAccount::LEN,
+            AccountKind::Player => PlayerAccount::LEN,
+            AccountKind::Property => PropertyAccount::LEN,
+            AccountKind::CardDeck => CardDeck::LEN,
+            AccountKind::TradeOffer => TradeOffer::LEN,
+            AccountKind::RandomnessRequest => RandomnessRequest::LEN,
+        }
+    }
+}
 
-        // Test validation functions
-        // Test GameAccount validation
-        assert!(validate_game_account(&game_account).is_ok());
-        assert!(validate_game_account(&uninitialized_account).is_err());
+impl Sealed for AccountKind {}
 
-        // Test PlayerAccount validation
-        assert!(validate_player_account(&player_account).is_ok());
-        assert!(validate_player_account(&uninitialized_player).is_err());
+impl Pack for AccountKind {
+    const LEN: usize = 1;
 
-        // Test PropertyAccount validation
-        assert!(validate_property_account(&property_account).is_ok());
-        assert!(validate_property_account(&uninitialized_property).is_err());
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        Writer::new(dst).write_u8(match self {
+            AccountKind::Game => 0,
+            AccountKind::Player => 1,
+            AccountKind::Property => 2,
+            AccountKind::CardDeck => 3,
+            AccountKind::TradeOffer => 4,
+            AccountKind::RandomnessRequest => 5,
+        });
+    }
 
-        // Test CardDeck validation
-        assert!(validate_card_deck(&card_deck).is_ok());
-        assert!(validate_card_deck(&uninitialized_deck).is_err());
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Ok(match Reader::new(src).read_u8()? {
+            0 => AccountKind::Game,
+            1 => AccountKind::Player,
+            2 => AccountKind::Property,
+            3 => AccountKind::CardDeck,
+            4 => AccountKind::TradeOffer,
+            5 => AccountKind::RandomnessRequest,
+            _ => return Err(ProgramError::InvalidAccountData),
+        })
     }
 }
 
+fn pack_header(writer: &mut Writer, kind: AccountKind) {
+    writer.write_bytes(&kind.discriminator());
+    writer.write_u8(ACCOUNT_VERSION);
+}
+
+// A blank header is refused here; initialization paths check `is_blank_account` first
+fn check_header(reader: &mut Reader, kind: AccountKind) -> Result<(), ProgramError> {
+    let discriminator = reader.read_array::<8>()?;
+    let version = reader.read_u8()?;
+    if discriminator != kind.discriminator() {
+        return Err(MonopolyError::InvalidAccountType.into());
+    }
+    if version != ACCOUNT_VERSION {
+        return Err(MonopolyError::UnsupportedAccountVersion.into());
+    }
+    Ok(())
+}
+
+/// True for a freshly created account nothing has been written to yet, whose header is all zeros
+pub fn is_blank_account(data: &[u8]) -> bool {
+    data.get(..ACCOUNT_HEADER_LEN).is_some_and(|header| header.iter().all(|&byte| byte == 0))
+}
+
 #[derive(Clone, Debug)]
 pub struct GameAccount {
     pub is_initialized: bool,
@@ -656,20 +1349,20 @@ pub struct GameAccount {
 }
 
 impl Pack for GameAccount {
-    const LEN: usize = 1 + Game::LEN; // is_initialized + game
+    const LEN: usize = ACCOUNT_HEADER_LEN + 1 + Game::LEN; // header + is_initialized + game
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut offset = 0;
-        dst[offset] = self.is_initialized as u8;
-        offset += 1;
-        self.game.pack_into_slice(&mut dst[offset..]);
+        let mut writer = Writer::new(dst);
+        pack_header(&mut writer, AccountKind::Game);
+        writer.write_bool(self.is_initialized);
+        writer.write_pack(&self.game);
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut offset = 0;
-        let is_initialized = src[offset] != 0;
-        offset += 1;
-        let game = Game::unpack_from_slice(&src[offset..])?;
+        let mut reader = Reader::new(src);
+        check_header(&mut reader, AccountKind::Game)?;
+        let is_initialized = reader.read_bool()?;
+        let game = reader.read_pack()?;
         Ok(GameAccount {
             is_initialized,
             game,
@@ -679,12 +1372,400 @@ impl Pack for GameAccount {
 
 impl Sealed for GameAccount {}
 
+// A game account must be creatable in a single system program call
+const _: () = assert!(GameAccount::LEN <= MAX_PERMITTED_DATA_INCREASE);
+
 impl IsInitialized for GameAccount {
     fn is_initialized(&self) -> bool {
         self.is_initialized
     }
 }
 
+/// Zero-copy view of one seat in a game account, laid out byte for byte like `Player`'s packing
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct PlayerData {
+    pub name_len: u8,
+    pub name: [u8; MAX_NAME_LEN],
+    pub wallet: Pubkey,
+    pub has_delegate: u8,
+    pub delegate: Pubkey,
+    pub cash: [u8; 8],
+    pub position: u8,
+    pub jail_turns: u8,
+    pub properties_len: u8,
+    pub properties: [u8; MAX_PLAYER_PROPERTIES],
+    pub get_out_of_jail_cards: u8,
+    pub community_chest_jail_card: u8,
+    pub chance_jail_card: u8,
+    pub eliminated: u8,
+    pub has_dice_commitment: u8,
+    pub dice_commitment: [u8; 32],
+    pub has_dice_secret: u8,
+    pub dice_secret: [u8; 32],
+}
+
+impl PlayerData {
+    pub fn delegate(&self) -> Option<Pubkey> {
+        (self.has_delegate != 0).then_some(self.delegate)
+    }
+
+    pub fn set_delegate(&mut self, delegate: Option<Pubkey>) {
+        self.has_delegate = delegate.is_some() as u8;
+        self.delegate = delegate.unwrap_or_default();
+    }
+
+    pub fn dice_commitment(&self) -> Option<[u8; 32]> {
+        (self.has_dice_commitment != 0).then_some(self.dice_commitment)
+    }
+
+    pub fn set_dice_commitment(&mut self, commitment: Option<[u8; 32]>) {
+        self.has_dice_commitment = commitment.is_some() as u8;
+        self.dice_commitment = commitment.unwrap_or_default();
+    }
+
+    pub fn dice_secret(&self) -> Option<[u8; 32]> {
+        (self.has_dice_secret != 0).then_some(self.dice_secret)
+    }
+
+    pub fn set_dice_secret(&mut self, secret: Option<[u8; 32]>) {
+        self.has_dice_secret = secret.is_some() as u8;
+        self.dice_secret = secret.unwrap_or_default();
+    }
+}
+
+impl PlayerState for PlayerData {
+    fn name(&self) -> &str {
+        std::str::from_utf8(&self.name[..(self.name_len as usize).min(MAX_NAME_LEN)]).unwrap_or_default()
+    }
+
+    fn cash(&self) -> u64 {
+        u64::from_le_bytes(self.cash)
+    }
+
+    fn set_cash(&mut self, cash: u64) {
+        self.cash = cash.to_le_bytes();
+    }
+
+    fn position(&self) -> u8 {
+        self.position
+    }
+
+    fn set_position(&mut self, position: u8) {
+        self.position = position;
+    }
+
+    fn jail_turns(&self) -> u8 {
+        self.jail_turns
+    }
+
+    fn set_jail_turns(&mut self, jail_turns: u8) {
+        self.jail_turns = jail_turns;
+    }
+
+    fn is_eliminated(&self) -> bool {
+        self.eliminated != 0
+    }
+
+    fn push_property(&mut self, property_index: u8) -> ProgramResult {
+        let len = self.properties_len as usize;
+        if len >= MAX_PLAYER_PROPERTIES {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.properties[len] = property_index;
+        self.properties_len += 1;
+        Ok(())
+    }
+
+    fn is_controlled_by(&self, key: &Pubkey) -> bool {
+        self.wallet == *key || self.delegate().as_ref() == Some(key)
+    }
+}
+
+/// Zero-copy view of one tile's mutable state, laid out like `TileState`'s packing
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TileData {
+    /// Seat index of the owner, or `u8::MAX` when unowned
+    pub owner: u8,
+    pub houses: u8,
+    pub mortgaged: u8,
+}
+
+impl TileData {
+    pub fn owner(&self) -> Option<u8> {
+        (self.owner != u8::MAX).then_some(self.owner)
+    }
+
+    pub fn set_owner(&mut self, owner: Option<u8>) {
+        self.owner = owner.unwrap_or(u8::MAX);
+    }
+}
+
+/// Zero-copy view of a whole game account, laid out byte for byte like `GameAccount`'s packing.
+/// Instructions that touch a few fields borrow this straight from the account data instead of
+/// unpacking and repacking the full `Game`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct GameAccountData {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: u8,
+    pub players_len: u8,
+    pub players: [PlayerData; MAX_PLAYERS as usize],
+    pub board_id: u8,
+    pub tiles: [TileData; BOARD_SIZE],
+    pub current_player: u8,
+    pub free_parking: [u8; 8],
+    pub initialized: u8,
+    pub creator: Pubkey,
+    pub status: u8,
+    pub max_players: u8,
+    pub auction: [u8; 1 + Auction::LEN],
+    pub debt: [u8; 1 + Debt::LEN],
+    pub last_roll: u8,
+    pub houses_remaining: u8,
+    pub hotels_remaining: u8,
+    pub doubles_rolled: u8,
+    pub dice_round: [u8; 1 + DiceRound::LEN],
+    pub rolled_dice: [u8; 3],
+    pub randomness: [u8; RandomnessKind::LEN],
+    pub randomness_nonce: [u8; 8],
+    pub last_dice: [u8; 2],
+    pub last_doubles: u8,
+    pub turn_phase: u8,
+    pub pending_action: [u8; 1 + PendingAction::LEN],
+    pub rules: [u8; GameRules::LEN],
+    pub auction_queue_len: u8,
+    pub auction_queue: [u8; MAX_PLAYER_PROPERTIES],
+    pub has_randomness_request: u8,
+    pub randomness_request: Pubkey,
+    pub has_community_chest_deck: u8,
+    pub community_chest_deck: Pubkey,
+    pub has_chance_deck: u8,
+    pub chance_deck: Pubkey,
+}
+
+// The view must cover exactly the packed layout
+const _: () = assert!(std::mem::size_of::<GameAccountData>() == GameAccount::LEN);
+
+impl GameAccountData {
+    pub fn has_rolled_dice(&self) -> bool {
+        self.rolled_dice[0] != 0
+    }
+
+    pub fn has_auction(&self) -> bool {
+        self.auction[0] != 0
+    }
+
+    pub fn debt(&self) -> Result<Option<Debt>, ProgramError> {
+        read_option(&self.debt)
+    }
+
+    pub fn dice_round(&self) -> Result<Option<DiceRound>, ProgramError> {
+        read_option(&self.dice_round)
+    }
+
+    pub fn set_dice_round(&mut self, dice_round: Option<DiceRound>) {
+        write_option(&mut self.dice_round, dice_round.as_ref());
+    }
+
+    pub fn rolled_dice(&self) -> Option<(u8, u8)> {
+        self.has_rolled_dice().then_some((self.rolled_dice[1], self.rolled_dice[2]))
+    }
+
+    pub fn pending_action(&self) -> Result<Option<PendingAction>, ProgramError> {
+        read_option(&self.pending_action)
+    }
+
+    pub fn randomness(&self) -> Result<RandomnessKind, ProgramError> {
+        RandomnessKind::unpack_from_slice(&self.randomness)
+    }
+
+    pub fn randomness_nonce(&self) -> u64 {
+        u64::from_le_bytes(self.randomness_nonce)
+    }
+
+    pub fn set_randomness_nonce(&mut self, nonce: u64) {
+        self.randomness_nonce = nonce.to_le_bytes();
+    }
+
+    pub fn randomness_request(&self) -> Option<Pubkey> {
+        (self.has_randomness_request != 0).then_some(self.randomness_request)
+    }
+
+    pub fn set_randomness_request(&mut self, request: Option<Pubkey>) {
+        self.has_randomness_request = request.is_some() as u8;
+        self.randomness_request = request.unwrap_or_default();
+    }
+}
+
+// Static tile data comes from the board table, so reading a tile allocates nothing
+impl Board for GameAccountData {
+    fn tile_count(&self) -> usize {
+        match self.board_id {
+            BOARD_STANDARD => STANDARD_BOARD.len(),
+            _ => 0,
+        }
+    }
+
+    fn tile(&self, index: u8) -> Option<TileView<'_>> {
+        let info = match self.board_id {
+            BOARD_STANDARD => STANDARD_BOARD.get(index as usize)?,
+            _ => return None,
+        };
+        let state = &self.tiles[index as usize];
+        Some(match info {
+            TileInfo::Property(info) => TileView::Property(PropertyView {
+                color: &info.color,
+                kind: info.kind,
+                cost: info.cost,
+                rent: info.rent,
+                house_rent: info.house_rent,
+                owner: state.owner(),
+                houses: state.houses,
+                mortgaged: state.mortgaged != 0,
+            }),
+            TileInfo::Special(special) => TileView::Special(special),
+        })
+    }
+}
+
+impl GameState for GameAccountData {
+    type Player = PlayerData;
+
+    fn players(&self) -> &[PlayerData] {
+        &self.players[..(self.players_len as usize).min(self.players.len())]
+    }
+
+    fn players_mut(&mut self) -> &mut [PlayerData] {
+        let len = (self.players_len as usize).min(self.players.len());
+        &mut self.players[..len]
+    }
+
+    fn current_player(&self) -> u8 {
+        self.current_player
+    }
+
+    fn set_current_player(&mut self, current_player: u8) {
+        self.current_player = current_player;
+    }
+
+    fn status(&self) -> Result<GameStatus, ProgramError> {
+        GameStatus::unpack_from_slice(&[self.status])
+    }
+
+    fn turn_phase(&self) -> Result<TurnPhase, ProgramError> {
+        TurnPhase::unpack_from_slice(&[self.turn_phase])
+    }
+
+    fn set_turn_phase(&mut self, turn_phase: TurnPhase) {
+        turn_phase.pack_into_slice(std::slice::from_mut(&mut self.turn_phase));
+    }
+
+    fn rules(&self) -> Result<GameRules, ProgramError> {
+        GameRules::unpack_from_slice(&self.rules)
+    }
+
+    fn has_debt(&self) -> bool {
+        self.debt[0] != 0
+    }
+
+    fn set_debt(&mut self, debt: Option<Debt>) {
+        write_option(&mut self.debt, debt.as_ref());
+    }
+
+    fn has_pending_action(&self) -> bool {
+        self.pending_action[0] != 0
+    }
+
+    fn set_pending_action(&mut self, pending_action: Option<PendingAction>) {
+        write_option(&mut self.pending_action, pending_action.as_ref());
+    }
+
+    fn set_rolled_dice(&mut self, dice: Option<(u8, u8)>) {
+        let (die1, die2) = dice.unwrap_or_default();
+        self.rolled_dice = [dice.is_some() as u8, die1, die2];
+    }
+
+    fn free_parking(&self) -> u64 {
+        u64::from_le_bytes(self.free_parking)
+    }
+
+    fn set_free_parking(&mut self, free_parking: u64) {
+        self.free_parking = free_parking.to_le_bytes();
+    }
+
+    fn last_doubles(&self) -> bool {
+        self.last_doubles != 0
+    }
+
+    fn doubles_rolled(&self) -> u8 {
+        self.doubles_rolled
+    }
+
+    fn set_doubles_rolled(&mut self, doubles_rolled: u8) {
+        self.doubles_rolled = doubles_rolled;
+    }
+
+    fn set_owner(&mut self, property_index: u8, owner: Option<u8>) {
+        if let Some(tile) = self.tiles.get_mut(property_index as usize) {
+            tile.set_owner(owner);
+        }
+    }
+
+    fn remove_from_auction_queue(&mut self, property_index: u8) {
+        let len = (self.auction_queue_len as usize).min(MAX_PLAYER_PROPERTIES);
+        if let Some(position) = self.auction_queue[..len].iter().position(|&index| index == property_index) {
+            self.auction_queue.copy_within(position + 1..len, position);
+            self.auction_queue[len - 1] = 0;
+            self.auction_queue_len -= 1;
+        }
+    }
+}
+
+// An optional field is stored as a flag byte followed by the value, zeroed when absent
+fn read_option<T: Pack>(slot: &[u8]) -> Result<Option<T>, ProgramError> {
+    if slot[0] == 0 {
+        return Ok(None);
+    }
+    T::unpack_from_slice(&slot[1..]).map(Some)
+}
+
+fn write_option<T: Pack>(slot: &mut [u8], value: Option<&T>) {
+    slot[0] = value.is_some() as u8;
+    match value {
+        Some(value) => value.pack_into_slice(&mut slot[1..]),
+        None => slot[1..].fill(0),
+    }
+}
+
+/// Borrow an initialized game account owned by this program without unpacking it
+pub fn load_game_account_mut<'a>(account: &'a AccountInfo) -> Result<RefMut<'a, GameAccountData>, ProgramError> {
+    if account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = account.data.borrow_mut();
+    if data.len() != GameAccount::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    check_header(&mut Reader::new(&data), AccountKind::Game)?;
+    if data[ACCOUNT_HEADER_LEN] == 0 {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    let game: RefMut<GameAccountData> = RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[..]));
+
+    // Checked once here, as `Game` does on unpack, so the rules can index seats by tile owner
+    if !matches!(game.board_id, BOARD_NONE | BOARD_STANDARD) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let seats = game.players().len();
+    if game.tiles.iter().any(|tile| tile.owner().is_some_and(|owner| owner as usize >= seats)) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(game)
+}
+
 // Removed duplicate Pack implementation
 
 #[derive(Clone, Debug)]
@@ -696,6 +1777,9 @@ pub struct PlayerAccount {
 
 impl Sealed for PlayerAccount {}
 
+// A player account must be creatable in a single system program call
+const _: () = assert!(PlayerAccount::LEN <= MAX_PERMITTED_DATA_INCREASE);
+
 impl IsInitialized for PlayerAccount {
     fn is_initialized(&self) -> bool {
         self.is_initialized
@@ -703,17 +1787,16 @@ impl IsInitialized for PlayerAccount {
 }
 
 impl Pack for PlayerAccount {
-    const LEN: usize = 1000; // Calculate exact size based on max player state
+    const LEN: usize = ACCOUNT_HEADER_LEN + 1 + Player::LEN + 32; // header + is_initialized + player + game
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut current = 0;
-        let is_initialized = src[current] != 0;
-        current += 1;
+        let mut reader = Reader::new(src);
+        check_header(&mut reader, AccountKind::Player)?;
+        let is_initialized = reader.read_bool()?;
 
-        let player = Player::unpack_from_slice(&src[current..])?;
-        current += Player::LEN;
+        let player = reader.read_pack()?;
 
-        let game = Pubkey::from(<[u8; 32]>::try_from(&src[current..current + 32]).unwrap());
+        let game = reader.read_pubkey()?;
 
         Ok(PlayerAccount {
             is_initialized,
@@ -723,12 +1806,11 @@ impl Pack for PlayerAccount {
     }
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        dst[current] = self.is_initialized as u8;
-        current += 1;
-        self.player.pack_into_slice(&mut dst[current..current + Player::LEN]);
-        current += Player::LEN;
-        dst[current..current + 32].copy_from_slice(&self.game.to_bytes());
+        let mut writer = Writer::new(dst);
+        pack_header(&mut writer, AccountKind::Player);
+        writer.write_bool(self.is_initialized);
+        writer.write_pack(&self.player);
+        writer.write_pubkey(&self.game);
     }
 }
 
@@ -741,6 +1823,9 @@ pub struct PropertyAccount {
 
 impl Sealed for PropertyAccount {}
 
+// A property account must be creatable in a single system program call
+const _: () = assert!(PropertyAccount::LEN <= MAX_PERMITTED_DATA_INCREASE);
+
 impl IsInitialized for PropertyAccount {
     fn is_initialized(&self) -> bool {
         self.is_initialized
@@ -748,17 +1833,16 @@ impl IsInitialized for PropertyAccount {
 }
 
 impl Pack for PropertyAccount {
-    const LEN: usize = 500; // Calculate exact size based on max property state
+    const LEN: usize = ACCOUNT_HEADER_LEN + 1 + 32 + Property::LEN; // header + is_initialized + game + property
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut current = 0;
-        let is_initialized = src[current] != 0;
-        current += 1;
+        let mut reader = Reader::new(src);
+        check_header(&mut reader, AccountKind::Property)?;
+        let is_initialized = reader.read_bool()?;
 
-        let property = Property::unpack_from_slice(&src[current..])?;
-        current += Property::LEN;
+        let game = reader.read_pubkey()?;
 
-        let game = Pubkey::from(<[u8; 32]>::try_from(&src[current..current + 32]).unwrap());
+        let property = reader.read_pack()?;
 
         Ok(PropertyAccount {
             is_initialized,
@@ -768,24 +1852,53 @@ impl Pack for PropertyAccount {
     }
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        dst[current] = self.is_initialized as u8;
-        current += 1;
-        self.property.pack_into_slice(&mut dst[current..current + Property::LEN]);
-        current += Property::LEN;
-        dst[current..current + 32].copy_from_slice(&self.game.to_bytes());
+        let mut writer = Writer::new(dst);
+        pack_header(&mut writer, AccountKind::Property);
+        writer.write_bool(self.is_initialized);
+        // The game goes first so it sits at a fixed offset in front of the variable-size property
+        writer.write_pubkey(&self.game);
+        writer.write_pack(&self.property);
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct CardDeck {
     pub is_initialized: bool,
+    /// Every card of the deck in shuffled order
     pub cards: Vec<Card>,
     pub game: Pubkey,
     pub deck_type: DeckType,
+    /// Position of the next card to draw; the pile is reshuffled once it runs past the end
+    pub current_card_index: u8,
+    /// Whether a player holds this deck's Get Out of Jail Free card, keeping it out of the pile
+    pub jail_card_held: bool,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+impl CardDeck {
+    /// Shuffle every card back into the pile and start drawing from the top
+    pub fn shuffle(&mut self, source: &dyn RandomnessSource, nonce: u64) {
+        shuffle(&mut self.cards, source, nonce);
+        self.current_card_index = 0;
+    }
+
+    /// Take the next card off the pile, or `None` once it is exhausted.
+    /// A held Get Out of Jail Free card is skipped until it is returned.
+    pub fn next_card(&mut self) -> Option<Card> {
+        while let Some(card) = self.cards.get(self.current_card_index as usize) {
+            self.current_card_index += 1;
+            if matches!(card, Card::GetOutOfJail) {
+                if self.jail_card_held {
+                    continue;
+                }
+                self.jail_card_held = true;
+            }
+            return Some(card.clone());
+        }
+        None
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub enum DeckType {
     CommunityChest,
     Chance,
@@ -793,6 +1906,9 @@ pub enum DeckType {
 
 impl Sealed for CardDeck {}
 
+// A card deck must be creatable in a single system program call
+const _: () = assert!(CardDeck::LEN <= MAX_PERMITTED_DATA_INCREASE);
+
 impl IsInitialized for CardDeck {
     fn is_initialized(&self) -> bool {
         self.is_initialized
@@ -800,116 +1916,144 @@ impl IsInitialized for CardDeck {
 }
 
 impl Pack for CardDeck {
-    const LEN: usize = 1000; // Calculate exact size based on max deck state
+    const LEN: usize = ACCOUNT_HEADER_LEN + 1 + // header + is_initialized
+        4 + 4 + MAX_DECK_CARDS * Card::LEN + // card count, card bytes, cards
+        32 + // game
+        1 + // deck_type
+        1 + // current_card_index
+        1; // jail_card_held
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut current = 0;
-        let is_initialized = src[current] != 0;
-        current += 1;
-
-        let cards_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-
-        let data_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-
-        let mut cards = Vec::new();
-        let mut card_current = current;
-        while card_current < current + data_len {
-            let card_type = src[card_current];
-            card_current += 1;
-            
-            let card = match card_type {
-                0 => {
-                    let value = u64::from_le_bytes(src[card_current..card_current + 8].try_into().unwrap());
-                    card_current += 8;
-                    Card::CollectMoney(value)
-                },
-                1 => {
-                    let value = u64::from_le_bytes(src[card_current..card_current + 8].try_into().unwrap());
-                    card_current += 8;
-                    Card::PayMoney(value)
-                },
-                2 => {
-                    let value = src[card_current];
-                    card_current += 1;
-                    Card::Move(value)
-                },
-                3 => {
-                    Card::GetOutOfJail
-                },
-                _ => return Err(ProgramError::InvalidAccountData),
-            };
-            cards.push(card);
+        let mut reader = Reader::new(src);
+        check_header(&mut reader, AccountKind::CardDeck)?;
+        let is_initialized = reader.read_bool()?;
+
+        // Every card takes at least its tag byte
+        let cards_len = reader.read_len_u32(reader.remaining())?;
+
+        let data_len = reader.read_len_u32(reader.remaining())?;
+        let mut cards_reader = Reader::new(reader.read_slice(data_len)?);
+
+        let mut cards = Vec::with_capacity(cards_len);
+        while cards_reader.remaining() > 0 {
+            cards.push(Card::read(&mut cards_reader)?);
         }
-        current += data_len;
 
-        let game = Pubkey::from(<[u8; 32]>::try_from(&src[current..current + 32]).unwrap());
-        current += 32;
+        let game = reader.read_pubkey()?;
 
-        let deck_type = match src[current] {
+        let deck_type = match reader.read_u8()? {
             0 => DeckType::CommunityChest,
             1 => DeckType::Chance,
             _ => return Err(ProgramError::InvalidAccountData),
         };
 
+        let current_card_index = reader.read_u8()?;
+        let jail_card_held = reader.read_bool()?;
+
         Ok(CardDeck {
             is_initialized,
             cards,
             game,
             deck_type,
+            current_card_index,
+            jail_card_held,
         })
     }
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        
+        let mut writer = Writer::new(dst);
+        pack_header(&mut writer, AccountKind::CardDeck);
+
         // Write initialization flag
-        dst[current] = self.is_initialized as u8;
-        current += 1;
-        
+        writer.write_bool(self.is_initialized);
+
         // Write cards length
-        let cards_len = self.cards.len() as u32;
-        dst[current..current + 4].copy_from_slice(&cards_len.to_le_bytes());
-        current += 4;
-        
+        writer.write_u32(self.cards.len() as u32);
+
+        // Reserve the cards data length, filled in once the cards are written
+        let data_len_offset = writer.position();
+        writer.write_u32(0);
+
         // Write cards data
         for card in &self.cards {
-            match card {
-                Card::CollectMoney(value) => {
-                    dst[current] = 0;
-                    current += 1;
-                    dst[current..current + 8].copy_from_slice(&value.to_le_bytes());
-                    current += 8;
-                },
-                Card::PayMoney(value) => {
-                    dst[current] = 1;
-                    current += 1;
-                    dst[current..current + 8].copy_from_slice(&value.to_le_bytes());
-                    current += 8;
-                },
-                Card::Move(value) => {
-                    dst[current] = 2;
-                    current += 1;
-                    dst[current] = *value;
-                    current += 1;
-                },
-                Card::GetOutOfJail => {
-                    dst[current] = 3;
-                    current += 1;
-                },
-            }
+            card.write(&mut writer);
         }
-        
+        let data_len = (writer.position() - data_len_offset - 4) as u32;
+        writer.patch_u32(data_len_offset, data_len);
+
         // Write game pubkey
-        dst[current..current + 32].copy_from_slice(&self.game.to_bytes());
-        current += 32;
-        
+        writer.write_pubkey(&self.game);
+
         // Write deck type
-        dst[current] = match self.deck_type {
+        writer.write_u8(match self.deck_type {
             DeckType::CommunityChest => 0,
             DeckType::Chance => 1,
-        };
+        });
+
+        // Write draw pile state
+        writer.write_u8(self.current_card_index);
+        writer.write_bool(self.jail_card_held);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TradeOffer {
+    pub is_initialized: bool,
+    pub game: Pubkey,
+    /// Wallet of the player making the offer
+    pub proposer: Pubkey,
+    /// Wallet of the player the offer is addressed to
+    pub counterparty: Pubkey,
+    /// What the proposer gives up
+    pub offered: TradeTerms,
+    /// What the proposer asks for in return
+    pub requested: TradeTerms,
+}
+
+impl Sealed for TradeOffer {}
+
+impl IsInitialized for TradeOffer {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for TradeOffer {
+    const LEN: usize = ACCOUNT_HEADER_LEN + 1 + 32 * 3 + TradeTerms::LEN * 2;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        check_header(&mut reader, AccountKind::TradeOffer)?;
+        let is_initialized = reader.read_bool()?;
+
+        let game = reader.read_pubkey()?;
+        let proposer = reader.read_pubkey()?;
+        let counterparty = reader.read_pubkey()?;
+
+        let offered = reader.read_pack()?;
+        let requested = reader.read_pack()?;
+
+        Ok(TradeOffer {
+            is_initialized,
+            game,
+            proposer,
+            counterparty,
+            offered,
+            requested,
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        pack_header(&mut writer, AccountKind::TradeOffer);
+        writer.write_bool(self.is_initialized);
+
+        writer.write_pubkey(&self.game);
+        writer.write_pubkey(&self.proposer);
+        writer.write_pubkey(&self.counterparty);
+
+        writer.write_pack(&self.offered);
+        writer.write_pack(&self.requested);
     }
 }
 
@@ -941,3 +2085,73 @@ pub fn validate_card_deck(account: &AccountInfo) -> Result<CardDeck, ProgramErro
     }
     CardDeck::unpack(&account.data.borrow())
 }
+
+/// Load the deck a card is drawn from. A blank deck account has not been set up yet and reads as `None`.
+pub fn load_card_deck(account: &AccountInfo) -> Result<Option<CardDeck>, ProgramError> {
+    if account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if is_blank_account(&account.data.borrow()) {
+        return Ok(None);
+    }
+    CardDeck::unpack(&account.data.borrow()).map(Some)
+}
+
+pub fn validate_trade_offer(account: &AccountInfo) -> Result<TradeOffer, ProgramError> {
+    if account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    TradeOffer::unpack(&account.data.borrow())
+}
+
+/// Request for randomness from a game's trusted oracle; the oracle's callback fills in `output`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RandomnessRequest {
+    pub is_initialized: bool,
+    pub game: Pubkey,
+    /// Game randomness nonce the output is meant for, so each output serves a single draw
+    pub nonce: u64,
+    pub fulfilled: bool,
+    pub output: [u8; 32],
+}
+
+impl Sealed for RandomnessRequest {}
+
+impl IsInitialized for RandomnessRequest {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for RandomnessRequest {
+    const LEN: usize = ACCOUNT_HEADER_LEN + 1 + 32 + 8 + 1 + 32;
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        check_header(&mut reader, AccountKind::RandomnessRequest)?;
+        Ok(RandomnessRequest {
+            is_initialized: reader.read_bool()?,
+            game: reader.read_pubkey()?,
+            nonce: reader.read_u64()?,
+            fulfilled: reader.read_bool()?,
+            output: reader.read_array()?,
+        })
+    }
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        pack_header(&mut writer, AccountKind::RandomnessRequest);
+        writer.write_bool(self.is_initialized);
+        writer.write_pubkey(&self.game);
+        writer.write_u64(self.nonce);
+        writer.write_bool(self.fulfilled);
+        writer.write_bytes(&self.output);
+    }
+}
+
+pub fn validate_randomness_request(account: &AccountInfo) -> Result<RandomnessRequest, ProgramError> {
+    if account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    RandomnessRequest::unpack(&account.data.borrow())
+}
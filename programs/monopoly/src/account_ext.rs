@@ -15,9 +15,8 @@ mod tests {
             account_info::AccountInfo,
             program_pack::Pack,
         };
-        use std::cell::RefCell;
         use crate::account::GameAccount;
-        use crate::state::Game;
+        use crate::state::{Game, GameStatus, RandomnessKind, TurnPhase, GameRules, BANK_HOUSES, BANK_HOTELS};
 
         // Create a valid game account
         let game_account = GameAccount {
@@ -28,6 +27,28 @@ mod tests {
                 current_player: 0,
                 free_parking: 0,
                 initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::AwaitingRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
             },
         };
 
@@ -38,14 +59,14 @@ mod tests {
         // Create account info with valid data
         let key = Pubkey::new_unique();
         let mut lamports = 0;
-        let account_data = RefCell::new(buffer);
+        let mut account_data = buffer;
         let owner = Pubkey::new_unique();
         let account_info = AccountInfo::new(
             &key,
             false,
             true,
             &mut lamports,
-            account_data.borrow_mut().as_mut_slice(),
+            &mut account_data,
             &owner,
             false,
             0,
@@ -59,13 +80,13 @@ mod tests {
         assert!(unpacked.game.initialized);
 
         // Test with invalid data
-        let invalid_data = RefCell::new(vec![0u8; 10]); // Too small
+        let mut invalid_data = vec![0u8; 10]; // Too small
         let invalid_account = AccountInfo::new(
             &key,
             false,
             true,
             &mut lamports,
-            invalid_data.borrow_mut().as_mut_slice(),
+            &mut invalid_data,
             &owner,
             false,
             0,
@@ -0,0 +1,761 @@
+//! Version 1 account layouts, frozen as the program wrote them before accounts carried a
+//! discriminator and layout version. They exist only so `MigrateAccount` can read accounts
+//! still in the old layout and translate them field by field; they must never change.
+//!
+//! Version 1 data has no discriminator, so the requested kind can only be checked by
+//! decoding strictly: the account must be exactly that kind's version 1 size, and every
+//! flag, tag, length and string in it must be valid for that kind.
+
+use solana_program::{
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+use crate::account::{AccountKind, CardDeck, DeckType, GameAccount, PlayerAccount};
+use crate::board::create_board;
+use crate::cursor::Reader;
+use crate::error::MonopolyError;
+use crate::state::{
+    Card, Game, GameRules, GameStatus, Player, RandomnessKind, SpecialTile, TileType, TurnPhase,
+    BANK_HOTELS, BANK_HOUSES, BOARD_SIZE, MAX_DECK_CARDS, MAX_NAME_LEN, MAX_PLAYERS, MAX_PLAYER_PROPERTIES,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Property;
+
+    // The encoders below are the version 1 `pack_into_slice` implementations, kept byte for
+    // byte. Fields added since version 1 are not written.
+
+    fn pack_v1_property(property: &Property, wallets: &[Pubkey], dst: &mut [u8]) {
+        let mut current = 0;
+
+        let name_bytes = property.name.as_bytes();
+        dst[current..current + 4].copy_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+        current += 4;
+        dst[current..current + name_bytes.len()].copy_from_slice(name_bytes);
+        current += name_bytes.len();
+
+        property.color.pack_into_slice(&mut dst[current..current + 1]);
+        current += 1;
+
+        dst[current..current + 8].copy_from_slice(&property.cost.to_le_bytes());
+        current += 8;
+
+        dst[current..current + 4].copy_from_slice(&(property.rent.len() as u32).to_le_bytes());
+        current += 4;
+        for rent in &property.rent {
+            dst[current..current + 8].copy_from_slice(&rent.to_le_bytes());
+            current += 8;
+        }
+
+        dst[current..current + 8].copy_from_slice(&property.house_cost.to_le_bytes());
+        current += 8;
+        dst[current..current + 8].copy_from_slice(&property.hotel_cost.to_le_bytes());
+        current += 8;
+
+        dst[current..current + 4].copy_from_slice(&(property.house_rent.len() as u32).to_le_bytes());
+        current += 4;
+        for rent in &property.house_rent {
+            dst[current..current + 8].copy_from_slice(&rent.to_le_bytes());
+            current += 8;
+        }
+
+        dst[current] = property.owner.is_some() as u8;
+        current += 1;
+        if let Some(seat) = property.owner {
+            dst[current..current + 32].copy_from_slice(&wallets[seat as usize].to_bytes());
+            current += 32;
+        }
+
+        dst[current] = property.houses;
+    }
+
+    fn pack_v1_tile(tile: &TileType, wallets: &[Pubkey], dst: &mut [u8]) {
+        match tile {
+            TileType::Property(property) => {
+                dst[0] = 0;
+                pack_v1_property(property, wallets, &mut dst[1..]);
+            }
+            TileType::Special(special) => {
+                dst[0] = 1;
+                special.pack_into_slice(&mut dst[1..]);
+            }
+        }
+    }
+
+    fn pack_v1_player(player: &Player, dst: &mut [u8]) {
+        let mut current = 0;
+
+        let name_bytes = player.name.as_bytes();
+        dst[current..current + 4].copy_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+        current += 4;
+        dst[current..current + name_bytes.len()].copy_from_slice(name_bytes);
+        current += name_bytes.len();
+
+        dst[current..current + 8].copy_from_slice(&player.cash.to_le_bytes());
+        current += 8;
+        dst[current] = player.position;
+        current += 1;
+        dst[current] = player.jail_turns;
+        current += 1;
+
+        dst[current..current + 4].copy_from_slice(&(player.properties.len() as u32).to_le_bytes());
+        current += 4;
+        dst[current..current + player.properties.len()].copy_from_slice(&player.properties);
+        current += player.properties.len();
+
+        dst[current] = player.get_out_of_jail_cards;
+    }
+
+    fn pack_v1_game_account(is_initialized: bool, game: &Game, dst: &mut [u8]) {
+        dst[0] = is_initialized as u8;
+        let dst = &mut dst[1..];
+        let mut current = 0;
+
+        // Version 1 recorded the owner's wallet on the tile
+        let wallets: Vec<Pubkey> = game.players.iter().map(|player| player.wallet).collect();
+        dst[current..current + 4].copy_from_slice(&(game.board.len() as u32).to_le_bytes());
+        current += 4;
+        for tile in &game.board {
+            pack_v1_tile(tile, &wallets, &mut dst[current..current + TILE_LEN]);
+            current += TILE_LEN;
+        }
+
+        dst[current..current + 4].copy_from_slice(&(game.players.len() as u32).to_le_bytes());
+        current += 4;
+        for player in &game.players {
+            pack_v1_player(player, &mut dst[current..current + PLAYER_LEN]);
+            current += PLAYER_LEN;
+        }
+
+        dst[current] = game.current_player;
+        current += 1;
+        dst[current..current + 8].copy_from_slice(&game.free_parking.to_le_bytes());
+        current += 8;
+        dst[current] = game.initialized as u8;
+    }
+
+    fn pack_v1_player_account(is_initialized: bool, player: &Player, game: &Pubkey, dst: &mut [u8]) {
+        let mut current = 0;
+        dst[current] = is_initialized as u8;
+        current += 1;
+        pack_v1_player(player, &mut dst[current..current + PLAYER_LEN]);
+        current += PLAYER_LEN;
+        dst[current..current + 32].copy_from_slice(&game.to_bytes());
+    }
+
+    fn pack_v1_card_deck(is_initialized: bool, cards: &[Card], game: &Pubkey, deck_type: DeckType, dst: &mut [u8]) {
+        let mut current = 0;
+
+        dst[current] = is_initialized as u8;
+        current += 1;
+
+        dst[current..current + 4].copy_from_slice(&(cards.len() as u32).to_le_bytes());
+        current += 4;
+
+        for card in cards {
+            match card {
+                Card::CollectMoney(value) => {
+                    dst[current] = 0;
+                    current += 1;
+                    dst[current..current + 8].copy_from_slice(&value.to_le_bytes());
+                    current += 8;
+                },
+                Card::PayMoney(value) => {
+                    dst[current] = 1;
+                    current += 1;
+                    dst[current..current + 8].copy_from_slice(&value.to_le_bytes());
+                    current += 8;
+                },
+                Card::Move(value) => {
+                    dst[current] = 2;
+                    current += 1;
+                    dst[current] = *value;
+                    current += 1;
+                },
+                Card::GetOutOfJail => {
+                    dst[current] = 3;
+                    current += 1;
+                },
+                _ => unreachable!("version 1 decks only hold the original four cards"),
+            }
+        }
+
+        dst[current..current + 32].copy_from_slice(&game.to_bytes());
+        current += 32;
+
+        dst[current] = match deck_type {
+            DeckType::CommunityChest => 0,
+            DeckType::Chance => 1,
+        };
+    }
+
+    fn v1_player(name: &str, cash: u64, position: u8, properties: Vec<u8>) -> Player {
+        Player {
+            name: name.to_string(),
+            wallet: Pubkey::default(),
+            delegate: None,
+            cash,
+            position,
+            jail_turns: 0,
+            properties,
+            get_out_of_jail_cards: 0,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: None,
+            dice_secret: None,
+        }
+    }
+
+    /// A version 1 game with the first `tiles` tiles of the standard board
+    fn v1_game(tiles: usize, players: Vec<Player>) -> Game {
+        let mut game = Game {
+            board: create_board().into_iter().take(tiles).collect(),
+            players,
+            current_player: 1,
+            free_parking: 150,
+            initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Open,
+            max_players: 0,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: 0,
+            hotels_remaining: 0,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::SlotHash,
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+        // Version 1 stored the owner's key on the tile and the tile index on the player
+        let owners: Vec<(usize, Vec<u8>)> = game.players.iter().enumerate()
+            .map(|(seat, player)| (seat, player.properties.clone()))
+            .collect();
+        for (seat, properties) in owners {
+            for index in properties {
+                if let Some(TileType::Property(property)) = game.board.get_mut(index as usize) {
+                    property.owner = Some(seat as u8);
+                }
+            }
+        }
+        game
+    }
+
+    #[test]
+    fn test_upgrade_game() {
+        // Two seats leave room for six tiles; the version 1 layout could never hold a full board
+        let mut game = v1_game(6, vec![
+            v1_player("Alice", 1200, 3, vec![1, 3]),
+            v1_player("Bob", 900, 5, vec![5]),
+        ]);
+        game.players[1].jail_turns = 2;
+        game.players[1].get_out_of_jail_cards = 1;
+        if let TileType::Property(property) = &mut game.board[1] {
+            property.houses = 2;
+        }
+        if let TileType::Property(property) = &mut game.board[3] {
+            property.houses = 5;
+        }
+        let wallets = [Pubkey::new_unique(), Pubkey::new_unique()];
+        for (player, wallet) in game.players.iter_mut().zip(&wallets) {
+            player.wallet = *wallet;
+        }
+        let mut v1 = vec![0u8; GAME_ACCOUNT_LEN];
+        pack_v1_game_account(true, &game, &mut v1);
+
+        // Test case 1: Every version 1 field lands in its current counterpart
+        {
+            let upgraded = upgrade(&v1, AccountKind::Game, &wallets).unwrap();
+            assert_eq!(upgraded.len(), GameAccount::LEN);
+            let account = GameAccount::unpack(&upgraded).unwrap();
+            let migrated = account.game;
+
+            assert_eq!(migrated.players.len(), 2);
+            for ((player, old), wallet) in migrated.players.iter().zip(&game.players).zip(&wallets) {
+                assert_eq!(player.name, old.name);
+                assert_eq!(player.wallet, *wallet);
+                assert_eq!(player.cash, old.cash);
+                assert_eq!(player.position, old.position);
+                assert_eq!(player.jail_turns, old.jail_turns);
+                assert_eq!(player.properties, old.properties);
+                assert_eq!(player.get_out_of_jail_cards, old.get_out_of_jail_cards);
+                assert!(!player.eliminated);
+            }
+            assert_eq!(migrated.current_player, 1);
+            assert_eq!(migrated.free_parking, 150);
+            assert!(migrated.initialized);
+
+            // The board is the standard one, with the version 1 tiles' owners and buildings
+            assert_eq!(migrated.board.len(), BOARD_SIZE);
+            for (index, tile) in migrated.board.iter().enumerate() {
+                if let TileType::Property(property) = tile {
+                    let (owner, houses) = match index {
+                        1 => (Some(0), 2),
+                        3 => (Some(0), 5),
+                        5 => (Some(1), 0),
+                        _ => (None, 0),
+                    };
+                    assert_eq!(property.owner, owner, "tile {}", index);
+                    assert_eq!(property.houses, houses, "tile {}", index);
+                    assert!(!property.mortgaged);
+                }
+            }
+            assert_eq!(migrated.houses_remaining, BANK_HOUSES - 2);
+            assert_eq!(migrated.hotels_remaining, BANK_HOTELS - 1);
+
+            // A version 1 game was in play as soon as it existed
+            assert_eq!(migrated.status, GameStatus::Started);
+            assert_eq!(migrated.max_players, 2);
+            assert_eq!(migrated.turn_phase, TurnPhase::AwaitingRoll);
+        }
+
+        // Test case 2: A game with no board keeps an empty one
+        {
+            let mut empty = v1_game(0, vec![v1_player("Alice", 1500, 0, vec![])]);
+            empty.current_player = 0;
+            let mut v1 = vec![0u8; GAME_ACCOUNT_LEN];
+            pack_v1_game_account(true, &empty, &mut v1);
+            let upgraded = upgrade(&v1, AccountKind::Game, &wallets[..1]).unwrap();
+            let migrated = GameAccount::unpack(&upgraded).unwrap().game;
+            assert!(migrated.board.is_empty());
+            assert_eq!(migrated.players[0].wallet, wallets[0]);
+        }
+
+        // Test case 3: Every seat needs its own wallet
+        {
+            let result = upgrade(&v1, AccountKind::Game, &wallets[..1]);
+            assert_eq!(result.unwrap_err(), ProgramError::NotEnoughAccountKeys);
+            let result = upgrade(&v1, AccountKind::Game, &[wallets[0], wallets[1], Pubkey::new_unique()]);
+            assert_eq!(result.unwrap_err(), ProgramError::InvalidArgument);
+            let result = upgrade(&v1, AccountKind::Game, &[wallets[0], wallets[0]]);
+            assert_eq!(result.unwrap_err(), ProgramError::InvalidArgument);
+
+            // A seat's wallet must be the one its tiles record
+            let result = upgrade(&v1, AccountKind::Game, &[wallets[1], wallets[0]]);
+            assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+            let result = upgrade(&v1, AccountKind::Game, &[wallets[0], Pubkey::new_unique()]);
+            assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+        }
+
+        // Test case 4: Tiles must be the standard board's, and owners must match the seats' lists
+        {
+            let mut renamed = game.clone();
+            if let TileType::Property(property) = &mut renamed.board[1] {
+                property.name = String::from("Elsewhere");
+            }
+            let mut data = vec![0u8; GAME_ACCOUNT_LEN];
+            pack_v1_game_account(true, &renamed, &mut data);
+            assert_eq!(upgrade(&data, AccountKind::Game, &wallets).unwrap_err(), ProgramError::InvalidAccountData);
+
+            let mut unlisted = game.clone();
+            unlisted.players[1].properties.clear();
+            let mut data = vec![0u8; GAME_ACCOUNT_LEN];
+            pack_v1_game_account(true, &unlisted, &mut data);
+            assert_eq!(upgrade(&data, AccountKind::Game, &wallets).unwrap_err(), ProgramError::InvalidAccountData);
+
+            let mut unowned = game.clone();
+            unowned.players[0].properties.push(2);
+            let mut data = vec![0u8; GAME_ACCOUNT_LEN];
+            pack_v1_game_account(true, &unowned, &mut data);
+            assert_eq!(upgrade(&data, AccountKind::Game, &wallets).unwrap_err(), ProgramError::InvalidAccountData);
+        }
+
+        // Test case 5: Data of another kind, or of the wrong size, is refused
+        {
+            assert_eq!(upgrade(&v1, AccountKind::Player, &wallets[..1]).unwrap_err(), ProgramError::InvalidAccountData);
+            assert_eq!(upgrade(&v1[..GAME_ACCOUNT_LEN - 1], AccountKind::Game, &wallets).unwrap_err(), ProgramError::InvalidAccountData);
+            let mut flagged = v1.clone();
+            flagged[0] = 2;
+            assert_eq!(upgrade(&flagged, AccountKind::Game, &wallets).unwrap_err(), ProgramError::InvalidAccountData);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_player_and_deck() {
+        let game_key = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+
+        // Test case 1: A player account keeps its player and game and gains the wallet
+        {
+            let mut player = v1_player("Carol", 1500, 7, vec![6, 8]);
+            player.get_out_of_jail_cards = 1;
+            let mut v1 = vec![0u8; PLAYER_ACCOUNT_LEN];
+            pack_v1_player_account(true, &player, &game_key, &mut v1);
+
+            let upgraded = upgrade(&v1, AccountKind::Player, &[wallet]).unwrap();
+            let account = PlayerAccount::unpack(&upgraded).unwrap();
+            assert_eq!(account.game, game_key);
+            assert_eq!(account.player.name, "Carol");
+            assert_eq!(account.player.wallet, wallet);
+            assert_eq!(account.player.position, 7);
+            assert_eq!(account.player.properties, vec![6, 8]);
+            assert_eq!(account.player.get_out_of_jail_cards, 1);
+
+            assert_eq!(upgrade(&v1, AccountKind::Player, &[]).unwrap_err(), ProgramError::NotEnoughAccountKeys);
+            assert_eq!(upgrade(&v1, AccountKind::CardDeck, &[]).unwrap_err(), ProgramError::InvalidAccountData);
+        }
+
+        // Test case 2: A deck keeps its cards in order and draws from the top
+        {
+            let cards = vec![Card::CollectMoney(200), Card::Move(24), Card::GetOutOfJail, Card::PayMoney(50)];
+            let mut v1 = vec![0u8; CARD_DECK_LEN];
+            pack_v1_card_deck(true, &cards, &game_key, DeckType::Chance, &mut v1);
+
+            let upgraded = upgrade(&v1, AccountKind::CardDeck, &[]).unwrap();
+            let deck = CardDeck::unpack(&upgraded).unwrap();
+            assert_eq!(deck.cards, cards);
+            assert_eq!(deck.game, game_key);
+            assert_eq!(deck.deck_type, DeckType::Chance);
+            assert_eq!(deck.current_card_index, 0);
+            assert!(!deck.jail_card_held);
+
+            // A deck has no seats to bind
+            assert_eq!(upgrade(&v1, AccountKind::CardDeck, &[wallet]).unwrap_err(), ProgramError::InvalidArgument);
+            assert_eq!(upgrade(&v1, AccountKind::Player, &[wallet]).unwrap_err(), ProgramError::InvalidAccountData);
+        }
+
+        // Test case 3: Kinds without a version 1 layout are refused
+        {
+            for kind in [AccountKind::Property, AccountKind::TradeOffer, AccountKind::RandomnessRequest] {
+                assert_eq!(account_len(kind), None);
+                assert_eq!(upgrade(&[0; PLAYER_ACCOUNT_LEN], kind, &[]).unwrap_err(), MonopolyError::UnsupportedAccountVersion.into());
+            }
+        }
+    }
+}
+
+/// Size of the version 1 `Game`, a fixed slot the variable-width fields were written into
+const GAME_LEN: usize = 8192;
+/// Size of a version 1 board tile: a tag byte, then the tile in a 1024-byte slot
+const TILE_LEN: usize = 1025;
+/// Size of a version 1 player slot
+const PLAYER_LEN: usize = 512;
+
+/// Size of a version 1 game account: is_initialized, then the game
+pub const GAME_ACCOUNT_LEN: usize = 1 + GAME_LEN;
+/// Size of a version 1 player account
+pub const PLAYER_ACCOUNT_LEN: usize = 1000;
+/// Size of a version 1 card deck
+pub const CARD_DECK_LEN: usize = 1000;
+
+/// Size of a version 1 account of `kind`, or `None` if the kind has no version 1 layout.
+/// Trade offers and randomness requests were added after version 1, and a version 1 property
+/// account could never be written: its 1024-byte property slot overran the 500-byte account.
+pub fn account_len(kind: AccountKind) -> Option<usize> {
+    match kind {
+        AccountKind::Game => Some(GAME_ACCOUNT_LEN),
+        AccountKind::Player => Some(PLAYER_ACCOUNT_LEN),
+        AccountKind::CardDeck => Some(CARD_DECK_LEN),
+        AccountKind::Property | AccountKind::TradeOffer | AccountKind::RandomnessRequest => None,
+    }
+}
+
+/// Translate a version 1 account of `kind` into the current layout, returning data of
+/// `kind.account_len()` bytes. Version 1 seats were not bound to wallets, so `wallets`
+/// supplies one per seat of a game, in seat order, or the player's wallet for a player
+/// account; a card deck takes none. A game's owned tiles must name their seat's wallet.
+pub fn upgrade(data: &[u8], kind: AccountKind, wallets: &[Pubkey]) -> Result<Vec<u8>, ProgramError> {
+    let len = account_len(kind).ok_or(MonopolyError::UnsupportedAccountVersion)?;
+    if data.len() != len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let mut upgraded = vec![0u8; kind.account_len()];
+    let mut reader = Reader::new(data);
+    match kind {
+        AccountKind::Game => {
+            let is_initialized = read_flag(&mut reader)?;
+            let game = read_game(reader.read_slice(GAME_LEN)?)?;
+            let game = upgrade_game(game, wallets)?;
+            GameAccount { is_initialized, game }.pack_into_slice(&mut upgraded);
+        }
+        AccountKind::Player => {
+            let is_initialized = read_flag(&mut reader)?;
+            let player = read_player(reader.read_slice(PLAYER_LEN)?)?;
+            let game = reader.read_pubkey()?;
+            let wallet = match wallets {
+                [] => return Err(ProgramError::NotEnoughAccountKeys),
+                [wallet] => *wallet,
+                _ => return Err(ProgramError::InvalidArgument),
+            };
+            let player = upgrade_player(player, wallet);
+            PlayerAccount { is_initialized, player, game }.pack_into_slice(&mut upgraded);
+        }
+        AccountKind::CardDeck => {
+            if !wallets.is_empty() {
+                return Err(ProgramError::InvalidArgument);
+            }
+            read_card_deck(&mut reader)?.pack_into_slice(&mut upgraded);
+        }
+        AccountKind::Property | AccountKind::TradeOffer | AccountKind::RandomnessRequest => unreachable!(),
+    }
+    Ok(upgraded)
+}
+
+/// Version 1 wrote flags as exactly 0 or 1
+fn read_flag(reader: &mut Reader) -> Result<bool, ProgramError> {
+    match reader.read_u8()? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+struct PlayerV1 {
+    name: String,
+    cash: u64,
+    position: u8,
+    jail_turns: u8,
+    properties: Vec<u8>,
+    get_out_of_jail_cards: u8,
+}
+
+enum TileV1 {
+    Property {
+        name: String,
+        owner: Option<Pubkey>,
+        houses: u8,
+    },
+    Special(SpecialTile),
+}
+
+struct GameV1 {
+    board: Vec<TileV1>,
+    players: Vec<PlayerV1>,
+    current_player: u8,
+    free_parking: u64,
+    initialized: bool,
+}
+
+fn read_player(src: &[u8]) -> Result<PlayerV1, ProgramError> {
+    let mut reader = Reader::new(src);
+    let name_len = reader.read_len_u32(MAX_NAME_LEN)?;
+    let name = reader.read_string(name_len)?;
+    let cash = reader.read_u64()?;
+    let position = reader.read_u8()?;
+    if position as usize >= BOARD_SIZE {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let jail_turns = reader.read_u8()?;
+    let properties_len = reader.read_len_u32(MAX_PLAYER_PROPERTIES)?;
+    let properties = reader.read_slice(properties_len)?.to_vec();
+    let get_out_of_jail_cards = reader.read_u8()?;
+    Ok(PlayerV1 {
+        name,
+        cash,
+        position,
+        jail_turns,
+        properties,
+        get_out_of_jail_cards,
+    })
+}
+
+fn read_tile(src: &[u8]) -> Result<TileV1, ProgramError> {
+    let mut reader = Reader::new(src);
+    match reader.read_u8()? {
+        0 => {
+            let name_len = reader.read_len_u32(reader.remaining())?;
+            let name = reader.read_string(name_len)?;
+            // Prices and rents come from the standard board rather than the account
+            if reader.read_u8()? > 8 {
+                return Err(ProgramError::InvalidAccountData); // color
+            }
+            reader.read_u64()?; // cost
+            let rent_len = reader.read_len_u32(reader.remaining() / 8)?;
+            reader.skip(rent_len * 8)?;
+            reader.read_u64()?; // house_cost
+            reader.read_u64()?; // hotel_cost
+            let house_rent_len = reader.read_len_u32(reader.remaining() / 8)?;
+            reader.skip(house_rent_len * 8)?;
+            let owner = match read_flag(&mut reader)? {
+                true => Some(reader.read_pubkey()?),
+                false => None,
+            };
+            let houses = reader.read_u8()?;
+            if houses > 5 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            Ok(TileV1::Property { name, owner, houses })
+        }
+        1 => Ok(TileV1::Special(SpecialTile::unpack_from_slice(reader.read_slice(1)?)?)),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+fn read_game(src: &[u8]) -> Result<GameV1, ProgramError> {
+    let mut reader = Reader::new(src);
+
+    let board_len = reader.read_len_u32(BOARD_SIZE)?;
+    let mut board = Vec::with_capacity(board_len);
+    for _ in 0..board_len {
+        board.push(read_tile(reader.read_slice(TILE_LEN)?)?);
+    }
+
+    let players_len = reader.read_len_u32(MAX_PLAYERS as usize)?;
+    let mut players = Vec::with_capacity(players_len);
+    for _ in 0..players_len {
+        players.push(read_player(reader.read_slice(PLAYER_LEN)?)?);
+    }
+
+    let current_player = reader.read_u8()?;
+    if current_player as usize >= players_len.max(1) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let free_parking = reader.read_u64()?;
+    let initialized = read_flag(&mut reader)?;
+
+    Ok(GameV1 {
+        board,
+        players,
+        current_player,
+        free_parking,
+        initialized,
+    })
+}
+
+fn read_card_deck(reader: &mut Reader) -> Result<CardDeck, ProgramError> {
+    let is_initialized = read_flag(reader)?;
+
+    // Version 1 packed the card count and then the cards, with no byte length in between
+    let cards_len = reader.read_len_u32(MAX_DECK_CARDS)?;
+    let mut cards = Vec::with_capacity(cards_len);
+    for _ in 0..cards_len {
+        cards.push(match reader.read_u8()? {
+            0 => Card::CollectMoney(reader.read_u64()?),
+            1 => Card::PayMoney(reader.read_u64()?),
+            2 => Card::Move(reader.read_u8()?),
+            3 => Card::GetOutOfJail,
+            _ => return Err(ProgramError::InvalidAccountData),
+        });
+    }
+
+    let game = reader.read_pubkey()?;
+    let deck_type = match reader.read_u8()? {
+        0 => DeckType::CommunityChest,
+        1 => DeckType::Chance,
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+
+    // The pile keeps its version 1 order and is drawn from the top
+    Ok(CardDeck {
+        is_initialized,
+        cards,
+        game,
+        deck_type,
+        current_card_index: 0,
+        jail_card_held: false,
+    })
+}
+
+fn upgrade_player(player: PlayerV1, wallet: Pubkey) -> Player {
+    Player {
+        name: player.name,
+        wallet,
+        delegate: None,
+        cash: player.cash,
+        position: player.position,
+        jail_turns: player.jail_turns,
+        properties: player.properties,
+        get_out_of_jail_cards: player.get_out_of_jail_cards,
+        community_chest_jail_card: false,
+        chance_jail_card: false,
+        eliminated: false,
+        dice_commitment: None,
+        dice_secret: None,
+    }
+}
+
+fn upgrade_game(game: GameV1, wallets: &[Pubkey]) -> Result<Game, ProgramError> {
+    if wallets.len() < game.players.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if wallets.len() > game.players.len()
+        || wallets.iter().enumerate().any(|(i, wallet)| wallets[..i].contains(wallet))
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Version 1 boards were cut from the standard board, so each tile must match the
+    // standard tile at its index. Ownership moves from the tile's owner key to the seat
+    // listing the tile; the two records must agree, and the key must be that seat's wallet.
+    let mut board = if game.board.is_empty() { Vec::new() } else { create_board() };
+    for (index, tile) in game.board.iter().enumerate() {
+        match (tile, &mut board[index]) {
+            (TileV1::Special(special), TileType::Special(standard)) if special == standard => {}
+            (TileV1::Property { name, owner, houses }, TileType::Property(standard)) if *name == standard.name => {
+                let mut seats = game.players.iter().enumerate()
+                    .filter(|(_, player)| player.properties.contains(&(index as u8)))
+                    .map(|(seat, _)| seat);
+                let seat = seats.next();
+                if seats.next().is_some() || *owner != seat.map(|seat| wallets[seat]) {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                standard.owner = seat.map(|seat| seat as u8);
+                standard.houses = *houses;
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+    }
+    let listed_unowned = game.players.iter().flat_map(|player| &player.properties).any(|&index| {
+        !matches!(game.board.get(index as usize), Some(TileV1::Property { owner: Some(_), .. }))
+    });
+    if listed_unowned {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Version 1 had no building supply; a bank it overdrew starts empty
+    let properties = board.iter().filter_map(TileType::as_property);
+    let hotels = properties.clone().filter(|property| property.houses == 5).count();
+    let houses: usize = properties.filter(|property| property.houses < 5).map(|property| property.houses as usize).sum();
+
+    let players_len = game.players.len() as u8;
+    let players = game.players.into_iter().zip(wallets)
+        .map(|(player, wallet)| upgrade_player(player, *wallet))
+        .collect();
+
+    // A version 1 game was in play from the moment it was created, under the standard rules
+    Ok(Game {
+        board,
+        players,
+        current_player: game.current_player,
+        free_parking: game.free_parking,
+        initialized: game.initialized,
+        creator: Pubkey::default(),
+        status: GameStatus::Started,
+        max_players: players_len,
+        auction: None,
+        debt: None,
+        last_roll: 0,
+        houses_remaining: BANK_HOUSES.saturating_sub(houses.min(u8::MAX as usize) as u8),
+        hotels_remaining: BANK_HOTELS.saturating_sub(hotels as u8),
+        doubles_rolled: 0,
+        dice_round: None,
+        rolled_dice: None,
+        randomness: RandomnessKind::SlotHash,
+        randomness_nonce: 0,
+        last_dice: (0, 0),
+        last_doubles: false,
+        turn_phase: TurnPhase::AwaitingRoll,
+        pending_action: None,
+        rules: GameRules::default(),
+        auction_queue: vec![],
+        randomness_request: None,
+        community_chest_deck: None,
+        chance_deck: None,
+    })
+}
@@ -1,4 +1,4 @@
-use crate::state::{TileType, Property, SpecialTile, Color, Card};
+use crate::state::{TileType, TileKind, Property, SpecialTile, Color, Card, BOARD_SIZE};
 
 #[cfg(test)]
 mod tests {
@@ -69,6 +69,20 @@ mod tests {
         assert_eq!(property_count, 28, "Should have 28 properties");
         assert_eq!(special_count, 12, "Should have 12 special tiles");
 
+        // Count property kinds
+        let count_kind = |kind: TileKind| board.iter()
+            .filter(|tile| matches!(tile, TileType::Property(prop) if prop.kind == kind))
+            .count();
+        assert_eq!(count_kind(TileKind::Street), 22, "Should have 22 streets");
+        assert_eq!(count_kind(TileKind::Railroad), 4, "Should have 4 railroads");
+        assert_eq!(count_kind(TileKind::Utility), 2, "Should have 2 utilities");
+        for index in [5, 15, 25, 35] {
+            assert!(matches!(&board[index], TileType::Property(prop) if prop.kind == TileKind::Railroad));
+        }
+        for index in [12, 28] {
+            assert!(matches!(&board[index], TileType::Property(prop) if prop.kind == TileKind::Utility));
+        }
+
         // Verify all properties have no initial owner and no houses
         board.iter().filter_map(|tile| {
             if let TileType::Property(prop) = tile {
@@ -87,7 +101,7 @@ mod tests {
         let cards = create_community_chest();
         
         // Test number of cards
-        assert_eq!(cards.len(), 7, "Should have 7 community chest cards");
+        assert_eq!(cards.len(), 16, "Should have 16 community chest cards");
 
         // Test specific cards
         let mut collect_200 = false;
@@ -97,6 +111,8 @@ mod tests {
         let mut collect_100 = false;
         let mut pay_100 = false;
         let mut move_to_jail = false;
+        let mut birthday = false;
+        let mut street_repairs = false;
 
         for card in cards {
             match card {
@@ -106,7 +122,10 @@ mod tests {
                 Card::Move(0) => move_to_go = true,
                 Card::CollectMoney(100) => collect_100 = true,
                 Card::PayMoney(100) => pay_100 = true,
-                Card::Move(10) => move_to_jail = true,
+                Card::GoToJail => move_to_jail = true,
+                Card::CollectFromEachPlayer(10) => birthday = true,
+                Card::PayPerHouseAndHotel { house: 40, hotel: 115 } => street_repairs = true,
+                Card::CollectMoney(_) | Card::PayMoney(_) => {},
                 _ => panic!("Unexpected card in community chest"),
             }
         }
@@ -118,6 +137,8 @@ mod tests {
         assert!(collect_100, "Missing Collect $100 card");
         assert!(pay_100, "Missing Pay $100 card");
         assert!(move_to_jail, "Missing Move to Jail card");
+        assert!(birthday, "Missing Collect $10 from each player card");
+        assert!(street_repairs, "Missing street repairs card");
     }
 
     #[test]
@@ -125,7 +146,7 @@ mod tests {
         let cards = create_chance_cards();
         
         // Test number of cards
-        assert_eq!(cards.len(), 6, "Should have 6 chance cards");
+        assert_eq!(cards.len(), 16, "Should have 16 chance cards");
 
         // Test specific cards
         let mut collect_150 = false;
@@ -134,6 +155,11 @@ mod tests {
         let mut get_out_of_jail = false;
         let mut move_to_jail = false;
         let mut move_to_railroad = false;
+        let mut nearest_railroads = 0;
+        let mut nearest_utility = false;
+        let mut go_back_3 = false;
+        let mut general_repairs = false;
+        let mut pay_each_50 = false;
 
         for card in cards {
             match card {
@@ -141,8 +167,15 @@ mod tests {
                 Card::PayMoney(15) => pay_15 = true,
                 Card::Move(0) => move_to_go = true,
                 Card::GetOutOfJail => get_out_of_jail = true,
-                Card::Move(10) => move_to_jail = true,
+                Card::GoToJail => move_to_jail = true,
                 Card::Move(5) => move_to_railroad = true,
+                Card::AdvanceToNearest(TileKind::Railroad) => nearest_railroads += 1,
+                Card::AdvanceToNearest(TileKind::Utility) => nearest_utility = true,
+                Card::MoveBack(3) => go_back_3 = true,
+                Card::PayPerHouseAndHotel { house: 25, hotel: 100 } => general_repairs = true,
+                Card::PayEachPlayer(50) => pay_each_50 = true,
+                Card::Move(position) => assert!(position < 40, "Move off the board"),
+                Card::CollectMoney(_) => {},
                 _ => panic!("Unexpected card in chance deck"),
             }
         }
@@ -153,318 +186,428 @@ mod tests {
         assert!(get_out_of_jail, "Missing Get Out of Jail card");
         assert!(move_to_jail, "Missing Move to Jail card");
         assert!(move_to_railroad, "Missing Move to Railroad card");
+        assert_eq!(nearest_railroads, 2, "Should have two nearest railroad cards");
+        assert!(nearest_utility, "Missing nearest utility card");
+        assert!(go_back_3, "Missing Go Back 3 Spaces card");
+        assert!(general_repairs, "Missing general repairs card");
+        assert!(pay_each_50, "Missing Pay each player $50 card");
     }
 }
 
+/// Rules of an ownable tile that never change during a game
+#[derive(Debug)]
+pub struct PropertyInfo {
+    pub name: &'static str,
+    pub color: Color,
+    pub kind: TileKind,
+    pub cost: u64,
+    pub rent: &'static [u64],
+    pub house_cost: u64,
+    pub hotel_cost: u64,
+    pub house_rent: &'static [u64],
+}
+
+/// One tile of a static board table
+#[derive(Debug)]
+pub enum TileInfo {
+    Property(PropertyInfo),
+    Special(SpecialTile),
+}
+
+impl TileInfo {
+    /// An owned copy of the tile, unowned and undeveloped
+    pub fn to_tile(&self) -> TileType {
+        match self {
+            TileInfo::Property(info) => TileType::Property(Property {
+                name: info.name.to_string(),
+                color: info.color.clone(),
+                kind: info.kind,
+                cost: info.cost,
+                rent: info.rent.to_vec(),
+                house_cost: info.house_cost,
+                hotel_cost: info.hotel_cost,
+                house_rent: info.house_rent.to_vec(),
+                owner: None,
+                houses: 0,
+                mortgaged: false,
+            }),
+            TileInfo::Special(special) => TileType::Special(special.clone()),
+        }
+    }
+}
+
+/// The standard board's tiles, in order from GO. Game accounts store only each tile's owner,
+/// buildings and mortgage, so this table is the single source of the rest.
+pub const STANDARD_BOARD: [TileInfo; BOARD_SIZE] = [
+    // Square 0 - GO
+    TileInfo::Special(SpecialTile::Go),
+
+    // Brown Group (1-3)
+    TileInfo::Property(PropertyInfo {
+        name: "Solana Genesis Block",
+        color: Color::Brown,
+        kind: TileKind::Street,
+        cost: 60,
+        rent: &[2, 10, 30, 90, 160, 250],
+        house_cost: 50,
+        hotel_cost: 250,
+        house_rent: &[10, 30, 90, 160, 250],
+    }),
+    TileInfo::Special(SpecialTile::CommunityChest),
+    TileInfo::Property(PropertyInfo {
+        name: "Metaplex Protocol",
+        color: Color::Brown,
+        kind: TileKind::Street,
+        cost: 60,
+        rent: &[4, 20, 60, 180, 320, 450],
+        house_cost: 50,
+        hotel_cost: 250,
+        house_rent: &[20, 60, 180, 320, 450],
+    }),
+
+    // Tax Square (4)
+    TileInfo::Special(SpecialTile::IncomeTax),
+
+    // First Railroad (5)
+    TileInfo::Property(PropertyInfo {
+        name: "Solana Network Rail",
+        color: Color::Brown,
+        kind: TileKind::Railroad,
+        cost: 200,
+        rent: &[25, 50, 100, 200],
+        house_cost: 0,
+        hotel_cost: 0,
+        house_rent: &[],
+    }),
+
+    // Light Blue Group (6-9)
+    TileInfo::Property(PropertyInfo {
+        name: "Serum DEX",
+        color: Color::LightBlue,
+        kind: TileKind::Street,
+        cost: 100,
+        rent: &[6, 30, 90, 270, 400, 550],
+        house_cost: 50,
+        hotel_cost: 250,
+        house_rent: &[30, 90, 270, 400, 550],
+    }),
+    TileInfo::Special(SpecialTile::Chance),
+    TileInfo::Property(PropertyInfo {
+        name: "Raydium AMM",
+        color: Color::LightBlue,
+        kind: TileKind::Street,
+        cost: 100,
+        rent: &[6, 30, 90, 270, 400, 550],
+        house_cost: 50,
+        hotel_cost: 250,
+        house_rent: &[30, 90, 270, 400, 550],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Orca DEX",
+        color: Color::LightBlue,
+        kind: TileKind::Street,
+        cost: 120,
+        rent: &[8, 40, 100, 300, 450, 600],
+        house_cost: 50,
+        hotel_cost: 250,
+        house_rent: &[40, 100, 300, 450, 600],
+    }),
+
+    // Jail (10)
+    TileInfo::Special(SpecialTile::Jail),
+
+    // Pink Group (11-14) with the first Utility (12)
+    TileInfo::Property(PropertyInfo {
+        name: "Magic Eden",
+        color: Color::Pink,
+        kind: TileKind::Street,
+        cost: 140,
+        rent: &[10, 50, 150, 450, 625, 750],
+        house_cost: 100,
+        hotel_cost: 500,
+        house_rent: &[50, 150, 450, 625, 750],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Helius RPC",
+        color: Color::Brown,
+        kind: TileKind::Utility,
+        cost: 150,
+        rent: &[4, 10],
+        house_cost: 0,
+        hotel_cost: 0,
+        house_rent: &[],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Tensor",
+        color: Color::Pink,
+        kind: TileKind::Street,
+        cost: 140,
+        rent: &[10, 50, 150, 450, 625, 750],
+        house_cost: 100,
+        hotel_cost: 500,
+        house_rent: &[50, 150, 450, 625, 750],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Hyperspace",
+        color: Color::Pink,
+        kind: TileKind::Street,
+        cost: 160,
+        rent: &[12, 60, 180, 500, 700, 900],
+        house_cost: 100,
+        hotel_cost: 500,
+        house_rent: &[60, 180, 500, 700, 900],
+    }),
+
+    // Second Railroad (15)
+    TileInfo::Property(PropertyInfo {
+        name: "Wormhole Rail",
+        color: Color::Brown,
+        kind: TileKind::Railroad,
+        cost: 200,
+        rent: &[25, 50, 100, 200],
+        house_cost: 0,
+        hotel_cost: 0,
+        house_rent: &[],
+    }),
+
+    // Orange Group (16-19)
+    TileInfo::Property(PropertyInfo {
+        name: "Marinade",
+        color: Color::Orange,
+        kind: TileKind::Street,
+        cost: 180,
+        rent: &[14, 70, 200, 550, 750, 950],
+        house_cost: 100,
+        hotel_cost: 500,
+        house_rent: &[70, 200, 550, 750, 950],
+    }),
+    TileInfo::Special(SpecialTile::CommunityChest),
+    TileInfo::Property(PropertyInfo {
+        name: "Lido",
+        color: Color::Orange,
+        kind: TileKind::Street,
+        cost: 180,
+        rent: &[14, 70, 200, 550, 750, 950],
+        house_cost: 100,
+        hotel_cost: 500,
+        house_rent: &[70, 200, 550, 750, 950],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "JPool",
+        color: Color::Orange,
+        kind: TileKind::Street,
+        cost: 200,
+        rent: &[16, 80, 220, 600, 800, 1000],
+        house_cost: 100,
+        hotel_cost: 500,
+        house_rent: &[80, 220, 600, 800, 1000],
+    }),
+
+    // Free Parking (20)
+    TileInfo::Special(SpecialTile::FreeParking),
+
+    // Red Group (21-24)
+    TileInfo::Property(PropertyInfo {
+        name: "Jupiter",
+        color: Color::Red,
+        kind: TileKind::Street,
+        cost: 220,
+        rent: &[18, 90, 250, 700, 875, 1050],
+        house_cost: 150,
+        hotel_cost: 750,
+        house_rent: &[90, 250, 700, 875, 1050],
+    }),
+    TileInfo::Special(SpecialTile::Chance),
+    TileInfo::Property(PropertyInfo {
+        name: "Orca",
+        color: Color::Red,
+        kind: TileKind::Street,
+        cost: 220,
+        rent: &[18, 90, 250, 700, 875, 1050],
+        house_cost: 150,
+        hotel_cost: 750,
+        house_rent: &[90, 250, 700, 875, 1050],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Raydium",
+        color: Color::Red,
+        kind: TileKind::Street,
+        cost: 240,
+        rent: &[20, 100, 300, 750, 925, 1100],
+        house_cost: 150,
+        hotel_cost: 750,
+        house_rent: &[100, 300, 750, 925, 1100],
+    }),
+
+    // Third Railroad (25)
+    TileInfo::Property(PropertyInfo {
+        name: "Firedancer Line",
+        color: Color::Brown,
+        kind: TileKind::Railroad,
+        cost: 200,
+        rent: &[25, 50, 100, 200],
+        house_cost: 0,
+        hotel_cost: 0,
+        house_rent: &[],
+    }),
+
+    // Yellow Group (26-29) with the second Utility (28)
+    TileInfo::Property(PropertyInfo {
+        name: "Pyth",
+        color: Color::Yellow,
+        kind: TileKind::Street,
+        cost: 260,
+        rent: &[22, 110, 330, 800, 975, 1150],
+        house_cost: 150,
+        hotel_cost: 750,
+        house_rent: &[110, 330, 800, 975, 1150],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Switchboard",
+        color: Color::Yellow,
+        kind: TileKind::Street,
+        cost: 260,
+        rent: &[22, 110, 330, 800, 975, 1150],
+        house_cost: 150,
+        hotel_cost: 750,
+        house_rent: &[110, 330, 800, 975, 1150],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Triton RPC",
+        color: Color::Brown,
+        kind: TileKind::Utility,
+        cost: 150,
+        rent: &[4, 10],
+        house_cost: 0,
+        hotel_cost: 0,
+        house_rent: &[],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Chainlink",
+        color: Color::Yellow,
+        kind: TileKind::Street,
+        cost: 280,
+        rent: &[24, 120, 360, 850, 1025, 1200],
+        house_cost: 150,
+        hotel_cost: 750,
+        house_rent: &[120, 360, 850, 1025, 1200],
+    }),
+
+    // Go To Jail (30)
+    TileInfo::Special(SpecialTile::GoToJail),
+
+    // Green Group (31-34)
+    TileInfo::Property(PropertyInfo {
+        name: "Metaplex",
+        color: Color::Green,
+        kind: TileKind::Street,
+        cost: 300,
+        rent: &[26, 130, 390, 900, 1100, 1275],
+        house_cost: 200,
+        hotel_cost: 1000,
+        house_rent: &[130, 390, 900, 1100, 1275],
+    }),
+    TileInfo::Property(PropertyInfo {
+        name: "Cardinal",
+        color: Color::Green,
+        kind: TileKind::Street,
+        cost: 300,
+        rent: &[26, 130, 390, 900, 1100, 1275],
+        house_cost: 200,
+        hotel_cost: 1000,
+        house_rent: &[130, 390, 900, 1100, 1275],
+    }),
+    TileInfo::Special(SpecialTile::CommunityChest),
+    TileInfo::Property(PropertyInfo {
+        name: "Goki",
+        color: Color::Green,
+        kind: TileKind::Street,
+        cost: 320,
+        rent: &[28, 150, 450, 1000, 1200, 1400],
+        house_cost: 200,
+        hotel_cost: 1000,
+        house_rent: &[150, 450, 1000, 1200, 1400],
+    }),
+
+    // Fourth Railroad (35) and Chance (36)
+    TileInfo::Property(PropertyInfo {
+        name: "Turbine Express",
+        color: Color::Brown,
+        kind: TileKind::Railroad,
+        cost: 200,
+        rent: &[25, 50, 100, 200],
+        house_cost: 0,
+        hotel_cost: 0,
+        house_rent: &[],
+    }),
+    TileInfo::Special(SpecialTile::Chance),
+
+    // Dark Blue Group (37-39)
+    TileInfo::Property(PropertyInfo {
+        name: "Solana Labs",
+        color: Color::DarkBlue,
+        kind: TileKind::Street,
+        cost: 350,
+        rent: &[35, 175, 500, 1100, 1300, 1500],
+        house_cost: 200,
+        hotel_cost: 1000,
+        house_rent: &[175, 500, 1100, 1300, 1500],
+    }),
+    TileInfo::Special(SpecialTile::LuxuryTax),
+    TileInfo::Property(PropertyInfo {
+        name: "Solana Foundation",
+        color: Color::DarkBlue,
+        kind: TileKind::Street,
+        cost: 400,
+        rent: &[50, 200, 600, 1400, 1700, 2000],
+        house_cost: 200,
+        hotel_cost: 1000,
+        house_rent: &[200, 600, 1400, 1700, 2000],
+    }),
+];
+
 pub fn create_board() -> Vec<TileType> {
-    vec![
-        // Square 0 - GO
-        TileType::Special(SpecialTile::Go),
-        
-        // Purple Group (1-2)
-        TileType::Property(Property {
-            name: "Solana Genesis Block".to_string(),
-            color: Color::Brown,
-            cost: 60,
-            rent: vec![2, 10, 30, 90, 160, 250],
-            house_cost: 50,
-            hotel_cost: 250,
-            house_rent: vec![10, 30, 90, 160, 250],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Special(SpecialTile::CommunityChest),
-        TileType::Property(Property {
-            name: "Metaplex Protocol".to_string(),
-            color: Color::Brown,
-            cost: 60,
-            rent: vec![4, 20, 60, 180, 320, 450],
-            house_cost: 50,
-            hotel_cost: 250,
-            house_rent: vec![20, 60, 180, 320, 450],
-            owner: None,
-            houses: 0,
-        }),
-        
-        // Tax Square (4)
-        TileType::Special(SpecialTile::IncomeTax),
-        
-        // First Railroad (5)
-        TileType::Property(Property {
-            name: "Solana Network Rail".to_string(),
-            color: Color::Brown,
-            cost: 200,
-            rent: vec![25, 50, 100, 200],
-            house_cost: 0,
-            hotel_cost: 0,
-            house_rent: vec![50, 100, 200],
-            owner: None,
-            houses: 0,
-        }),
-        
-        // Light Blue Group (6-8)
-        TileType::Property(Property {
-            name: "Serum DEX".to_string(),
-            color: Color::LightBlue,
-            cost: 100,
-            rent: vec![6, 30, 90, 270, 400, 550],
-            house_cost: 50,
-            hotel_cost: 250,
-            house_rent: vec![30, 90, 270, 400, 550],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Special(SpecialTile::Chance),
-        TileType::Property(Property {
-            name: "Raydium AMM".to_string(),
-            color: Color::LightBlue,
-            cost: 100,
-            rent: vec![6, 30, 90, 270, 400, 550],
-            house_cost: 50,
-            hotel_cost: 250,
-            house_rent: vec![30, 90, 270, 400, 550],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Orca DEX".to_string(),
-            color: Color::LightBlue,
-            cost: 120,
-            rent: vec![8, 40, 100, 300, 450, 600],
-            house_cost: 50,
-            hotel_cost: 250,
-            house_rent: vec![40, 100, 300, 450, 600],
-            owner: None,
-            houses: 0,
-        }),
-        
-        // Jail (10)
-        TileType::Special(SpecialTile::Jail),
-        
-        // Pink Group (11-13)
-        TileType::Property(Property {
-            name: "Magic Eden".to_string(),
-            color: Color::Pink,
-            cost: 140,
-            rent: vec![10, 50, 150, 450, 625, 750],
-            house_cost: 100,
-            hotel_cost: 500,
-            house_rent: vec![50, 150, 450, 625, 750],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Tensor".to_string(),
-            color: Color::Pink,
-            cost: 140,
-            rent: vec![10, 50, 150, 450, 625, 750],
-            house_cost: 100,
-            hotel_cost: 500,
-            house_rent: vec![50, 150, 450, 625, 750],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Hyperspace".to_string(),
-            color: Color::Pink,
-            cost: 160,
-            rent: vec![12, 60, 180, 500, 700, 900],
-            house_cost: 100,
-            hotel_cost: 500,
-            owner: None,
-            houses: 0,
-        }),
-
-        // Orange Group (14-16)
-        TileType::Property(Property {
-            name: "Marinade".to_string(),
-            color: Color::Orange,
-            cost: 180,
-            rent: vec![14, 70, 200, 550, 750, 950],
-            house_cost: 100,
-            hotel_cost: 500,
-            house_rent: vec![70, 200, 550, 750, 950],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Special(SpecialTile::CommunityChest),
-        TileType::Property(Property {
-            name: "Lido".to_string(),
-            color: Color::Orange,
-            cost: 180,
-            rent: vec![14, 70, 200, 550, 750, 950],
-            house_cost: 100,
-            hotel_cost: 500,
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "JPool".to_string(),
-            color: Color::Orange,
-            cost: 200,
-            rent: vec![16, 80, 220, 600, 800, 1000],
-            house_cost: 100,
-            hotel_cost: 500,
-            owner: None,
-            houses: 0,
-        }),
-
-        // Free Parking (20)
-        TileType::Special(SpecialTile::FreeParking),
-
-        // Red Group (21-23)
-        TileType::Property(Property {
-            name: "Jupiter".to_string(),
-            color: Color::Red,
-            cost: 220,
-            rent: vec![18, 90, 250, 700, 875, 1050],
-            house_cost: 150,
-            hotel_cost: 750,
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Special(SpecialTile::Chance),
-        TileType::Property(Property {
-            name: "Orca".to_string(),
-            color: Color::Red,
-            cost: 220,
-            rent: vec![18, 90, 250, 700, 875, 1050],
-            house_cost: 150,
-            hotel_cost: 750,
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Raydium".to_string(),
-            color: Color::Red,
-            cost: 240,
-            rent: vec![20, 100, 300, 750, 925, 1100],
-            house_cost: 150,
-            hotel_cost: 750,
-            house_rent: vec![100, 300, 750, 925, 1100],
-            owner: None,
-            houses: 0,
-        }),
-
-        // Yellow Group (24-26)
-        TileType::Property(Property {
-            name: "Pyth".to_string(),
-            color: Color::Yellow,
-            cost: 260,
-            rent: vec![22, 110, 330, 800, 975, 1150],
-            house_cost: 150,
-            hotel_cost: 750,
-            house_rent: vec![110, 330, 800, 975, 1150],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Switchboard".to_string(),
-            color: Color::Yellow,
-            cost: 260,
-            rent: vec![22, 110, 330, 800, 975, 1150],
-            house_cost: 150,
-            hotel_cost: 750,
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Chainlink".to_string(),
-            color: Color::Yellow,
-            cost: 280,
-            rent: vec![24, 120, 360, 850, 1025, 1200],
-            house_cost: 150,
-            hotel_cost: 750,
-            house_rent: vec![120, 360, 850, 1025, 1200],
-            owner: None,
-            houses: 0,
-        }),
-
-        // Go To Jail (30)
-        TileType::Special(SpecialTile::GoToJail),
-
-        // Green Group (31-33)
-        TileType::Property(Property {
-            name: "Metaplex".to_string(),
-            color: Color::Green,
-            cost: 300,
-            rent: vec![26, 130, 390, 900, 1100, 1275],
-            house_cost: 200,
-            hotel_cost: 1000,
-            house_rent: vec![130, 390, 900, 1100, 1275],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Property(Property {
-            name: "Cardinal".to_string(),
-            color: Color::Green,
-            cost: 300,
-            rent: vec![26, 130, 390, 900, 1100, 1275],
-            house_cost: 200,
-            hotel_cost: 1000,
-            house_rent: vec![130, 390, 900, 1100, 1275],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Special(SpecialTile::CommunityChest),
-        TileType::Property(Property {
-            name: "Goki".to_string(),
-            color: Color::Green,
-            cost: 320,
-            rent: vec![28, 150, 450, 1000, 1200, 1400],
-            house_cost: 200,
-            hotel_cost: 1000,
-            house_rent: vec![150, 450, 1000, 1200, 1400],
-            owner: None,
-            houses: 0,
-        }),
-
-        // Dark Blue Group (37-39)
-        TileType::Property(Property {
-            name: "Solana Labs".to_string(),
-            color: Color::DarkBlue,
-            cost: 350,
-            rent: vec![35, 175, 500, 1100, 1300, 1500],
-            house_cost: 200,
-            hotel_cost: 1000,
-            house_rent: vec![175, 500, 1100, 1300, 1500],
-            owner: None,
-            houses: 0,
-        }),
-        TileType::Special(SpecialTile::LuxuryTax),
-        TileType::Property(Property {
-            name: "Solana Foundation".to_string(),
-            color: Color::DarkBlue,
-            cost: 400,
-            rent: vec![50, 200, 600, 1400, 1700, 2000],
-            house_cost: 200,
-            hotel_cost: 1000,
-            house_rent: vec![200, 600, 1400, 1700, 2000],
-            owner: None,
-            houses: 0,
-        }),
-    ]
+    STANDARD_BOARD.iter().map(TileInfo::to_tile).collect()
 }
 
 pub fn create_community_chest() -> Vec<Card> {
     vec![
-        Card::CollectMoney(200),
-        Card::PayMoney(50),
+        Card::Move(0), // Advance to GO
+        Card::CollectMoney(200), // Bank error in your favor
+        Card::PayMoney(50), // Doctor's fee
+        Card::CollectMoney(50), // Sale of stock
         Card::GetOutOfJail,
-        Card::Move(0), // GO
-        Card::CollectMoney(100),
-        Card::PayMoney(100),
-        Card::Move(10), // Jail
+        Card::GoToJail,
+        Card::CollectMoney(100), // Holiday fund matures
+        Card::CollectMoney(20), // Income tax refund
+        Card::CollectFromEachPlayer(10), // It is your birthday
+        Card::CollectMoney(100), // Life insurance matures
+        Card::PayMoney(100), // Hospital fees
+        Card::PayMoney(50), // School fees
+        Card::CollectMoney(25), // Consultancy fee
+        Card::PayPerHouseAndHotel { house: 40, hotel: 115 }, // Street repairs
+        Card::CollectMoney(10), // Second prize in a beauty contest
+        Card::CollectMoney(100), // Inheritance
     ]
 }
 
 pub fn create_chance_cards() -> Vec<Card> {
     vec![
-        Card::CollectMoney(150),
-        Card::PayMoney(15),
+        Card::Move(39), // Advance to the last property
         Card::Move(0), // Advance to GO
+        Card::Move(24), // Advance to the third red property
+        Card::Move(11), // Advance to the first pink property
+        Card::AdvanceToNearest(TileKind::Railroad),
+        Card::AdvanceToNearest(TileKind::Railroad),
+        Card::AdvanceToNearest(TileKind::Utility),
+        Card::CollectMoney(50), // Bank pays you a dividend
         Card::GetOutOfJail,
-        Card::Move(10), // Go to Jail
-        Card::Move(5),  // Move to first Railroad
+        Card::MoveBack(3),
+        Card::GoToJail,
+        Card::PayPerHouseAndHotel { house: 25, hotel: 100 }, // General repairs
+        Card::PayMoney(15), // Speeding fine
+        Card::Move(5), // Take a trip to the first Railroad
+        Card::PayEachPlayer(50), // Elected chairman of the board
+        Card::CollectMoney(150), // Building loan matures
     ]
 }
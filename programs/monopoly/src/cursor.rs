@@ -0,0 +1,235 @@
+use solana_program::{
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader() {
+        let data = [7u8, 1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, b'h', b'i'];
+        let mut reader = Reader::new(&data);
+
+        // Test reads advance the cursor in order
+        assert_eq!(reader.read_u8().unwrap(), 7);
+        assert_eq!(reader.read_u32().unwrap(), 1);
+        assert_eq!(reader.read_u64().unwrap(), 2);
+        assert_eq!(reader.remaining(), 2);
+        assert_eq!(reader.read_string(2).unwrap(), "hi");
+        assert_eq!(reader.position(), data.len());
+
+        // Test running off the end is an error and leaves the cursor where it was
+        assert_eq!(reader.read_u8().unwrap_err(), ProgramError::InvalidAccountData);
+        assert_eq!(reader.position(), data.len());
+        let mut reader = Reader::new(&data[..3]);
+        assert_eq!(reader.read_u32().unwrap_err(), ProgramError::InvalidAccountData);
+        assert_eq!(reader.read_slice(usize::MAX).unwrap_err(), ProgramError::InvalidAccountData);
+        assert_eq!(reader.position(), 0);
+
+        // Test oversized lengths are rejected
+        let mut reader = Reader::new(&[5u8]);
+        assert_eq!(reader.read_len_u8(4).unwrap_err(), ProgramError::InvalidAccountData);
+        let mut reader = Reader::new(&[255u8, 255, 255, 255]);
+        assert_eq!(reader.read_len_u32(1024).unwrap_err(), ProgramError::InvalidAccountData);
+
+        // Test invalid UTF-8 is rejected
+        let mut reader = Reader::new(&[0xff, 0xfe]);
+        assert_eq!(reader.read_string(2).unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut data = [0xaau8; 20];
+        let mut writer = Writer::new(&mut data);
+        writer.write_u8(7);
+        let len_at = writer.position();
+        writer.write_u32(0);
+        writer.write_u64(2);
+        writer.write_bytes(b"hi");
+        writer.write_zeros(2);
+        writer.patch_u32(len_at, 1);
+        assert_eq!(writer.position(), 17);
+
+        let mut reader = Reader::new(&data);
+        assert_eq!(reader.read_u8().unwrap(), 7);
+        assert_eq!(reader.read_u32().unwrap(), 1);
+        assert_eq!(reader.read_u64().unwrap(), 2);
+        assert_eq!(reader.read_slice(4).unwrap(), b"hi\0\0");
+        assert_eq!(reader.read_slice(3).unwrap(), [0xaa; 3]);
+    }
+}
+
+/// Cursor over packed data that fails with `InvalidAccountData` instead of panicking when
+/// the data is shorter than its contents claim
+pub struct Reader<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(src: &'a [u8]) -> Self {
+        Reader { src, pos: 0 }
+    }
+
+    /// Bytes read so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes left to read
+    pub fn remaining(&self) -> usize {
+        self.src.len() - self.pos
+    }
+
+    /// Take the next `len` bytes; the cursor does not move if there are not enough
+    pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        if len > self.remaining() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let slice = &self.src[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(slice)
+    }
+
+    /// Take everything left, for a variable-length value that reads its own bounds
+    pub fn read_rest(&mut self) -> &'a [u8] {
+        let rest = &self.src[self.pos..];
+        self.pos = self.src.len();
+        rest
+    }
+
+    /// Step over `len` bytes, such as the unused tail of a fixed-size slot
+    pub fn skip(&mut self, len: usize) -> Result<(), ProgramError> {
+        self.read_slice(len).map(|_| ())
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ProgramError> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read_slice(N)?);
+        Ok(array)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Any non-zero byte reads as true
+    pub fn read_bool(&mut self) -> Result<bool, ProgramError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, ProgramError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_pubkey(&mut self) -> Result<Pubkey, ProgramError> {
+        Ok(Pubkey::from(self.read_array::<32>()?))
+    }
+
+    /// Read a one-byte length prefix, rejecting anything above `max`
+    pub fn read_len_u8(&mut self, max: usize) -> Result<usize, ProgramError> {
+        let len = self.read_u8()? as usize;
+        if len > max {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(len)
+    }
+
+    /// Read a four-byte length prefix, rejecting anything above `max`
+    pub fn read_len_u32(&mut self, max: usize) -> Result<usize, ProgramError> {
+        let len = self.read_u32()? as usize;
+        if len > max {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(len)
+    }
+
+    /// Read `len` bytes of UTF-8
+    pub fn read_string(&mut self, len: usize) -> Result<String, ProgramError> {
+        String::from_utf8(self.read_slice(len)?.to_vec())
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Unpack a `T` from the next `T::LEN` bytes
+    pub fn read_pack<T: Pack>(&mut self) -> Result<T, ProgramError> {
+        T::unpack_from_slice(self.read_slice(T::LEN)?)
+    }
+}
+
+/// Cursor for packing data. `pack_into_slice` has no way to report an error, so writing
+/// past the end panics like the slice indexing it replaces; `Pack::pack` checks the
+/// destination length before it gets here.
+pub struct Writer<'a> {
+    dst: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(dst: &'a mut [u8]) -> Self {
+        Writer { dst, pos: 0 }
+    }
+
+    /// Bytes written so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Hand out the next `len` bytes for the caller to fill
+    pub fn slot(&mut self, len: usize) -> &mut [u8] {
+        let slot = &mut self.dst[self.pos..self.pos + len];
+        self.pos += len;
+        slot
+    }
+
+    /// Hand out everything left, for a variable-length value
+    pub fn rest(&mut self) -> &mut [u8] {
+        let len = self.dst.len() - self.pos;
+        self.slot(len)
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.slot(bytes.len()).copy_from_slice(bytes);
+    }
+
+    /// Zero the next `len` bytes, such as the unused tail of a fixed-size slot
+    pub fn write_zeros(&mut self, len: usize) {
+        self.slot(len).fill(0);
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_pubkey(&mut self, value: &Pubkey) {
+        self.write_bytes(value.as_ref());
+    }
+
+    /// Overwrite a `u32` written earlier at `at`, for lengths only known after the data
+    pub fn patch_u32(&mut self, at: usize, value: u32) {
+        self.dst[at..at + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Pack `value` into the next `T::LEN` bytes
+    pub fn write_pack<T: Pack>(&mut self, value: &T) {
+        value.pack_into_slice(self.slot(T::LEN));
+    }
+}
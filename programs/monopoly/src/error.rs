@@ -1,7 +1,6 @@
 use num_derive::FromPrimitive;
 use solana_program::{
     decode_error::DecodeError,
-    msg,
     program_error::ProgramError,
 };
 use thiserror::Error;
@@ -46,6 +45,76 @@ pub enum MonopolyError {
     MaxHousesReached,
     #[error("Invalid Player Count")]
     InvalidPlayerCount,
+    #[error("Player Not In Game")]
+    PlayerNotInGame,
+    #[error("Player Already Joined")]
+    PlayerAlreadyJoined,
+    #[error("Not Game Creator")]
+    NotGameCreator,
+    #[error("Auction In Progress")]
+    AuctionInProgress,
+    #[error("No Active Auction")]
+    NoActiveAuction,
+    #[error("Auction Ended")]
+    AuctionEnded,
+    #[error("Auction Not Ended")]
+    AuctionNotEnded,
+    #[error("Bid Too Low")]
+    BidTooLow,
+    #[error("Property Mortgaged")]
+    PropertyMortgaged,
+    #[error("Property Not Mortgaged")]
+    PropertyNotMortgaged,
+    #[error("Invalid Trade")]
+    InvalidTrade,
+    #[error("Not Trade Party")]
+    NotTradeParty,
+    #[error("Stale Trade Offer")]
+    StaleTradeOffer,
+    #[error("Debt Outstanding")]
+    DebtOutstanding,
+    #[error("No Outstanding Debt")]
+    NoOutstandingDebt,
+    #[error("Not Debtor")]
+    NotDebtor,
+    #[error("Housing Shortage")]
+    HousingShortage,
+    #[error("Not In Jail")]
+    NotInJail,
+    #[error("No Jail Card")]
+    NoJailCard,
+    #[error("Invalid Card Deck")]
+    InvalidCardDeck,
+    #[error("Invalid Dice Phase")]
+    InvalidDicePhase,
+    #[error("Dice Already Committed")]
+    DiceAlreadyCommitted,
+    #[error("Invalid Dice Reveal")]
+    InvalidDiceReveal,
+    #[error("Dice Round Not Expired")]
+    DiceRoundNotExpired,
+    #[error("Dice Already Rolled")]
+    DiceAlreadyRolled,
+    #[error("Randomness Unavailable")]
+    RandomnessUnavailable,
+    #[error("Not Randomness Oracle")]
+    NotRandomnessOracle,
+    #[error("Pending Action Unresolved")]
+    PendingActionUnresolved,
+    #[error("No Pending Action")]
+    NoPendingAction,
+    #[error("Auction Disabled")]
+    AuctionDisabled,
+    #[error("Name Too Long")]
+    NameTooLong,
+    #[error("Invalid Account Type")]
+    InvalidAccountType,
+    #[error("Unsupported Account Version")]
+    UnsupportedAccountVersion,
+    #[error("Not Upgrade Authority")]
+    NotUpgradeAuthority,
+    #[error("Invalid Game Rules")]
+    InvalidGameRules,
 }
 
 impl From<MonopolyError> for ProgramError {
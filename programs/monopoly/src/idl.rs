@@ -0,0 +1,270 @@
+//! Machine-readable description of the program's wire format, in the Shank IDL style that
+//! Solita and beet clients read.
+//!
+//! Instruction arguments, account layouts and every type they use come from the Borsh schemas
+//! of `MonopolyInstruction` and the `layout` types, so the IDL cannot drift from the code.
+//! Instruction accounts come from `MonopolyInstruction::ACCOUNTS`, the table the instruction
+//! docs and `to_instruction` are built from, and errors from `MonopolyError`. `examples/idl.rs` prints the result; the checked-in copy
+//! at `idl/monopoly.json` is compared against it by the tests here.
+
+use std::collections::BTreeMap;
+
+use borsh::schema::{BorshSchemaContainer, Declaration, Definition, Fields};
+use borsh::BorshSchema;
+use num_traits::FromPrimitive;
+use serde_json::{json, Value};
+use crate::account::{AccountKind, ACCOUNT_VERSION};
+use crate::error::MonopolyError;
+use crate::instruction::{AccountSpec, MonopolyInstruction};
+use crate::layout::{
+    CardDeckLayout, GameAccountLayout, PlayerAccountLayout, PropertyAccountLayout, TradeOfferLayout,
+    RandomnessRequestLayout,
+};
+use crate::state::Card;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idl_is_up_to_date() {
+        let checked_in: Value = serde_json::from_str(include_str!("../../../idl/monopoly.json")).unwrap();
+        assert!(
+            checked_in == generate(),
+            "idl/monopoly.json is stale; regenerate it with `yarn idl` from the repository root"
+        );
+    }
+
+    #[test]
+    fn test_instructions() {
+        let idl = generate();
+        let instructions = idl["instructions"].as_array().unwrap();
+        assert_eq!(instructions.len(), 32);
+
+        // Test every instruction documents its accounts, with the game account first
+        for (tag, instruction) in instructions.iter().enumerate() {
+            assert_eq!(instruction["discriminant"]["value"], tag);
+            let accounts = instruction["accounts"].as_array().unwrap();
+            assert!(!accounts.is_empty(), "{} lists no accounts", instruction["name"]);
+        }
+        let create_game = &instructions[0];
+        assert_eq!(create_game["name"], "createGame");
+        assert_eq!(create_game["accounts"][0], json!({ "name": "game", "isMut": true, "isSigner": false, "docs": ["Game account"] }));
+        assert_eq!(create_game["accounts"][1]["name"], "creatorWallet");
+        assert_eq!(create_game["args"][0], json!({ "name": "playerName", "type": "string" }));
+        assert_eq!(create_game["args"][2], json!({ "name": "randomness", "type": { "defined": "RandomnessKind" } }));
+
+        let migrate = &instructions[31];
+        assert_eq!(migrate["accounts"][1], json!({ "name": "authority", "isMut": true, "isSigner": true, "docs": ["Program upgrade authority, covering any rent the new layout needs"] }));
+
+        let set_delegate = &instructions[8];
+        assert_eq!(set_delegate["args"][0]["type"], json!({ "option": "publicKey" }));
+    }
+
+    #[test]
+    fn test_accounts_and_errors() {
+        let idl = generate();
+        let game = &idl["accounts"][0];
+        assert_eq!(game["name"], "GameAccount");
+        assert_eq!(game["discriminator"], json!(b"MONOGAME"));
+        let fields = game["type"]["fields"].as_array().unwrap();
+        assert_eq!(fields[0], json!({ "name": "discriminator", "type": { "array": ["u8", 8] } }));
+        assert!(fields.contains(&json!({ "name": "freeParking", "type": "u64" })));
+        assert!(fields.contains(&json!({ "name": "rules", "type": { "defined": "GameRules" } })));
+
+        // Test nested types are listed once, and the decoded card type is included
+        let types: Vec<_> = idl["types"].as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap()).collect();
+        for name in ["PlayerLayout", "RandomnessLayout", "GameRules", "TradeTerms", "Card", "TileKind"] {
+            assert_eq!(types.iter().filter(|&&t| t == name).count(), 1, "{}", name);
+        }
+
+        let errors = idl["errors"].as_array().unwrap();
+        assert_eq!(errors[0], json!({ "code": 0, "name": "InvalidInstruction", "msg": "Invalid Instruction" }));
+        assert_eq!(errors.len(), MonopolyError::InvalidGameRules as usize + 1);
+    }
+}
+
+/// Build the IDL
+pub fn generate() -> Value {
+    let mut types = TypeCollector::default();
+
+    let instruction_schema = BorshSchemaContainer::for_type::<MonopolyInstruction>();
+    let variants = match instruction_schema.get_definition(instruction_schema.declaration()) {
+        Some(Definition::Enum { variants, .. }) => variants.clone(),
+        _ => unreachable!("instructions are an enum"),
+    };
+    let accounts: BTreeMap<&str, &[AccountSpec]> = MonopolyInstruction::ACCOUNTS.iter().copied().collect();
+    let instructions: Vec<Value> = variants
+        .iter()
+        .map(|(tag, name, declaration)| {
+            let args = match instruction_schema.get_definition(declaration) {
+                Some(Definition::Struct { fields: Fields::NamedFields(fields) }) => {
+                    types.fields(&instruction_schema, fields)
+                }
+                _ => Vec::new(),
+            };
+            json!({
+                "name": camel_case(name),
+                "accounts": accounts[name.as_str()].iter().map(|account| json!({
+                    "name": camel_case(account.name),
+                    "isMut": account.writable,
+                    "isSigner": account.signer,
+                    "docs": [account.docs],
+                })).collect::<Vec<_>>(),
+                "args": args,
+                "discriminant": { "type": "u8", "value": tag },
+            })
+        })
+        .collect();
+
+    let accounts = vec![
+        types.account::<GameAccountLayout>("GameAccount", AccountKind::Game),
+        types.account::<PlayerAccountLayout>("PlayerAccount", AccountKind::Player),
+        types.account::<PropertyAccountLayout>("PropertyAccount", AccountKind::Property),
+        types.account::<CardDeckLayout>("CardDeck", AccountKind::CardDeck),
+        types.account::<TradeOfferLayout>("TradeOffer", AccountKind::TradeOffer),
+        types.account::<RandomnessRequestLayout>("RandomnessRequest", AccountKind::RandomnessRequest),
+    ];
+
+    // Card deck accounts hold cards as raw bytes, so the card type is not reached from a field
+    let card_schema = BorshSchemaContainer::for_type::<Card>();
+    types.add(&card_schema, card_schema.declaration());
+
+    let errors: Vec<Value> = (0..)
+        .map_while(MonopolyError::from_u32)
+        .map(|error| json!({
+            "code": error as u32,
+            "name": format!("{:?}", error),
+            "msg": error.to_string(),
+        }))
+        .collect();
+
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "name": "monopoly",
+        "instructions": instructions,
+        "accounts": accounts,
+        "types": types.defined.into_values().collect::<Vec<_>>(),
+        "errors": errors,
+        "metadata": {
+            "origin": "shank",
+            "address": crate::id().to_string(),
+            "accountVersion": ACCOUNT_VERSION,
+        },
+    })
+}
+
+// Named types reached from instructions and accounts, keyed by name so each is listed once
+#[derive(Default)]
+struct TypeCollector {
+    defined: BTreeMap<Declaration, Value>,
+}
+
+impl TypeCollector {
+    fn account<T: BorshSchema>(&mut self, name: &str, kind: AccountKind) -> Value {
+        let schema = BorshSchemaContainer::for_type::<T>();
+        let fields = match schema.get_definition(schema.declaration()) {
+            Some(Definition::Struct { fields: Fields::NamedFields(fields) }) => {
+                self.fields(&schema, fields)
+            }
+            _ => unreachable!("account layouts are structs with named fields"),
+        };
+        json!({
+            "name": name,
+            "discriminator": kind.discriminator(),
+            "type": { "kind": "struct", "fields": fields },
+        })
+    }
+
+    fn fields(&mut self, schema: &BorshSchemaContainer, fields: &[(String, Declaration)]) -> Vec<Value> {
+        fields
+            .iter()
+            .map(|(name, declaration)| json!({
+                "name": camel_case(name),
+                "type": self.type_of(schema, declaration),
+            }))
+            .collect()
+    }
+
+    // The IDL type of a declaration, recording any named type it refers to
+    fn type_of(&mut self, schema: &BorshSchemaContainer, declaration: &Declaration) -> Value {
+        match declaration.as_str() {
+            "String" => return json!("string"),
+            "Pubkey" => return json!("publicKey"),
+            _ => {}
+        }
+        match schema.get_definition(declaration) {
+            None | Some(Definition::Primitive(_)) => json!(declaration),
+            Some(Definition::Sequence { length_width, length_range, elements }) => {
+                if *length_width == Definition::ARRAY_LENGTH_WIDTH {
+                    json!({ "array": [self.type_of(schema, elements), length_range.end()] })
+                } else if elements == "u8" {
+                    json!("bytes")
+                } else {
+                    json!({ "vec": self.type_of(schema, elements) })
+                }
+            }
+            Some(Definition::Tuple { elements }) => {
+                json!({ "tuple": elements.iter().map(|e| self.type_of(schema, e)).collect::<Vec<_>>() })
+            }
+            Some(Definition::Enum { variants, .. }) if declaration.starts_with("Option<") => {
+                json!({ "option": self.type_of(schema, &variants[1].2) })
+            }
+            Some(_) => {
+                self.add(schema, declaration);
+                json!({ "defined": declaration })
+            }
+        }
+    }
+
+    fn add(&mut self, schema: &BorshSchemaContainer, declaration: &Declaration) {
+        if self.defined.contains_key(declaration) {
+            return;
+        }
+        let ty = match schema.get_definition(declaration) {
+            Some(Definition::Struct { fields: Fields::NamedFields(fields) }) => {
+                json!({ "kind": "struct", "fields": self.fields(schema, fields) })
+            }
+            Some(Definition::Enum { variants, .. }) => {
+                let variants: Vec<Value> = variants
+                    .iter()
+                    .map(|(_, name, variant)| match schema.get_definition(variant) {
+                        Some(Definition::Struct { fields: Fields::NamedFields(fields) }) => {
+                            json!({ "name": name, "fields": self.fields(schema, fields) })
+                        }
+                        Some(Definition::Struct { fields: Fields::UnnamedFields(fields) }) => {
+                            let fields: Vec<Value> = fields.iter().map(|f| self.type_of(schema, f)).collect();
+                            json!({ "name": name, "fields": fields })
+                        }
+                        _ => json!({ "name": name }),
+                    })
+                    .collect();
+                json!({ "kind": "enum", "variants": variants })
+            }
+            other => unreachable!("{} is not a struct or enum: {:?}", declaration, other),
+        };
+        self.defined.insert(declaration.clone(), json!({ "name": declaration, "type": ty }));
+    }
+}
+
+// "player_name" and "CreateGame" become "playerName" and "createGame"
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, word) in name.split('_').filter(|w| !w.is_empty()).enumerate() {
+        let mut chars = word.chars();
+        let first = chars.next().unwrap();
+        if i == 0 {
+            out.push(first.to_ascii_lowercase());
+        } else {
+            out.push(first.to_ascii_uppercase());
+        }
+        // Words written in capitals, such as "VRF", are not acronyms in camel case
+        let rest: String = chars.collect();
+        if word.chars().all(|c| c.is_ascii_uppercase()) {
+            out.push_str(&rest.to_lowercase());
+        } else {
+            out.push_str(&rest);
+        }
+    }
+    out
+}
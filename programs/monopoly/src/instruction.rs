@@ -1,93 +1,409 @@
 use solana_program::{
     instruction::{AccountMeta, Instruction},
-    program_error::ProgramError,
-    program_pack::{Pack, Sealed},
     pubkey::Pubkey,
 };
-use crate::account::DeckType;
-
-#[derive(Debug)]
-pub enum MonopolyInstruction {
-    /// Initialize a new game
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Rent payer
-    InitGame {
-        player_names: Vec<String>,
-    },
-
-    /// Move player and process tile effects
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Current player
-    MovePlayer,
-
-    /// Buy property
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Player buying property
-    BuyProperty {
-        property_index: u8,
-    },
-
-    /// Build house/hotel
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Property owner
-    BuildHouse {
-        property_index: u8,
-    },
-
-    /// Pay rent
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Player paying rent
-    /// 2. `[]` Property owner
-    PayRent {
-        property_index: u8,
-    },
-
-    /// End current player's turn and move to next player
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Current player
-    NextTurn,
-
-    /// Check if there is a winner
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    CheckWinner,
-
-    /// Draw and process a card from Community Chest or Chance
-    /// Accounts expected:
-    /// 0. `[writable]` Game account
-    /// 1. `[signer]` Current player
-    /// 2. `[writable]` Card deck account
-    /// 3. `[]` Clock sysvar
-    DrawCard {
-        deck_type: DeckType,
-    },
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use crate::account::{DeckType, AccountKind};
+use crate::state::{TradeTerms, RandomnessKind, GameRules};
+
+/// An account an instruction expects, at its position in the instruction's account list
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccountSpec {
+    /// Name clients know the account by
+    pub name: &'static str,
+    pub writable: bool,
+    pub signer: bool,
+    /// What the account is
+    pub docs: &'static str,
 }
 
-impl Sealed for MonopolyInstruction {}
+impl AccountSpec {
+    const fn new(name: &'static str, docs: &'static str) -> Self {
+        AccountSpec { name, writable: false, signer: false, docs }
+    }
+}
+
+// Declares the instruction enum from variants that each list their accounts in an
+// `#[accounts(name [flags] "docs", ...)]` line. The list becomes the variant's "Accounts
+// expected" docs and its entry in `MonopolyInstruction::ACCOUNTS`, which `to_instruction` and
+// the IDL read, so the three cannot disagree.
+macro_rules! instructions {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                #[accounts($($account:ident [$($flag:ident),*] $account_docs:literal),* $(,)?)]
+                $variant:ident $({ $($fields:tt)* })?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[doc = $doc])*
+                #[doc = ""]
+                #[doc = "Accounts expected:"]
+                $(#[doc = concat!(
+                    "- `", stringify!($account), "` `[", stringify!($($flag),*), "]` ", $account_docs
+                )])*
+                $variant $({ $($fields)* })?,
+            )*
+        }
+
+        impl $name {
+            /// Accounts each instruction expects, in order, listed by variant name in tag order
+            pub const ACCOUNTS: &'static [(&'static str, &'static [AccountSpec])] = &[
+                $((stringify!($variant), &[
+                    $(AccountSpec {
+                        $($flag: true,)*
+                        ..AccountSpec::new(stringify!($account), $account_docs)
+                    }),*
+                ])),*
+            ];
+        }
+    };
+}
+
+instructions! {
+    /// Instructions are Borsh encoded: a one-byte tag in declaration order, then the variant's
+    /// fields. `idl::generate` describes the same encoding for clients.
+    #[derive(Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+    pub enum MonopolyInstruction {
+        /// Create a new game lobby with the creator seated as the first player
+        #[accounts(
+            game [writable] "Game account",
+            creator_wallet [signer] "Creator wallet",
+        )]
+        CreateGame {
+            /// At most `MAX_NAME_LEN` bytes
+            player_name: String,
+            max_players: u8,
+            /// Randomness backend used for the whole game
+            randomness: RandomnessKind,
+            /// House rules used for the whole game
+            rules: GameRules,
+        },
+
+        /// Move player by the dice a commit–reveal round settled and process tile effects; doubles
+        /// earn another roll. Only a seeded game may move without a settled round.
+        #[accounts(
+            game [writable] "Game account",
+            current_player [signer] "Current player",
+        )]
+        MovePlayer,
+
+        /// Buy the property the current player has just landed on
+        #[accounts(
+            game [writable] "Game account",
+            player_buying_property [signer] "Player buying property",
+        )]
+        BuyProperty {
+            property_index: u8,
+        },
+
+        /// Build house/hotel
+        #[accounts(
+            game [writable] "Game account",
+            property_owner [signer] "Property owner",
+        )]
+        BuildHouse {
+            property_index: u8,
+        },
+
+        /// Pay the rent owed for the property the player landed on
+        #[accounts(
+            game [writable] "Game account",
+            player_paying_rent [signer] "Player paying rent",
+            property_owner [] "Property owner",
+        )]
+        PayRent {
+            property_index: u8,
+        },
+
+        /// End current player's turn and move to next player; the landed tile must be resolved first
+        #[accounts(
+            game [writable] "Game account",
+            current_player [signer] "Current player",
+        )]
+        NextTurn,
+
+        /// Check if there is a winner
+        #[accounts(
+            game [writable] "Game account",
+        )]
+        CheckWinner,
+
+        /// Draw the top card of the Community Chest or Chance pile the player landed on and process it
+        #[accounts(
+            game [writable] "Game account",
+            current_player [signer] "Current player",
+            card_deck [writable] "Card deck account: the game's deck of this type, or a blank account \
+                the first draw sets up and binds to the game",
+            randomness [] "Randomness account: SlotHashes sysvar or fulfilled randomness request, per \
+                the game's source; only read when the pile has to be shuffled",
+        )]
+        DrawCard {
+            deck_type: DeckType,
+        },
+
+        /// Set or clear the delegate key allowed to play on a wallet's behalf. Allowed in any
+        /// phase, since it changes who may act for a seat rather than the turn.
+        #[accounts(
+            game [writable] "Game account",
+            player_wallet [signer] "Player wallet",
+        )]
+        SetDelegate {
+            delegate: Option<Pubkey>,
+        },
+
+        /// Take a seat in an open lobby
+        #[accounts(
+            game [writable] "Game account",
+            player_wallet [signer] "Player wallet",
+        )]
+        JoinGame {
+            /// At most `MAX_NAME_LEN` bytes
+            player_name: String,
+        },
+
+        /// Give up a seat in an open lobby
+        #[accounts(
+            game [writable] "Game account",
+            player_wallet [signer] "Player wallet",
+        )]
+        LeaveGame,
+
+        /// Close the lobby and start play
+        #[accounts(
+            game [writable] "Game account",
+            creator_wallet [signer] "Creator wallet",
+        )]
+        StartGame,
+
+        /// Put the unowned property the current player is standing on, or one a bankruptcy
+        /// returned to the bank, up for auction
+        #[accounts(
+            game [writable] "Game account",
+            current_player [signer] "Current player",
+            clock_sysvar [] "Clock sysvar",
+        )]
+        StartAuction {
+            property_index: u8,
+        },
+
+        /// Bid on the running auction
+        #[accounts(
+            game [writable] "Game account",
+            bidding_player [signer] "Bidding player",
+            clock_sysvar [] "Clock sysvar",
+        )]
+        PlaceBid {
+            amount: u64,
+        },
+
+        /// Close the auction once its deadline has passed and hand the property to the highest bidder
+        #[accounts(
+            game [writable] "Game account",
+            any_player [signer] "Any player",
+            clock_sysvar [] "Clock sysvar",
+        )]
+        SettleAuction,
+
+        /// Mortgage an owned property to the bank for half its cost
+        #[accounts(
+            game [writable] "Game account",
+            property_owner [signer] "Property owner",
+        )]
+        Mortgage {
+            property_index: u8,
+        },
+
+        /// Lift a mortgage by repaying it with 10% interest
+        #[accounts(
+            game [writable] "Game account",
+            property_owner [signer] "Property owner",
+        )]
+        Unmortgage {
+            property_index: u8,
+        },
+
+        /// Offer another player a swap of properties, cash and Get Out of Jail cards
+        #[accounts(
+            game [] "Game account",
+            trade_offer [writable] "Trade offer account",
+            proposing_player [signer] "Proposing player",
+        )]
+        ProposeTrade {
+            counterparty: Pubkey,
+            offered: TradeTerms,
+            requested: TradeTerms,
+        },
+
+        /// Accept a trade offer and exchange both sides
+        #[accounts(
+            game [writable] "Game account",
+            trade_offer [writable] "Trade offer account",
+            counterparty [signer] "Counterparty",
+        )]
+        AcceptTrade,
+
+        /// Turn down a trade offer. Allowed in any phase, including after the game ends, since
+        /// closing an offer moves nothing.
+        #[accounts(
+            game [] "Game account",
+            trade_offer [writable] "Trade offer account",
+            counterparty [signer] "Counterparty",
+        )]
+        RejectTrade,
+
+        /// Withdraw a trade offer. Allowed in any phase, like `RejectTrade`.
+        #[accounts(
+            game [] "Game account",
+            trade_offer [writable] "Trade offer account",
+            proposing_player [signer] "Proposing player",
+        )]
+        CancelTrade,
+
+        /// Settle an outstanding debt once enough cash has been raised
+        #[accounts(
+            game [writable] "Game account",
+            indebted_player [signer] "Indebted player",
+        )]
+        PayDebt,
+
+        /// Give up, handing all assets to the creditor (or the bank) and leaving the game.
+        /// Jail cards going to the bank are returned to the card deck they were drawn from
+        #[accounts(
+            game [writable] "Game account",
+            indebted_player [signer] "Indebted player",
+            community_chest_deck [writable] "Community Chest card deck account",
+            chance_deck [writable] "Chance card deck account",
+        )]
+        DeclareBankruptcy,
+
+        /// Sell a house (or break a hotel back into four houses) to the bank for half its cost
+        #[accounts(
+            game [writable] "Game account",
+            property_owner [signer] "Property owner",
+        )]
+        SellHouse {
+            property_index: u8,
+        },
+
+        /// Pay the fine to leave jail before rolling
+        #[accounts(
+            game [writable] "Game account",
+            jailed_player [signer] "Jailed player",
+        )]
+        PayBail,
+
+        /// Spend a Get Out of Jail Free card, returning it to the deck it was drawn from
+        #[accounts(
+            game [writable] "Game account",
+            jailed_player [signer] "Jailed player",
+            card_deck [writable] "Card deck account the card came from",
+        )]
+        UseJailCard,
+
+        /// Commit to a secret for the current player's dice, opening a round if none is running
+        #[accounts(
+            game [writable] "Game account",
+            any_active_player [signer] "Any active player",
+            clock_sysvar [] "Clock sysvar",
+        )]
+        CommitDice {
+            /// Keccak hash of the secret followed by the player's wallet
+            commitment: [u8; 32],
+        },
+
+        /// Reveal the secret behind a dice commitment; the last reveal settles the roll. A slot hash
+        /// game rolls on the hash of the slot the commit phase closed on, so it cannot settle in that slot.
+        #[accounts(
+            game [writable] "Game account",
+            committed_player [signer] "Committed player",
+            clock_sysvar [] "Clock sysvar",
+            randomness [] "Randomness account: SlotHashes sysvar or fulfilled randomness request, per \
+                the game's source",
+        )]
+        RevealDice {
+            secret: [u8; 32],
+        },
+
+        /// Settle a dice round whose reveal window has closed, fining players who did not reveal
+        #[accounts(
+            game [writable] "Game account",
+            any_player [signer] "Any player",
+            clock_sysvar [] "Clock sysvar",
+            randomness [] "Randomness account: SlotHashes sysvar or fulfilled randomness request, per \
+                the game's source",
+        )]
+        SettleDice,
+
+        /// Ask the game's trusted oracle for its next random draw. Each draw takes one request,
+        /// and only that request's output is accepted for it.
+        #[accounts(
+            game [writable] "Game account",
+            randomness_request [writable] "Randomness request account",
+            any_player [signer] "Any player",
+        )]
+        RequestRandomness,
+
+        /// Oracle callback delivering the output for a request. The output is taken on trust: no
+        /// proof is checked, so the oracle authority can post any value it likes.
+        #[accounts(
+            game [] "Game account",
+            randomness_request [writable] "Randomness request account",
+            oracle_authority [signer] "Oracle authority configured for the game",
+        )]
+        FulfillRandomness {
+            output: [u8; 32],
+        },
+
+        /// Upgrade a version 1 account, which has no discriminator or version, to the current layout.
+        /// Version 1 seats were not bound to wallets, so the wallets taking them follow the accounts
+        /// below as signers: one per seat in seat order for a game, the player's wallet for a player
+        /// account, and none for a card deck. Version 1 data carries no kind and little proof of
+        /// who sat where, so only the program's upgrade authority may migrate.
+        #[accounts(
+            account [writable] "Account to migrate",
+            authority [writable, signer] "Program upgrade authority, covering any rent the new layout needs",
+            program_data [] "The program's ProgramData account, naming its upgrade authority",
+            rent_sysvar [] "Rent sysvar",
+            system_program [] "System program",
+        )]
+        MigrateAccount {
+            kind: AccountKind,
+        },
+    }
+}
 
 impl MonopolyInstruction {
-    pub fn try_from_slice(input: &[u8]) -> Result<Self, ProgramError> {
-        Self::unpack_from_slice(input)
+    /// Accounts this instruction expects, in order
+    pub fn accounts(&self) -> &'static [AccountSpec] {
+        let data = borsh::to_vec(self).expect("instruction serializes");
+        Self::ACCOUNTS[data[0] as usize].1
     }
 
+    /// Build the instruction from the keys of its accounts, in the order `accounts` lists them.
+    /// Keys past the listed accounts are read-only signers, such as the seat wallets
+    /// `MigrateAccount` takes.
     pub fn to_instruction(
         &self,
         program_id: &Pubkey,
         accounts: &[&Pubkey],
     ) -> Instruction {
-        let mut account_metas = Vec::new();
-        for account in accounts {
-            account_metas.push(AccountMeta::new(**account, true));
-        }
-
-        let mut data = Vec::with_capacity(Self::LEN);
-        self.pack_into_slice(&mut data);
+        let data = borsh::to_vec(self).expect("instruction serializes");
+        let specs = Self::ACCOUNTS[data[0] as usize].1;
+        let account_metas = accounts
+            .iter()
+            .enumerate()
+            .map(|(index, &&key)| match specs.get(index) {
+                Some(spec) if spec.writable => AccountMeta::new(key, spec.signer),
+                Some(spec) => AccountMeta::new_readonly(key, spec.signer),
+                None => AccountMeta::new_readonly(key, true),
+            })
+            .collect();
 
         Instruction {
             program_id: *program_id,
@@ -100,100 +416,197 @@ impl MonopolyInstruction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_program::program_pack::Pack;
+
+    fn round_trip(instruction: &MonopolyInstruction) -> MonopolyInstruction {
+        borsh::from_slice(&borsh::to_vec(instruction).unwrap()).unwrap()
+    }
 
     #[test]
     fn test_instruction_pack() {
-        // Test InitGame instruction
-        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
-        let init_instruction = MonopolyInstruction::InitGame { player_names: player_names.clone() };
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        init_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
-        assert!(matches!(unpacked, MonopolyInstruction::InitGame { player_names: ref names } if names == &player_names));
+        // Test CreateGame instruction
+        let create_instruction = MonopolyInstruction::CreateGame {
+            player_name: "Alice".to_string(),
+            max_players: 4,
+            randomness: RandomnessKind::Seeded { seed: 9 },
+            rules: GameRules { free_parking_jackpot: true, ..GameRules::default() },
+        };
+        let unpacked = round_trip(&create_instruction);
+        assert!(matches!(unpacked, MonopolyInstruction::CreateGame { ref player_name, max_players, randomness: RandomnessKind::Seeded { seed: 9 }, ref rules } if player_name == "Alice" && max_players == 4 && rules.free_parking_jackpot));
 
         // Test MovePlayer instruction
         let move_instruction = MonopolyInstruction::MovePlayer;
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        move_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&move_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::MovePlayer));
 
         // Test BuyProperty instruction
         let buy_instruction = MonopolyInstruction::BuyProperty { property_index: 5 };
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        buy_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&buy_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::BuyProperty { property_index } if property_index == 5));
 
         // Test BuildHouse instruction
         let build_instruction = MonopolyInstruction::BuildHouse { property_index: 3 };
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        build_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&build_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::BuildHouse { property_index } if property_index == 3));
 
         // Test PayRent instruction
         let rent_instruction = MonopolyInstruction::PayRent { property_index: 7 };
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        rent_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&rent_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::PayRent { property_index } if property_index == 7));
 
         // Test NextTurn instruction
         let next_turn_instruction = MonopolyInstruction::NextTurn;
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        next_turn_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&next_turn_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::NextTurn));
 
         // Test CheckWinner instruction
         let check_winner_instruction = MonopolyInstruction::CheckWinner;
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        check_winner_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&check_winner_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::CheckWinner));
 
         // Test DrawCard instruction
         let draw_instruction = MonopolyInstruction::DrawCard { deck_type: DeckType::CommunityChest };
-        let mut packed = vec![0u8; MonopolyInstruction::LEN];
-        draw_instruction.pack_into_slice(&mut packed);
-        let unpacked = MonopolyInstruction::try_from_slice(&packed).unwrap();
+        let unpacked = round_trip(&draw_instruction);
         assert!(matches!(unpacked, MonopolyInstruction::DrawCard { deck_type } if matches!(deck_type, DeckType::CommunityChest)));
+
+        // Test SetDelegate instruction
+        let delegate = Pubkey::new_unique();
+        let delegate_instruction = MonopolyInstruction::SetDelegate { delegate: Some(delegate) };
+        let unpacked = round_trip(&delegate_instruction);
+        assert!(matches!(unpacked, MonopolyInstruction::SetDelegate { delegate: Some(key) } if key == delegate));
+
+        let clear_instruction = MonopolyInstruction::SetDelegate { delegate: None };
+        let unpacked = round_trip(&clear_instruction);
+        assert!(matches!(unpacked, MonopolyInstruction::SetDelegate { delegate: None }));
+
+        // Test lobby instructions
+        let join_instruction = MonopolyInstruction::JoinGame { player_name: "Bob".to_string() };
+        let unpacked = round_trip(&join_instruction);
+        assert!(matches!(unpacked, MonopolyInstruction::JoinGame { ref player_name } if player_name == "Bob"));
+
+        let unpacked = round_trip(&MonopolyInstruction::LeaveGame);
+        assert!(matches!(unpacked, MonopolyInstruction::LeaveGame));
+
+        let unpacked = round_trip(&MonopolyInstruction::StartGame);
+        assert!(matches!(unpacked, MonopolyInstruction::StartGame));
+
+        // Test auction instructions
+        let unpacked = round_trip(&MonopolyInstruction::StartAuction { property_index: 3 });
+        assert!(matches!(unpacked, MonopolyInstruction::StartAuction { property_index: 3 }));
+
+        let unpacked = round_trip(&MonopolyInstruction::PlaceBid { amount: 1_000_000 });
+        assert!(matches!(unpacked, MonopolyInstruction::PlaceBid { amount: 1_000_000 }));
+
+        let unpacked = round_trip(&MonopolyInstruction::SettleAuction);
+        assert!(matches!(unpacked, MonopolyInstruction::SettleAuction));
+
+        // Test mortgage instructions
+        let unpacked = round_trip(&MonopolyInstruction::Mortgage { property_index: 39 });
+        assert!(matches!(unpacked, MonopolyInstruction::Mortgage { property_index: 39 }));
+
+        let unpacked = round_trip(&MonopolyInstruction::Unmortgage { property_index: 39 });
+        assert!(matches!(unpacked, MonopolyInstruction::Unmortgage { property_index: 39 }));
+
+        // Test trade instructions
+        let counterparty = Pubkey::new_unique();
+        let offered = TradeTerms { properties: vec![1, 3], cash: 100, jail_cards: 0 };
+        let requested = TradeTerms { properties: vec![], cash: 0, jail_cards: 1 };
+        let unpacked = round_trip(&MonopolyInstruction::ProposeTrade {
+            counterparty,
+            offered: offered.clone(),
+            requested: requested.clone(),
+        });
+        assert!(matches!(
+            unpacked,
+            MonopolyInstruction::ProposeTrade { counterparty: c, offered: ref o, requested: ref r }
+                if c == counterparty && *o == offered && *r == requested
+        ));
+
+        for (instruction, tag) in [
+            (MonopolyInstruction::AcceptTrade, 18),
+            (MonopolyInstruction::RejectTrade, 19),
+            (MonopolyInstruction::CancelTrade, 20),
+        ] {
+            let packed = borsh::to_vec(&instruction).unwrap();
+            assert_eq!(packed, [tag]);
+        }
+        let unpacked = borsh::from_slice::<MonopolyInstruction>(&[18]).unwrap();
+        assert!(matches!(unpacked, MonopolyInstruction::AcceptTrade));
+
+        // Test debt instructions
+        let unpacked = round_trip(&MonopolyInstruction::PayDebt);
+        assert!(matches!(unpacked, MonopolyInstruction::PayDebt));
+
+        let unpacked = round_trip(&MonopolyInstruction::DeclareBankruptcy);
+        assert!(matches!(unpacked, MonopolyInstruction::DeclareBankruptcy));
+
+        let unpacked = round_trip(&MonopolyInstruction::SellHouse { property_index: 39 });
+        assert!(matches!(unpacked, MonopolyInstruction::SellHouse { property_index: 39 }));
+
+        let unpacked = round_trip(&MonopolyInstruction::PayBail);
+        assert!(matches!(unpacked, MonopolyInstruction::PayBail));
+
+        let unpacked = round_trip(&MonopolyInstruction::UseJailCard);
+        assert!(matches!(unpacked, MonopolyInstruction::UseJailCard));
+
+        let unpacked = round_trip(&MonopolyInstruction::CommitDice { commitment: [3; 32] });
+        assert!(matches!(unpacked, MonopolyInstruction::CommitDice { commitment } if commitment == [3; 32]));
+
+        let unpacked = round_trip(&MonopolyInstruction::RevealDice { secret: [4; 32] });
+        assert!(matches!(unpacked, MonopolyInstruction::RevealDice { secret } if secret == [4; 32]));
+
+        let unpacked = round_trip(&MonopolyInstruction::SettleDice);
+        assert!(matches!(unpacked, MonopolyInstruction::SettleDice));
+
+        let unpacked = round_trip(&MonopolyInstruction::RequestRandomness);
+        assert!(matches!(unpacked, MonopolyInstruction::RequestRandomness));
+
+        let unpacked = round_trip(&MonopolyInstruction::FulfillRandomness { output: [6; 32] });
+        assert!(matches!(unpacked, MonopolyInstruction::FulfillRandomness { output } if output == [6; 32]));
+
+        let unpacked = round_trip(&MonopolyInstruction::MigrateAccount { kind: AccountKind::CardDeck });
+        assert!(matches!(unpacked, MonopolyInstruction::MigrateAccount { kind: AccountKind::CardDeck }));
+
+        // Test an unknown account kind and trailing bytes are both rejected
+        let mut packed = borsh::to_vec(&MonopolyInstruction::MigrateAccount { kind: AccountKind::CardDeck }).unwrap();
+        packed[1] = 6;
+        assert!(borsh::from_slice::<MonopolyInstruction>(&packed).is_err());
+        assert!(borsh::from_slice::<MonopolyInstruction>(&[5, 0]).is_err());
     }
 
     #[test]
     fn test_instruction_unpack() {
-        // Test InitGame instruction
-        let mut init_data = vec![0u8]; // Variant index 0
-        let player_names = vec!["Alice".to_string(), "Bob".to_string()];
-        let names_len = player_names.len() as u32;
-        init_data.extend_from_slice(&names_len.to_le_bytes());
-        for name in &player_names {
-            let name_bytes = name.as_bytes();
-            init_data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-            init_data.extend_from_slice(name_bytes);
-        }
-        let mut rest = &init_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        // Test CreateGame instruction
+        let mut create_data = vec![0u8]; // Variant index 0
+        let name_bytes = "Alice".as_bytes();
+        create_data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+        create_data.extend_from_slice(name_bytes);
+        create_data.push(6); // max_players
+        create_data.push(0); // slot hash randomness
+        let mut rules_data = vec![0u8; GameRules::LEN];
+        GameRules::default().pack_into_slice(&mut rules_data);
+        create_data.extend_from_slice(&rules_data);
+        let result = borsh::from_slice::<MonopolyInstruction>(&create_data);
         assert!(result.is_ok());
-        if let MonopolyInstruction::InitGame { player_names: unpacked_names } = result.unwrap() {
-            assert_eq!(unpacked_names, player_names);
+        if let MonopolyInstruction::CreateGame { player_name, max_players, randomness, rules } = result.unwrap() {
+            assert_eq!(player_name, "Alice");
+            assert_eq!(max_players, 6);
+            assert_eq!(randomness, RandomnessKind::SlotHash);
+            assert_eq!(rules, GameRules::default());
         } else {
-            panic!("Expected InitGame instruction");
+            panic!("Expected CreateGame instruction");
         }
 
         // Test MovePlayer instruction
         let move_data = vec![1u8];
-        let mut rest = &move_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&move_data);
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), MonopolyInstruction::MovePlayer));
 
         // Test BuyProperty instruction
         let mut buy_data = vec![2u8];
         buy_data.push(5); // property_index
-        let mut rest = &buy_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&buy_data);
         assert!(result.is_ok());
         if let MonopolyInstruction::BuyProperty { property_index } = result.unwrap() {
             assert_eq!(property_index, 5);
@@ -204,8 +617,7 @@ mod tests {
         // Test BuildHouse instruction
         let mut build_data = vec![3u8];
         build_data.push(3); // property_index
-        let mut rest = &build_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&build_data);
         assert!(result.is_ok());
         if let MonopolyInstruction::BuildHouse { property_index } = result.unwrap() {
             assert_eq!(property_index, 3);
@@ -216,8 +628,7 @@ mod tests {
         // Test PayRent instruction
         let mut rent_data = vec![4u8];
         rent_data.push(7); // property_index
-        let mut rest = &rent_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&rent_data);
         assert!(result.is_ok());
         if let MonopolyInstruction::PayRent { property_index } = result.unwrap() {
             assert_eq!(property_index, 7);
@@ -227,23 +638,20 @@ mod tests {
 
         // Test NextTurn instruction
         let next_turn_data = vec![5u8];
-        let mut rest = &next_turn_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&next_turn_data);
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), MonopolyInstruction::NextTurn));
 
         // Test CheckWinner instruction
         let check_winner_data = vec![6u8];
-        let mut rest = &check_winner_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&check_winner_data);
         assert!(result.is_ok());
         assert!(matches!(result.unwrap(), MonopolyInstruction::CheckWinner));
 
         // Test DrawCard instruction
         let mut draw_data = vec![7u8];
         draw_data.push(0); // CommunityChest
-        let mut rest = &draw_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&draw_data);
         assert!(result.is_ok());
         if let MonopolyInstruction::DrawCard { deck_type } = result.unwrap() {
             assert!(matches!(deck_type, DeckType::CommunityChest));
@@ -253,125 +661,165 @@ mod tests {
 
         // Test invalid variant
         let invalid_data = vec![255u8];
-        let mut rest = &invalid_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&invalid_data);
         assert!(result.is_err());
 
         // Test empty data
         let empty_data: Vec<u8> = vec![];
-        let mut rest = &empty_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let result = borsh::from_slice::<MonopolyInstruction>(&empty_data);
         assert!(result.is_err());
 
         // Test truncated data
-        let mut truncated_data = vec![2u8]; // BuyProperty without property_index
-        let mut rest = &truncated_data[..];
-        let result = MonopolyInstruction::unpack_from_slice(&mut rest);
+        let truncated_data = vec![2u8]; // BuyProperty without property_index
+        let result = borsh::from_slice::<MonopolyInstruction>(&truncated_data);
         assert!(result.is_err());
     }
-}
 
-impl Pack for MonopolyInstruction {
-    const LEN: usize = 1024; // Large enough for instruction data
-
-    fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        match self {
-            MonopolyInstruction::InitGame { player_names } => {
-                dst[current] = 0; // Variant index
-                current += 1;
-                
-                dst[current..current + 4].copy_from_slice(&(player_names.len() as u32).to_le_bytes());
-                current += 4;
-                
-                for name in player_names {
-                    let name_bytes = name.as_bytes();
-                    dst[current..current + 4].copy_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-                    current += 4;
-                    dst[current..current + name_bytes.len()].copy_from_slice(name_bytes);
-                    current += name_bytes.len();
-                }
-            }
-            MonopolyInstruction::MovePlayer => {
-                dst[current] = 1;
-            }
-            MonopolyInstruction::BuyProperty { property_index } => {
-                dst[current] = 2;
-                current += 1;
-                dst[current] = *property_index;
-            }
-            MonopolyInstruction::BuildHouse { property_index } => {
-                dst[current] = 3;
-                current += 1;
-                dst[current] = *property_index;
-            }
-            MonopolyInstruction::PayRent { property_index } => {
-                dst[current] = 4;
-                current += 1;
-                dst[current] = *property_index;
-            }
-            MonopolyInstruction::NextTurn => {
-                dst[current] = 5;
-            }
-            MonopolyInstruction::CheckWinner => {
-                dst[current] = 6;
-            }
-            MonopolyInstruction::DrawCard { deck_type } => {
-                dst[current] = 7;
-                current += 1;
-                dst[current] = match deck_type {
-                    DeckType::CommunityChest => 0,
-                    DeckType::Chance => 1,
-                };
+    #[test]
+    fn test_to_instruction() {
+        let program_id = Pubkey::new_unique();
+        let game = Pubkey::new_unique();
+        let counterparty = Pubkey::new_unique();
+        let propose = MonopolyInstruction::ProposeTrade {
+            counterparty,
+            offered: TradeTerms { properties: vec![1, 3], cash: 100, jail_cards: 0 },
+            requested: TradeTerms::default(),
+        };
+
+        // Test each account is marked as the instruction's account list declares it
+        let offer = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let instruction = propose.to_instruction(&program_id, &[&game, &offer, &proposer]);
+        assert_eq!(instruction.program_id, program_id);
+        assert_eq!(instruction.accounts, vec![
+            AccountMeta::new_readonly(game, false),
+            AccountMeta::new(offer, false),
+            AccountMeta::new_readonly(proposer, true),
+        ]);
+        assert_eq!(propose.accounts()[1], AccountSpec {
+            name: "trade_offer",
+            writable: true,
+            signer: false,
+            docs: "Trade offer account",
+        });
+
+        // Test sysvars are read-only and wallets past the listed accounts sign
+        let wallet = Pubkey::new_unique();
+        let migrate = MonopolyInstruction::MigrateAccount { kind: AccountKind::Game };
+        let program_data = Pubkey::new_unique();
+        let keys = [&game, &proposer, &program_data, &solana_program::sysvar::rent::ID, &solana_program::system_program::ID, &wallet];
+        let accounts = migrate.to_instruction(&program_id, &keys).accounts;
+        assert_eq!(accounts[1], AccountMeta::new(proposer, true));
+        assert_eq!(accounts[3], AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false));
+        assert_eq!(accounts[5], AccountMeta::new_readonly(wallet, true));
+
+        // Test the data is the plain Borsh encoding: tag, key, then each side with a vector of properties
+        let mut expected = vec![17u8];
+        expected.extend_from_slice(counterparty.as_ref());
+        expected.extend_from_slice(&2u32.to_le_bytes());
+        expected.extend_from_slice(&[1, 3]);
+        expected.extend_from_slice(&100u64.to_le_bytes());
+        expected.push(0);
+        expected.extend_from_slice(&0u32.to_le_bytes());
+        expected.extend_from_slice(&0u64.to_le_bytes());
+        expected.push(0);
+        assert_eq!(instruction.data, expected);
+
+        let unpacked = borsh::from_slice::<MonopolyInstruction>(&instruction.data).unwrap();
+        assert_eq!(format!("{:?}", unpacked), format!("{:?}", propose));
+
+        // Test randomness is not padded inside an instruction
+        let data = MonopolyInstruction::MovePlayer.to_instruction(&program_id, &[]).data;
+        assert_eq!(data, [1]);
+        let data = MonopolyInstruction::CreateGame {
+            player_name: "Al".to_string(),
+            max_players: 2,
+            randomness: RandomnessKind::Seeded { seed: 9 },
+            rules: GameRules::default(),
+        }.to_instruction(&program_id, &[]).data;
+        assert_eq!(data.len(), 1 + 4 + 2 + 1 + 1 + 8 + GameRules::LEN);
+    }
+
+    #[test]
+    fn test_instruction_unpack_truncated() {
+        let instructions = vec![
+            MonopolyInstruction::CreateGame {
+                player_name: "Alice".to_string(),
+                max_players: 4,
+                randomness: RandomnessKind::Oracle { oracle: Pubkey::new_unique() },
+                rules: GameRules::default(),
+            },
+            MonopolyInstruction::SetDelegate { delegate: Some(Pubkey::new_unique()) },
+            MonopolyInstruction::JoinGame { player_name: "Bob".to_string() },
+            MonopolyInstruction::PlaceBid { amount: 250 },
+            MonopolyInstruction::ProposeTrade {
+                counterparty: Pubkey::new_unique(),
+                offered: TradeTerms { properties: vec![1, 3], cash: 0, jail_cards: 0 },
+                requested: TradeTerms { properties: vec![], cash: 200, jail_cards: 1 },
+            },
+            MonopolyInstruction::CommitDice { commitment: [3; 32] },
+            MonopolyInstruction::MigrateAccount { kind: AccountKind::CardDeck },
+        ];
+
+        for instruction in instructions {
+            let mut packed = borsh::to_vec(&instruction).unwrap();
+
+            // Test only the exact encoding decodes: nothing shorter, and nothing with bytes left over
+            let repacked = borsh::from_slice::<MonopolyInstruction>(&packed).unwrap();
+            assert_eq!(format!("{:?}", repacked), format!("{:?}", instruction));
+            for cut in 0..packed.len() {
+                assert!(borsh::from_slice::<MonopolyInstruction>(&packed[..cut]).is_err());
             }
+            packed.push(0);
+            assert!(borsh::from_slice::<MonopolyInstruction>(&packed).is_err());
         }
+
+        // Test a name length running past the end of the data
+        let mut data = vec![9u8];
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        data.extend_from_slice(b"Bob");
+        assert!(borsh::from_slice::<MonopolyInstruction>(&data).is_err());
+
+        // Test a bad nested value is rejected
+        let mut data = borsh::to_vec(&MonopolyInstruction::CreateGame {
+            player_name: String::new(),
+            max_players: 2,
+            randomness: RandomnessKind::SlotHash,
+            rules: GameRules::default(),
+        }).unwrap();
+        data[6] = 3; // randomness kind tag
+        assert!(borsh::from_slice::<MonopolyInstruction>(&data).is_err());
     }
 
-    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        if src.is_empty() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
 
-        let variant = src[0];
-        let mut current = 1;
-
-        Ok(match variant {
-            0 => {
-                let names_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-                current += 4;
-                let mut player_names = Vec::with_capacity(names_len);
-                
-                for _ in 0..names_len {
-                    let name_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-                    current += 4;
-                    let name = String::from_utf8(src[current..current + name_len].to_vec())
-                        .map_err(|_| ProgramError::InvalidInstructionData)?;
-                    current += name_len;
-                    player_names.push(name);
+        proptest! {
+            #[test]
+            fn fuzz_unpack_arbitrary_bytes(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+                // Whatever decodes must be the exact encoding of what it decoded to
+                if let Ok(instruction) = borsh::from_slice::<MonopolyInstruction>(&data) {
+                    prop_assert_eq!(borsh::to_vec(&instruction).unwrap(), data);
                 }
-                
-                MonopolyInstruction::InitGame { player_names }
             }
-            1 => MonopolyInstruction::MovePlayer,
-            2 => MonopolyInstruction::BuyProperty {
-                property_index: src[current],
-            },
-            3 => MonopolyInstruction::BuildHouse {
-                property_index: src[current],
-            },
-            4 => MonopolyInstruction::PayRent {
-                property_index: src[current],
-            },
-            5 => MonopolyInstruction::NextTurn,
-            6 => MonopolyInstruction::CheckWinner,
-            7 => MonopolyInstruction::DrawCard {
-                deck_type: match src[current] {
-                    0 => DeckType::CommunityChest,
-                    1 => DeckType::Chance,
-                    _ => return Err(ProgramError::InvalidInstructionData),
-                },
-            },
-            _ => return Err(ProgramError::InvalidInstructionData),
-        })
+
+            #[test]
+            fn fuzz_unpack_known_variant(variant in 0u8..32, body in proptest::collection::vec(any::<u8>(), 0..128)) {
+                let mut data = vec![variant];
+                data.extend_from_slice(&body);
+                if let Ok(instruction) = borsh::from_slice::<MonopolyInstruction>(&data) {
+                    prop_assert_eq!(borsh::to_vec(&instruction).unwrap(), data);
+                }
+            }
+
+            #[test]
+            fn fuzz_join_game_round_trip(player_name in "\\PC{0,32}") {
+                match round_trip(&MonopolyInstruction::JoinGame { player_name: player_name.clone() }) {
+                    MonopolyInstruction::JoinGame { player_name: unpacked } => prop_assert_eq!(unpacked, player_name),
+                    other => prop_assert!(false, "Expected JoinGame, got {:?}", other),
+                }
+            }
+        }
     }
 }
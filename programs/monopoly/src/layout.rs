@@ -0,0 +1,405 @@
+//! Borsh description of the bytes of every account.
+//!
+//! Accounts are packed by hand and the game account is borrowed zero-copy, so their data keeps
+//! fixed-width slots: an optional value is a flag byte followed by a slot that is zeroed when
+//! unset, and a list sits in a fixed-capacity slot after its length. The types here spell those
+//! slots out field by field so that account data is also plain Borsh. Decoding packed data into
+//! the matching layout and encoding it again gives back the same bytes. The IDL's accounts are
+//! generated from these types.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use crate::account::DeckType;
+use crate::state::{
+    Color, DiceRound, GameRules, GameStatus, TileKind, TurnPhase, BOARD_SIZE, MAX_NAME_LEN, MAX_PLAYERS,
+    MAX_PLAYER_PROPERTIES, MAX_TRADE_PROPERTIES,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program_pack::Pack;
+    use crate::account::{
+        AccountKind, CardDeck, GameAccount, PlayerAccount, PropertyAccount, TradeOffer, RandomnessRequest,
+        ACCOUNT_VERSION,
+    };
+    use crate::board::create_board;
+    use crate::state::{
+        Auction, Card, Debt, DicePhase, Game, PendingAction, Player, RandomnessKind, TileType, TradeTerms,
+    };
+
+    fn pack<T: Pack>(value: &T) -> Vec<u8> {
+        let mut packed = vec![0u8; T::LEN];
+        value.pack_into_slice(&mut packed);
+        packed
+    }
+
+    // Decode the whole of `packed` as `L` and check it encodes back to the same bytes
+    fn decode<L: BorshSerialize + BorshDeserialize>(packed: &[u8]) -> L {
+        let layout = borsh::from_slice::<L>(packed).unwrap();
+        assert_eq!(borsh::to_vec(&layout).unwrap(), packed);
+        layout
+    }
+
+    // Same as `decode`, for layouts that leave the rest of the account unused
+    fn decode_prefix<L: BorshSerialize + BorshDeserialize>(packed: &[u8]) -> L {
+        let layout = L::deserialize(&mut &packed[..]).unwrap();
+        let encoded = borsh::to_vec(&layout).unwrap();
+        assert_eq!(encoded, packed[..encoded.len()]);
+        assert!(packed[encoded.len()..].iter().all(|&byte| byte == 0));
+        layout
+    }
+
+    fn sample_player(wallet: Pubkey) -> Player {
+        Player {
+            name: "Alice".to_string(),
+            wallet,
+            delegate: Some(Pubkey::new_unique()),
+            cash: 1_234,
+            position: 7,
+            jail_turns: 0,
+            properties: vec![1, 3],
+            get_out_of_jail_cards: 1,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: Some([5; 32]),
+            dice_secret: None,
+        }
+    }
+
+    #[test]
+    fn test_layout_sizes() {
+        // Every variant of a padded slot encodes to the same width, so the layouts are fixed size
+        let size = |max: Result<usize, _>| max.unwrap();
+        assert_eq!(size(borsh::max_serialized_size::<GameAccountLayout>()), GameAccount::LEN);
+        assert_eq!(size(borsh::max_serialized_size::<TradeOfferLayout>()), TradeOffer::LEN);
+        assert_eq!(size(borsh::max_serialized_size::<RandomnessRequestLayout>()), RandomnessRequest::LEN);
+        assert_eq!(size(borsh::max_serialized_size::<PlayerAccountLayout>()), PlayerAccount::LEN);
+        assert_eq!(size(borsh::max_serialized_size::<PlayerLayout>()), Player::LEN);
+        assert_eq!(size(borsh::max_serialized_size::<RandomnessLayout>()), RandomnessKind::LEN);
+        assert_eq!(size(borsh::max_serialized_size::<PendingActionLayout>()), PendingAction::LEN);
+    }
+
+    #[test]
+    fn test_game_account_layout() {
+        let wallet = Pubkey::new_unique();
+        let request = Pubkey::new_unique();
+        let game = Game {
+            board: create_board(),
+            players: vec![sample_player(wallet)],
+            current_player: 0,
+            free_parking: 75,
+            initialized: true,
+            creator: wallet,
+            status: GameStatus::Started,
+            max_players: 4,
+            auction: Some(Auction { property_index: 3, highest_bid: 50, highest_bidder: Some(0), deadline_slot: 99 }),
+            debt: Some(Debt { debtor: 0, creditor: None, amount: 20 }),
+            last_roll: 7,
+            houses_remaining: 32,
+            hotels_remaining: 12,
+            doubles_rolled: 0,
+            dice_round: Some(DiceRound { phase: DicePhase::Reveal, deadline_slot: 12 }),
+            rolled_dice: Some((3, 4)),
+            randomness: RandomnessKind::Seeded { seed: 9 },
+            randomness_nonce: 2,
+            last_dice: (3, 4),
+            last_doubles: false,
+            turn_phase: TurnPhase::ResolvingTile,
+            pending_action: Some(PendingAction::BuyProperty { property_index: 3 }),
+            rules: GameRules::default(),
+            auction_queue: vec![5, 39],
+            randomness_request: Some(request),
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+        let packed = pack(&GameAccount { is_initialized: true, game });
+
+        let layout: GameAccountLayout = decode(&packed);
+        assert_eq!(layout.discriminator, AccountKind::Game.discriminator());
+        assert_eq!(layout.version, ACCOUNT_VERSION);
+        assert!(layout.is_initialized);
+        assert_eq!(layout.players_len, 1);
+        assert_eq!(layout.players[0].wallet, wallet);
+        assert_eq!(&layout.players[0].name[..5], b"Alice");
+        assert_eq!(layout.players[0].cash, 1_234);
+        assert!(layout.players[0].has_dice_commitment);
+        assert_eq!(layout.free_parking, 75);
+        assert_eq!(layout.status, GameStatus::Started);
+        assert!(layout.has_auction);
+        assert_eq!(layout.auction.highest_bidder, 0);
+        assert!(layout.auction.has_highest_bidder);
+        assert!(layout.has_debt);
+        assert!(!layout.debt.has_creditor);
+        assert_eq!(layout.dice_round, DiceRound { phase: DicePhase::Reveal, deadline_slot: 12 });
+        assert!(matches!(layout.randomness, RandomnessLayout::Seeded { seed: 9, .. }));
+        assert!(matches!(layout.pending_action, PendingActionLayout::BuyProperty { property_index: 3, .. }));
+        assert_eq!(layout.rolled_dice, [3, 4]);
+        assert_eq!(layout.turn_phase, TurnPhase::ResolvingTile);
+        assert_eq!(layout.rules, GameRules::default());
+        assert_eq!(layout.auction_queue_len, 2);
+        assert_eq!(&layout.auction_queue[..2], &[5, 39]);
+        assert!(layout.has_randomness_request);
+        assert_eq!(layout.randomness_request, request);
+    }
+
+    #[test]
+    fn test_other_account_layouts() {
+        let game = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+
+        let packed = pack(&PlayerAccount { is_initialized: true, player: sample_player(wallet), game });
+        let layout: PlayerAccountLayout = decode(&packed);
+        assert_eq!(layout.discriminator, AccountKind::Player.discriminator());
+        assert_eq!(layout.player.wallet, wallet);
+        assert_eq!(layout.game, game);
+
+        let property = create_board().into_iter().find_map(|tile| match tile {
+            TileType::Property(property) => Some(property),
+            _ => None,
+        }).unwrap();
+        let packed = pack(&PropertyAccount { is_initialized: true, property: property.clone(), game });
+        let layout: PropertyAccountLayout = decode_prefix(&packed);
+        assert_eq!(layout.discriminator, AccountKind::Property.discriminator());
+        assert_eq!(layout.property.name, property.name);
+        assert_eq!(layout.property.rent, property.rent);
+        assert_eq!(layout.property.owner, u8::MAX);
+        assert_eq!(layout.game, game);
+
+        let cards = vec![Card::CollectMoney(200), Card::GetOutOfJail, Card::PayPerHouseAndHotel { house: 40, hotel: 115 }];
+        let packed = pack(&CardDeck {
+            is_initialized: true,
+            cards: cards.clone(),
+            game,
+            deck_type: DeckType::Chance,
+            current_card_index: 1,
+            jail_card_held: true,
+        });
+        let layout: CardDeckLayout = decode_prefix(&packed);
+        assert_eq!(layout.cards_len, 3);
+        let mut card_data = &layout.cards[..];
+        for card in cards {
+            assert_eq!(Card::deserialize(&mut card_data).unwrap(), card);
+        }
+        assert!(card_data.is_empty());
+        assert_eq!(layout.deck_type, DeckType::Chance);
+        assert!(layout.jail_card_held);
+
+        let packed = pack(&TradeOffer {
+            is_initialized: true,
+            game,
+            proposer: wallet,
+            counterparty: Pubkey::new_unique(),
+            offered: TradeTerms { properties: vec![1, 3], cash: 10, jail_cards: 0 },
+            requested: TradeTerms { properties: vec![], cash: 0, jail_cards: 1 },
+        });
+        let layout: TradeOfferLayout = decode(&packed);
+        assert_eq!(layout.proposer, wallet);
+        assert_eq!(layout.offered.properties_len, 2);
+        assert_eq!(&layout.offered.properties[..2], [1, 3]);
+        assert_eq!(layout.requested.jail_cards, 1);
+
+        let packed = pack(&RandomnessRequest { is_initialized: true, game, nonce: 4, fulfilled: true, output: [8; 32] });
+        let layout: RandomnessRequestLayout = decode(&packed);
+        assert_eq!(layout.nonce, 4);
+        assert_eq!(layout.output, [8; 32]);
+    }
+}
+
+/// One seat, laid out like `Player`'s packing
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct PlayerLayout {
+    pub name_len: u8,
+    /// UTF-8 name in the first `name_len` bytes, zero padded
+    pub name: [u8; MAX_NAME_LEN],
+    pub wallet: Pubkey,
+    pub has_delegate: bool,
+    pub delegate: Pubkey,
+    pub cash: u64,
+    pub position: u8,
+    pub jail_turns: u8,
+    pub properties_len: u8,
+    pub properties: [u8; MAX_PLAYER_PROPERTIES],
+    pub get_out_of_jail_cards: u8,
+    pub community_chest_jail_card: bool,
+    pub chance_jail_card: bool,
+    pub eliminated: bool,
+    pub has_dice_commitment: bool,
+    pub dice_commitment: [u8; 32],
+    pub has_dice_secret: bool,
+    pub dice_secret: [u8; 32],
+}
+
+/// Mutable state of one tile, laid out like `TileState`'s packing
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct TileLayout {
+    /// Seat index of the owner, or `u8::MAX` when unowned
+    pub owner: u8,
+    pub houses: u8,
+    pub mortgaged: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct AuctionLayout {
+    pub property_index: u8,
+    pub highest_bid: u64,
+    pub has_highest_bidder: bool,
+    pub highest_bidder: u8,
+    pub deadline_slot: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct DebtLayout {
+    pub debtor: u8,
+    /// Unset when the debt is owed to the bank
+    pub has_creditor: bool,
+    pub creditor: u8,
+    pub amount: u64,
+}
+
+/// `RandomnessKind` padded to its fixed slot
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum RandomnessLayout {
+    SlotHash { padding: [u8; 32] },
+    Oracle { oracle: Pubkey },
+    Seeded { seed: u64, padding: [u8; 24] },
+}
+
+/// `PendingAction` padded to its fixed slot
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum PendingActionLayout {
+    PayRent { property_index: u8, owner: u8, card_rent: bool },
+    DrawCard { deck_type: DeckType, padding: [u8; 2] },
+    BuyProperty { property_index: u8, padding: [u8; 2] },
+}
+
+/// Game account, laid out like `GameAccountData`. A slot whose `has_` flag is unset is zeroed.
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct GameAccountLayout {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: bool,
+    pub players_len: u8,
+    pub players: [PlayerLayout; MAX_PLAYERS as usize],
+    pub board_id: u8,
+    pub tiles: [TileLayout; BOARD_SIZE],
+    pub current_player: u8,
+    pub free_parking: u64,
+    pub initialized: bool,
+    pub creator: Pubkey,
+    pub status: GameStatus,
+    pub max_players: u8,
+    pub has_auction: bool,
+    pub auction: AuctionLayout,
+    pub has_debt: bool,
+    pub debt: DebtLayout,
+    pub last_roll: u8,
+    pub houses_remaining: u8,
+    pub hotels_remaining: u8,
+    pub doubles_rolled: u8,
+    pub has_dice_round: bool,
+    pub dice_round: DiceRound,
+    pub has_rolled_dice: bool,
+    pub rolled_dice: [u8; 2],
+    pub randomness: RandomnessLayout,
+    pub randomness_nonce: u64,
+    pub last_dice: [u8; 2],
+    pub last_doubles: bool,
+    pub turn_phase: TurnPhase,
+    pub has_pending_action: bool,
+    pub pending_action: PendingActionLayout,
+    pub rules: GameRules,
+    pub auction_queue_len: u8,
+    pub auction_queue: [u8; MAX_PLAYER_PROPERTIES],
+    pub has_randomness_request: bool,
+    pub randomness_request: Pubkey,
+    pub has_community_chest_deck: bool,
+    pub community_chest_deck: Pubkey,
+    pub has_chance_deck: bool,
+    pub chance_deck: Pubkey,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct PlayerAccountLayout {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: bool,
+    pub player: PlayerLayout,
+    pub game: Pubkey,
+}
+
+/// A property, laid out like `Property`'s packing
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct PropertyLayout {
+    pub name: String,
+    pub color: Color,
+    pub kind: TileKind,
+    pub cost: u64,
+    pub rent: Vec<u64>,
+    pub house_cost: u64,
+    pub hotel_cost: u64,
+    pub house_rent: Vec<u64>,
+    /// Seat index of the owner, or `u8::MAX` when unowned
+    pub owner: u8,
+    pub houses: u8,
+    pub mortgaged: bool,
+}
+
+/// Property account; the rest of the account after `property` is unused
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct PropertyAccountLayout {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: bool,
+    pub game: Pubkey,
+    pub property: PropertyLayout,
+}
+
+/// Card deck account; the rest of the account after `jail_card_held` is unused
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct CardDeckLayout {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: bool,
+    /// Number of cards in `cards`
+    pub cards_len: u32,
+    /// Borsh-encoded `Card`s back to back, in draw order
+    pub cards: Vec<u8>,
+    pub game: Pubkey,
+    pub deck_type: DeckType,
+    pub current_card_index: u8,
+    pub jail_card_held: bool,
+}
+
+/// One side of a trade, laid out like `TradeTerms`'s packing
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct TradeTermsLayout {
+    pub properties_len: u32,
+    pub properties: [u8; MAX_TRADE_PROPERTIES],
+    pub cash: u64,
+    pub jail_cards: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct TradeOfferLayout {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: bool,
+    pub game: Pubkey,
+    pub proposer: Pubkey,
+    pub counterparty: Pubkey,
+    pub offered: TradeTermsLayout,
+    pub requested: TradeTermsLayout,
+}
+
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct RandomnessRequestLayout {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+    pub is_initialized: bool,
+    pub game: Pubkey,
+    pub nonce: u64,
+    pub fulfilled: bool,
+    pub output: [u8; 32],
+}
@@ -1,39 +1,59 @@
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     entrypoint::ProgramResult,
     msg,
+    program::invoke,
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
+    system_instruction,
     sysvar::clock::Clock,
+    sysvar::rent::Rent,
     sysvar::Sysvar,
 };
 
 use crate::{
     error::MonopolyError,
     instruction::MonopolyInstruction,
-    state::{Game, Player, TileType, SpecialTile, Card},
-    account::{GameAccount, DeckType, validate_game_account, validate_card_deck},
-    utils::{roll_dice, calculate_rent, can_build_house},
+    state::{
+        Game, GameStatus, Player, TileType, TileKind, SpecialTile, Card, Auction, TradeTerms, Debt, DiceRound, DicePhase, RandomnessKind,
+        PendingAction, TurnPhase, GameRules, Board, GameState, PlayerState, TileView,
+        MIN_PLAYERS, MAX_PLAYERS, MAX_NAME_LEN, MAX_RULE_AMOUNT, AUCTION_DURATION_SLOTS, MIN_BID_INCREMENT, BANK_HOUSES, BANK_HOTELS,
+        JAIL_POSITION, MAX_JAIL_ROLLS, MAX_DOUBLES, MAX_TRADE_PROPERTIES,
+        DICE_COMMIT_SLOTS, DICE_REVEAL_SLOTS, DICE_TIMEOUT_PENALTY, INCOME_TAX, LUXURY_TAX,
+    },
+    account::{
+        GameAccount, CardDeck, DeckType, TradeOffer, RandomnessRequest, AccountKind, ACCOUNT_VERSION,
+        GameAccountData, is_blank_account, load_game_account_mut, load_card_deck, validate_game_account, validate_card_deck,
+        validate_trade_offer, validate_randomness_request,
+    },
+    utils::{
+        roll_dice, dice_commitment, calculate_tile_rent, calculate_card_rent, nearest_of_kind, count_buildings,
+        building_sell_price, can_build_house, can_sell_house, hotel_upgrade_cost,
+        mortgage_value, unmortgage_cost, slot_hash_at, RandomnessSource, SlotHashEntropy, OracleOutput, SeededRandomness,
+    },
     board::{create_board, create_community_chest, create_chance_cards},
+    account_v1,
 };
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::JAIL_FINE;
 
     #[test]
-    fn test_process_init_game() {
+    fn test_process_create_game() {
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
+        let mut creator_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -46,146 +66,225 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
+            true,  // Creator must be signer
             true,
-            &mut player_lamports,
-            &mut player_data,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        // Test case 1: Successful game initialization
+        // Test case 1: Missing creator signature
         {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let player_names = vec![String::from("Player 1"), String::from("Player 2")];
-            process_init_game(&program_id, accounts, player_names.clone()).unwrap();
+            let mut unsigned_creator_account = creator_account.clone();
+            unsigned_creator_account.is_signer = false;
+
+            let accounts = &[game_account.clone(), unsigned_creator_account];
+            let result = process_create_game(&program_id, accounts, String::from("Player 1"), 4, RandomnessKind::Seeded { seed: 0 }, GameRules::default());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test case 2: Invalid lobby size
+        {
+            let accounts = &[game_account.clone(), creator_account.clone()];
+            let result = process_create_game(&program_id, accounts, String::from("Player 1"), 1, RandomnessKind::Seeded { seed: 0 }, GameRules::default());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidPlayerCount.into());
+
+            let result = process_create_game(&program_id, accounts, String::from("Player 1"), 9, RandomnessKind::Seeded { seed: 0 }, GameRules::default());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidPlayerCount.into());
+        }
+
+        // Test case 3: Name longer than a seat can hold
+        {
+            let accounts = &[game_account.clone(), creator_account.clone()];
+            let result = process_create_game(&program_id, accounts, "A".repeat(MAX_NAME_LEN + 1), 4, RandomnessKind::Seeded { seed: 0 }, GameRules::default());
+            assert_eq!(result.unwrap_err(), MonopolyError::NameTooLong.into());
+        }
+
+        // Test case 4: Amounts past the supported range are refused
+        {
+            let accounts = &[game_account.clone(), creator_account.clone()];
+            for rules in [
+                GameRules { starting_cash: MAX_RULE_AMOUNT + 1, ..GameRules::default() },
+                GameRules { go_salary: u64::MAX, ..GameRules::default() },
+                GameRules { jail_fine: MAX_RULE_AMOUNT + 1, ..GameRules::default() },
+            ] {
+                let result = process_create_game(&program_id, accounts, String::from("Player 1"), 4, RandomnessKind::Seeded { seed: 0 }, rules);
+                assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameRules.into());
+            }
+        }
+
+        // Test case 5: Successful game creation
+        {
+            let accounts = &[game_account.clone(), creator_account.clone()];
+            process_create_game(&program_id, accounts, String::from("Player 1"), 4, RandomnessKind::Seeded { seed: 7 }, GameRules::default()).unwrap();
 
             let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
             assert!(game_state.is_initialized);
             assert!(game_state.game.initialized);
-            assert_eq!(game_state.game.players.len(), 2);
+            assert_eq!(game_state.game.status, GameStatus::Open);
+            assert_eq!(game_state.game.creator, creator_key);
+            assert_eq!(game_state.game.max_players, 4);
+            assert_eq!(game_state.game.rules, GameRules::default());
+            assert_eq!(game_state.game.randomness, RandomnessKind::Seeded { seed: 7 });
+            assert_eq!(game_state.game.players.len(), 1);
             assert_eq!(game_state.game.players[0].name, "Player 1");
-            assert_eq!(game_state.game.players[1].name, "Player 2");
+            assert_eq!(game_state.game.players[0].wallet, creator_key);
             assert_eq!(game_state.game.players[0].cash, 1500); // Starting cash
-            assert_eq!(game_state.game.current_player, 0);
-            assert_eq!(game_state.game.free_parking, 0);
             assert_eq!(game_state.game.board.len(), 40); // Standard Monopoly board size
         }
 
-        // Test case 2: Missing player signature
+        // Test case 6: Reinitializing an already initialized game
         {
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
-
-            let accounts = &[game_account.clone(), unsigned_player_account];
-            let player_names = vec![String::from("Player 1")];
-            let result = process_init_game(&program_id, accounts, player_names.clone());
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+            let accounts = &[game_account.clone(), creator_account.clone()];
+            let result = process_create_game(&program_id, accounts, String::from("Player 1"), 4, RandomnessKind::Seeded { seed: 0 }, GameRules::default());
+            assert_eq!(result.unwrap_err(), ProgramError::AccountAlreadyInitialized);
         }
+    }
+
+    #[test]
+    fn test_lobby_flow() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let late_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut late_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut late_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let creator_account = AccountInfo::new(
+            &creator_key,
+            true,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let late_account = AccountInfo::new(
+            &late_key,
+            true,
+            true,
+            &mut late_lamports,
+            &mut late_data,
+            &program_id,
+            false,
+            0,
+        );
 
-        // Test case 3: Invalid number of players (less than 2)
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        let guest_accounts = &[game_account.clone(), guest_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+
+        // Test case 1: Cannot start with a single player
         {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let player_names = vec![String::from("Player 1")];
-            let result = process_init_game(&program_id, accounts, player_names.clone());
-            assert!(result.is_err());
+            let result = process_start_game(&program_id, creator_accounts);
             assert_eq!(result.unwrap_err(), MonopolyError::InvalidPlayerCount.into());
         }
 
-        // Test case 4: Invalid number of players (more than 8)
+        // Test case 2: Join, rejoin and leave
         {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let player_names = vec![
-                String::from("Player 1"),
-                String::from("Player 2"),
-                String::from("Player 3"),
-                String::from("Player 4"),
-                String::from("Player 5"),
-                String::from("Player 6"),
-                String::from("Player 7"),
-                String::from("Player 8"),
-                String::from("Player 9"),
-            ];
-            let result = process_init_game(&program_id, accounts, player_names.clone());
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InvalidPlayerCount.into());
+            process_join_game(&program_id, guest_accounts, String::from("Player 2")).unwrap();
+            let result = process_join_game(&program_id, guest_accounts, String::from("Player 2"));
+            assert_eq!(result.unwrap_err(), MonopolyError::PlayerAlreadyJoined.into());
+
+            process_leave_game(&program_id, guest_accounts).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players.len(), 1);
+
+            let result = process_join_game(&program_id, guest_accounts, "A".repeat(MAX_NAME_LEN + 1));
+            assert_eq!(result.unwrap_err(), MonopolyError::NameTooLong.into());
+
+            let result = process_leave_game(&program_id, guest_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::PlayerNotInGame.into());
         }
 
-        // Test case 5: Reinitializing an already initialized game
+        // Test case 3: Lobby is full
         {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let player_names = vec![String::from("Player 1"), String::from("Player 2")];
-            process_init_game(&program_id, accounts, player_names.clone()).unwrap();
+            process_join_game(&program_id, guest_accounts, String::from("Player 2")).unwrap();
 
-            let result = process_init_game(&program_id, accounts, player_names.clone());
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::AccountAlreadyInitialized);
+            let accounts = &[game_account.clone(), late_account.clone()];
+            let result = process_join_game(&program_id, accounts, String::from("Player 3"));
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidPlayerCount.into());
         }
-    }
 
-    #[test]
-    fn test_is_player_bankrupt() {
-        // Test case 1: Player with cash and properties is not bankrupt
-        let player = Player {
-            name: String::from("Rich Player"),
-            cash: 500,
-            position: 0,
-            jail_turns: 0,
-            properties: vec![1, 2, 3],
-            get_out_of_jail_cards: 0,
-        };
-        assert!(!is_player_bankrupt(&player));
+        // Test case 4: Only the creator may start the game
+        {
+            let result = process_start_game(&program_id, guest_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotGameCreator.into());
 
-        // Test case 2: Player with cash but no properties is not bankrupt
-        let player = Player {
-            name: String::from("Cash Only Player"),
-            cash: 100,
-            position: 0,
-            jail_turns: 0,
-            properties: vec![],
-            get_out_of_jail_cards: 0,
-        };
-        assert!(!is_player_bankrupt(&player));
+            process_start_game(&program_id, creator_accounts).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.status, GameStatus::Started);
+            assert_eq!(game_state.game.players[1].wallet, guest_key);
+        }
 
-        // Test case 3: Player with no cash but has properties is not bankrupt
-        let player = Player {
-            name: String::from("Property Rich Player"),
-            cash: 0,
-            position: 0,
-            jail_turns: 0,
-            properties: vec![1],
-            get_out_of_jail_cards: 0,
-        };
-        assert!(!is_player_bankrupt(&player));
+        // Test case 5: Lobby is closed once started
+        {
+            let result = process_leave_game(&program_id, guest_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
 
-        // Test case 4: Player with no cash and no properties is bankrupt
-        let player = Player {
-            name: String::from("Bankrupt Player"),
-            cash: 0,
-            position: 0,
-            jail_turns: 0,
-            properties: vec![],
-            get_out_of_jail_cards: 0,
-        };
-        assert!(is_player_bankrupt(&player));
+            let result = process_start_game(&program_id, creator_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+        }
     }
 
     #[test]
-    fn test_process_move_player() {
+    fn test_auction_flow() {
+        use solana_program::sysvar::clock;
+
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let clock_key = clock::ID;
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut clock_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut clock_data = bincode::serialize(&Clock::default()).unwrap();
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -198,161 +297,147 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        // Initialize game state
-        let mut game = Game {
-            board: create_board(),
-            players: vec![
-                Player {
-                    name: String::from("Player 1"),
-                    cash: 1500,
-                    position: 0,
-                    jail_turns: 0,
-                    properties: vec![],
-                    get_out_of_jail_cards: 0,
-                },
-                Player {
-                    name: String::from("Player 2"),
-                    cash: 1500,
-                    position: 0,
-                    jail_turns: 0,
-                    properties: vec![],
-                    get_out_of_jail_cards: 0,
-                },
-            ],
-            current_player: 0,
-            free_parking: 0,
-            initialized: true,
-        };
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
 
-        let game_state = GameAccount {
-            is_initialized: true,
-            game: game.clone(),
+        let clock_account = AccountInfo::new(
+            &clock_key,
+            false,
+            false,
+            &mut clock_lamports,
+            &mut clock_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let set_slot = |slot: u64| {
+            let clock = Clock { slot, ..Clock::default() };
+            clock_account.data.borrow_mut().copy_from_slice(&bincode::serialize(&clock).unwrap());
         };
-        game_state.pack_into_slice(&mut game_data);
 
-        // Test case 1: Normal movement
-        {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let dice_roll = 6;
-            process_move_player(&program_id, accounts, 6).unwrap();
+        let lobby_accounts = &[game_account.clone(), creator_account.clone()];
+        process_create_game(&program_id, lobby_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), guest_account.clone()], String::from("Player 2")).unwrap();
+        process_start_game(&program_id, lobby_accounts).unwrap();
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].position, 6);
-            assert_eq!(updated_game.game.players[0].cash, 1500); // No change in cash
+        // Put the current player on the first property
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.players[0].position = 1;
+            game_state.game.pending_action = Some(PendingAction::BuyProperty { property_index: 1 });
+            game_state.game.turn_phase = TurnPhase::ResolvingTile;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
         }
 
-        // Test case 2: Passing GO (collect $200)
-        {
-            game.players[0].position = 39; // Last space before GO
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+        let creator_accounts = &[game_account.clone(), creator_account.clone(), clock_account.clone()];
+        let guest_accounts = &[game_account.clone(), guest_account.clone(), clock_account.clone()];
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            process_move_player(&program_id, accounts, 3).unwrap();
+        // Test case 1: Only the tile the player is standing on can be auctioned
+        {
+            let result = process_start_auction(&program_id, creator_accounts, 3);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidPropertyIndex.into());
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].position, 2); // Wrapped around to position 2
-            assert_eq!(updated_game.game.players[0].cash, 1700); // Collected $200 for passing GO
+            let result = process_start_auction(&program_id, guest_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
         }
 
-        // Test case 3: Landing on Income Tax
+        // Test case 2: Starting the auction blocks buying and ending the turn
         {
-            game.players[0].position = 0;
-            game.players[0].cash = 1500;
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            set_slot(100);
+            process_start_auction(&program_id, creator_accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            let auction = game_state.game.auction.unwrap();
+            assert_eq!(auction.property_index, 1);
+            assert_eq!(auction.deadline_slot, 100 + AUCTION_DURATION_SLOTS);
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let dice_roll = 4; // Land on Income Tax
-            process_move_player(&program_id, accounts, dice_roll).unwrap();
+            let result = process_buy_property(&program_id, &creator_accounts[..2], 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::AuctionInProgress.into());
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].position, 4);
-            assert_eq!(updated_game.game.players[0].cash, 1300); // Paid $200 income tax
-            assert_eq!(updated_game.game.free_parking, 200); // Tax goes to Free Parking
+            let result = process_next_turn(&program_id, &creator_accounts[..2]);
+            assert_eq!(result.unwrap_err(), MonopolyError::AuctionInProgress.into());
         }
 
-        // Test case 4: Landing on Luxury Tax
+        // Test case 3: Bids must clear the minimum increment
         {
-            game.players[0].position = 36;
-            game.players[0].cash = 1500;
-            game.free_parking = 0;
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            process_place_bid(&program_id, creator_accounts, 20).unwrap();
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let dice_roll = 2; // Land on Luxury Tax
-            process_move_player(&program_id, accounts, dice_roll).unwrap();
+            let result = process_place_bid(&program_id, guest_accounts, 20 + MIN_BID_INCREMENT - 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::BidTooLow.into());
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].position, 38);
-            assert_eq!(updated_game.game.players[0].cash, 1425); // Paid $75 luxury tax
-            assert_eq!(updated_game.game.free_parking, 75); // Tax goes to Free Parking
+            let result = process_place_bid(&program_id, guest_accounts, 2000);
+            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientBalance.into());
+
+            process_place_bid(&program_id, guest_accounts, 20 + MIN_BID_INCREMENT).unwrap();
         }
 
-        // Test case 5: Missing player signature
+        // Test case 4: Cannot settle early or bid late
         {
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
+            let result = process_settle_auction(&program_id, creator_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::AuctionNotEnded.into());
 
-            let accounts = &[game_account.clone(), unsigned_player_account];
-            let dice_roll = 6;
-            let result = process_move_player(&program_id, accounts, dice_roll);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+            set_slot(101 + AUCTION_DURATION_SLOTS);
+            let result = process_place_bid(&program_id, creator_accounts, 100);
+            assert_eq!(result.unwrap_err(), MonopolyError::AuctionEnded.into());
         }
 
-        // Test case 6: Invalid dice roll (too high)
+        // Test case 5: Settlement transfers the property to the highest bidder
         {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let dice_roll = 13; // Max should be 12 (double sixes)
-            let result = process_move_player(&program_id, accounts, dice_roll);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InvalidDiceRoll.into());
-        }
+            process_settle_auction(&program_id, creator_accounts).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert!(game_state.game.auction.is_none());
+            assert_eq!(game_state.game.players[1].cash, 1500 - (20 + MIN_BID_INCREMENT));
+            assert_eq!(game_state.game.players[1].properties, vec![1]);
+            assert_eq!(game_state.game.players[0].cash, 1500);
+            match &game_state.game.board[1] {
+                TileType::Property(property) => assert_eq!(property.owner, Some(1)),
+                _ => panic!("Expected property"),
+            }
 
-        // Test case 7: Invalid dice roll (zero or negative)
-        {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let dice_roll = 0;
-            let result = process_move_player(&program_id, accounts, dice_roll);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InvalidDiceRoll.into());
+            let result = process_settle_auction(&program_id, creator_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NoActiveAuction.into());
         }
     }
 
     #[test]
-    fn test_process_buy_property() {
+    fn test_jail_flow() {
+        use crate::account::CardDeck;
+
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let deck_key = Pubkey::new_unique();
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut deck_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut deck_data = vec![0; CardDeck::LEN];
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -365,154 +450,204 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        // Initialize game state with a player on a property space
-        let mut game = Game {
-            board: create_board(),
-            players: vec![
-                Player {
-                    name: String::from("Player 1"),
-                    cash: 1500,
-                    position: 1, // Mediterranean Avenue
-                    jail_turns: 0,
-                    properties: vec![],
-                    get_out_of_jail_cards: 0,
-                },
-                Player {
-                    name: String::from("Player 2"),
-                    cash: 1500,
-                    position: 0,
-                    jail_turns: 0,
-                    properties: vec![],
-                    get_out_of_jail_cards: 0,
-                },
-            ],
-            current_player: 0,
-            free_parking: 0,
-            initialized: true,
-        };
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
 
-        let game_state = GameAccount {
-            is_initialized: true,
-            game: game.clone(),
+        let deck_account = AccountInfo::new(
+            &deck_key,
+            false,
+            true,
+            &mut deck_lamports,
+            &mut deck_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let lobby_accounts = &[game_account.clone(), creator_account.clone()];
+        let rules = GameRules { free_parking_jackpot: true, ..GameRules::default() };
+        process_create_game(&program_id, lobby_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, rules).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), guest_account.clone()], String::from("Player 2")).unwrap();
+        process_start_game(&program_id, lobby_accounts).unwrap();
+
+        let move_accounts = &[game_account.clone(), creator_account.clone()];
+        let jail_card_accounts = &[game_account.clone(), creator_account.clone(), deck_account.clone()];
+        let set_player = |position: u8, jail_turns: u8, jail_cards: u8| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            let player = &mut game_state.game.players[0];
+            player.position = position;
+            player.jail_turns = jail_turns;
+            player.get_out_of_jail_cards = jail_cards;
+            player.cash = 1500;
+            game_state.game.pending_action = None;
+            game_state.game.turn_phase = TurnPhase::AwaitingRoll;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+        let roll = |dice: (u8, u8)| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.rolled_dice = Some(dice);
+            // Tile actions are covered by test_pending_actions
+            if game_state.game.pending_action.take().is_some() {
+                update_turn_phase(&mut game_state.game);
+            }
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            process_move_player(&program_id, move_accounts)
         };
-        game_state.pack_into_slice(&mut game_data);
+        let player = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game.players[0].clone();
+        let free_parking = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game.free_parking;
 
-        // Test case 1: Successful property purchase
+        // Test case 1: Three doubles in a row go straight to jail
         {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            process_buy_property(&program_id, accounts, 1).unwrap(); // Mediterranean Avenue
+            roll((1, 1)).unwrap();
+            roll((2, 2)).unwrap();
+            assert_eq!(player().position, 6);
+            roll((3, 3)).unwrap();
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].cash, 1440); // 1500 - 60 (Mediterranean Ave cost)
-            assert_eq!(updated_game.game.players[0].properties.len(), 1);
-            assert_eq!(updated_game.game.players[0].properties[0], 1); // Mediterranean Ave index
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players[0].position, JAIL_POSITION);
+            assert!(game_state.game.players[0].in_jail());
+            assert_eq!(game_state.game.doubles_rolled, 0);
+            assert_eq!(game_state.game.turn_phase, TurnPhase::PostRoll);
+            assert_eq!(roll((1, 2)).unwrap_err(), MonopolyError::InvalidGameState.into());
         }
 
-        // Test case 2: Insufficient funds
+        // Test case 2: A failed roll keeps the player in jail
         {
-            game.players[0].cash = 50; // Less than property cost
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_buy_property(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientFunds.into());
+            set_player(JAIL_POSITION, 1, 0);
+            roll((1, 2)).unwrap();
+            assert_eq!(player().position, JAIL_POSITION);
+            assert_eq!(player().jail_turns, 2);
         }
 
-        // Test case 3: Property already owned
+        // Test case 3: Doubles leave jail and move by the roll
         {
-            game.players[0].cash = 1500;
-            game.players[0].properties = vec![1]; // Already owns Mediterranean Ave
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_buy_property(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::PropertyAlreadyOwned.into());
+            set_player(JAIL_POSITION, 2, 0);
+            roll((2, 2)).unwrap();
+            assert_eq!(player().position, 14);
+            assert!(!player().in_jail());
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.doubles_rolled, 0);
+
+            // Leaving jail on doubles does not earn another roll
+            game_state.game.pending_action = None;
+            update_turn_phase(&mut game_state.game);
+            assert_eq!(game_state.game.turn_phase, TurnPhase::PostRoll);
         }
 
-        // Test case 4: Not on a property space
+        // Test case 4: The last failed attempt forces the fine, into the Free Parking pot, and moves the player
         {
-            game.players[0].position = 0; // GO space
-            game.players[0].properties = vec![];
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_buy_property(&program_id, accounts, 0);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::NotAProperty.into());
+            set_player(JAIL_POSITION, MAX_JAIL_ROLLS, 0);
+            roll((1, 2)).unwrap();
+            assert_eq!(player().position, 13);
+            assert_eq!(player().cash, 1500 - JAIL_FINE);
+            assert!(!player().in_jail());
+            assert_eq!(free_parking(), JAIL_FINE);
         }
 
-        // Test case 5: Missing player signature
+        // Test case 5: Landing on the jail is just visiting
         {
-            game.players[0].position = 1; // Back to Mediterranean Ave
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
+            set_player(7, 0, 0);
+            roll((1, 2)).unwrap();
+            assert_eq!(player().position, JAIL_POSITION);
+            assert!(!player().in_jail());
+        }
 
-            let accounts = &[game_account.clone(), unsigned_player_account];
-            let result = process_buy_property(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        // Test case 6: Bail frees a jailed player
+        {
+            set_player(JAIL_POSITION, 0, 0);
+            let result = process_pay_bail(&program_id, lobby_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotInJail.into());
+
+            set_player(JAIL_POSITION, 1, 0);
+            process_pay_bail(&program_id, lobby_accounts).unwrap();
+            assert_eq!(player().cash, 1500 - JAIL_FINE);
+            assert!(!player().in_jail());
+            assert_eq!(free_parking(), 2 * JAIL_FINE);
         }
 
-        // Test case 6: Not current player's turn
+        // Test case 7: A jail card needs to be held and goes back to the deck holding it out
         {
-            game.current_player = 1; // Switch to player 2's turn
-            let game_state = GameAccount {
+            let mut card_deck = CardDeck {
                 is_initialized: true,
-                game: game.clone(),
+                cards: create_chance_cards(),
+                game: game_key,
+                deck_type: DeckType::Chance,
+                current_card_index: 0,
+                jail_card_held: false,
+            };
+            card_deck.pack_into_slice(&mut deck_account.data.borrow_mut());
+
+            set_player(JAIL_POSITION, 1, 0);
+            let result = process_use_jail_card(&program_id, jail_card_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NoJailCard.into());
+
+            set_player(JAIL_POSITION, 1, 1);
+            let result = process_use_jail_card(&program_id, jail_card_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidCardDeck.into());
+
+            card_deck.jail_card_held = true;
+            card_deck.pack_into_slice(&mut deck_account.data.borrow_mut());
+            let hold_card_from = |deck_type: DeckType| {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.chance_deck = Some(deck_key);
+                let player = &mut game_state.game.players[0];
+                player.community_chest_jail_card = deck_type == DeckType::CommunityChest;
+                player.chance_jail_card = deck_type == DeckType::Chance;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
             };
-            game_state.pack_into_slice(&mut game_data);
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_buy_property(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+            // A Community Chest card cannot go back to Chance
+            hold_card_from(DeckType::CommunityChest);
+            let result = process_use_jail_card(&program_id, jail_card_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidCardDeck.into());
+
+            hold_card_from(DeckType::Chance);
+            process_use_jail_card(&program_id, jail_card_accounts).unwrap();
+            assert!(!player().in_jail());
+            assert_eq!(player().get_out_of_jail_cards, 0);
+            assert!(!player().chance_jail_card);
+            assert_eq!(player().cash, 1500);
+
+            let card_deck = CardDeck::unpack_from_slice(&deck_account.data.borrow()).unwrap();
+            assert!(!card_deck.jail_card_held);
+            assert_eq!(card_deck.cards.len(), 16);
         }
     }
 
     #[test]
-    fn test_process_build_house() {
+    fn test_pending_actions() {
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -525,197 +660,125 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        // Initialize game state with a player owning a complete color set
-        let mut game = Game {
-            board: create_board(),
-            players: vec![
-                Player {
-                    name: String::from("Player 1"),
-                    cash: 1500,
-                    position: 1,
-                    jail_turns: 0,
-                    properties: vec![1, 3], // Mediterranean and Baltic (complete brown set)
-                    get_out_of_jail_cards: 0,
-                },
-                Player {
-                    name: String::from("Player 2"),
-                    cash: 1500,
-                    position: 0,
-                    jail_turns: 0,
-                    properties: vec![],
-                    get_out_of_jail_cards: 0,
-                },
-            ],
-            current_player: 0,
-            free_parking: 0,
-            initialized: true,
-        };
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
 
-        let game_state = GameAccount {
-            is_initialized: true,
-            game: game.clone(),
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        let guest_accounts = &[game_account.clone(), guest_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules { free_parking_jackpot: true, ..GameRules::default() }).unwrap();
+        process_join_game(&program_id, guest_accounts, String::from("Player 2")).unwrap();
+        process_start_game(&program_id, creator_accounts).unwrap();
+
+        let roll = |accounts: &[AccountInfo], dice: (u8, u8)| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.rolled_dice = Some(dice);
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            process_move_player(&program_id, accounts)
         };
-        game_state.pack_into_slice(&mut game_data);
-
-        // Test case 1: Successful house build
-        {
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 1; // Mediterranean Avenue
-            process_build_house(&program_id, accounts, property_index).unwrap();
-
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].cash, 1450); // 1500 - 50 (house cost)
-            assert_eq!(updated_game.game.board[property_index].houses, 1);
-        }
-
-        // Test case 2: Insufficient funds
-        {
-            game.players[0].cash = 40; // Less than house cost (50)
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 1;
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientFunds.into());
-        }
+        let load_game = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game;
 
-        // Test case 3: Property not owned by player
+        // Test case 1: An unowned property must be bought or auctioned before the turn ends
         {
-            game.players[0].cash = 1500;
-            game.players[0].properties = vec![1]; // Only owns Mediterranean
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 3; // Baltic Avenue (not owned)
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::PropertyNotOwned.into());
-        }
+            roll(creator_accounts, (1, 2)).unwrap();
+            assert_eq!(load_game().pending_action, Some(PendingAction::BuyProperty { property_index: 3 }));
 
-        // Test case 4: Incomplete color set
-        {
-            game.players[0].properties = vec![1]; // Only owns Mediterranean
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            let result = process_next_turn(&program_id, creator_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::PendingActionUnresolved.into());
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 1;
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::IncompleteColorSet.into());
+            process_buy_property(&program_id, creator_accounts, 3).unwrap();
+            assert_eq!(load_game().pending_action, None);
+            process_next_turn(&program_id, creator_accounts).unwrap();
         }
 
-        // Test case 5: Maximum houses reached
+        // Test case 2: Landing on an owned property owes rent to its owner
         {
-            game.players[0].properties = vec![1, 3]; // Complete brown set
-            if let TileType::Property(ref mut prop) = game.board[1] {
-                prop.houses = 5; // Max houses
-            }
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 1;
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::MaxHousesReached.into());
-        }
+            roll(guest_accounts, (1, 2)).unwrap();
+            assert_eq!(load_game().pending_action, Some(PendingAction::PayRent { property_index: 3, owner: 0, card_rent: false }));
 
-        // Test case 6: Missing player signature
-        {
-            if let TileType::Property(ref mut prop) = game.board[1] {
-                prop.houses = 0;
-            }
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            let rent_accounts = &[game_account.clone(), guest_account.clone(), creator_account.clone()];
+            let result = process_pay_rent(&program_id, rent_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::NoPendingAction.into());
 
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
+            process_pay_rent(&program_id, rent_accounts, 3).unwrap();
+            let game = load_game();
+            assert_eq!(game.pending_action, None);
+            assert_eq!(game.players[1].cash, 1500 - 4);
 
-            let accounts = &[game_account.clone(), unsigned_player_account];
-            let property_index = 1;
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+            let result = process_pay_rent(&program_id, rent_accounts, 3);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+            process_next_turn(&program_id, guest_accounts).unwrap();
         }
 
-        // Test case 7: Not current player's turn
+        // Test case 3: Doubles cannot be rolled again until the landed tile is resolved
         {
-            game.current_player = 1; // Switch to player 2's turn
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            roll(creator_accounts, (2, 2)).unwrap();
+            assert_eq!(load_game().pending_action, Some(PendingAction::DrawCard { deck_type: DeckType::Chance }));
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 1;
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+            let result = roll(creator_accounts, (1, 2));
+            assert_eq!(result.unwrap_err(), MonopolyError::PendingActionUnresolved.into());
         }
 
-        // Test case 8: Invalid property index
+        // Test case 4: Taxes feed Free Parking and landing there collects the pot
         {
-            game.current_player = 0;
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let property_index = 40; // Invalid index
-            let result = process_build_house(&program_id, accounts, property_index);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InvalidPropertyIndex.into());
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.pending_action = None;
+            update_turn_phase(&mut game_state.game);
+            game_state.game.players[0].position = 2;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            roll(creator_accounts, (1, 1)).unwrap();
+            let game = load_game();
+            assert_eq!(game.players[0].position, 4);
+            assert_eq!(game.free_parking, 200);
+            let cash = game.players[0].cash;
+
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.players[0].position = 16;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            roll(creator_accounts, (1, 3)).unwrap();
+            let game = load_game();
+            assert_eq!(game.players[0].position, 20);
+            assert_eq!(game.players[0].cash, cash + 200);
+            assert_eq!(game.free_parking, 0);
+            assert_eq!(game.pending_action, None);
         }
     }
 
     #[test]
-    fn test_process_pay_rent() {
+    fn test_turn_phases() {
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
-        let owner_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
-        let mut owner_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
-        let mut owner_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -728,199 +791,249 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        let owner_account = AccountInfo::new(
-            &owner_key,
-            false,
+        let guest_account = AccountInfo::new(
+            &guest_key,
             true,
-            &mut owner_lamports,
-            &mut owner_data,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
             &program_id,
             false,
             0,
         );
 
-        // Initialize game state with property owned by player 2
-        let mut game = Game {
-            board: create_board(),
-            players: vec![
-                Player {
-                    name: String::from("Player 1"),
-                    cash: 1500,
-                    position: 1, // Mediterranean Avenue
-                    jail_turns: 0,
-                    properties: vec![],
-                    get_out_of_jail_cards: 0,
-                },
-                Player {
-                    name: String::from("Player 2"),
-                    cash: 1500,
-                    position: 0,
-                    jail_turns: 0,
-                    properties: vec![1], // Owns Mediterranean Avenue
-                    get_out_of_jail_cards: 0,
-                },
-            ],
-            current_player: 0,
-            free_parking: 0,
-            initialized: true,
-        };
-
-        let game_state = GameAccount {
-            is_initialized: true,
-            game: game.clone(),
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        let guest_accounts = &[game_account.clone(), guest_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules { free_parking_jackpot: true, ..GameRules::default() }).unwrap();
+        process_join_game(&program_id, guest_accounts, String::from("Player 2")).unwrap();
+        process_start_game(&program_id, creator_accounts).unwrap();
+
+        let roll = |accounts: &[AccountInfo], dice: (u8, u8)| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.rolled_dice = Some(dice);
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            process_move_player(&program_id, accounts)
         };
-        game_state.pack_into_slice(&mut game_data);
+        let phase = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game.turn_phase;
 
-        // Test case 1: Successful rent payment
+        // Test case 1: The turn cannot end before rolling
         {
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            process_pay_rent(&program_id, accounts, 1).unwrap();
-
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].cash, 1498); // 1500 - 2 (base rent)
-            assert_eq!(updated_game.game.players[1].cash, 1502); // 1500 + 2 (received rent)
+            assert_eq!(phase(), TurnPhase::AwaitingRoll);
+            let result = process_next_turn(&program_id, creator_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
         }
 
-        // Test case 2: Property with houses (increased rent)
+        // Test case 2: Landing on a property has to be resolved before building or ending the turn
         {
-            if let TileType::Property(ref mut prop) = game.board[1] {
-                prop.houses = 3; // Add 3 houses to increase rent
-            }
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            roll(creator_accounts, (1, 2)).unwrap();
+            assert_eq!(phase(), TurnPhase::ResolvingTile);
 
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            process_pay_rent(&program_id, accounts, 1).unwrap();
+            let result = process_build_house(&program_id, creator_accounts, 3);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.players[0].cash, 1448); // 1500 - 50 (rent with 3 houses)
-            assert_eq!(updated_game.game.players[1].cash, 1550); // 1500 + 50 (received rent)
-        }
+            process_buy_property(&program_id, creator_accounts, 3).unwrap();
+            assert_eq!(phase(), TurnPhase::PostRoll);
 
-        // Test case 3: Insufficient funds
-        {
-            game.players[0].cash = 40; // Less than rent amount
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            let result = roll(creator_accounts, (1, 2));
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+            let result = process_buy_property(&program_id, creator_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
 
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            let result = process_pay_rent(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientFunds.into());
+            process_next_turn(&program_id, creator_accounts).unwrap();
+            assert_eq!(phase(), TurnPhase::AwaitingRoll);
         }
 
-        // Test case 4: Property not owned
+        // Test case 3: Doubles send the player back to roll once the tile is resolved
         {
-            game.players[1].properties = vec![]; // Remove property ownership
-            game.players[0].cash = 1500; // Reset cash
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            let result = process_pay_rent(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::PropertyNotOwned.into());
+            roll(guest_accounts, (3, 3)).unwrap();
+            assert_eq!(phase(), TurnPhase::ResolvingTile);
+            process_buy_property(&program_id, guest_accounts, 6).unwrap();
+            assert_eq!(phase(), TurnPhase::AwaitingRoll);
         }
 
-        // Test case 5: Player owns the property (no rent due)
+        // Test case 4: An unpaid tax waits on the debt before anything else
         {
-            game.players[0].properties = vec![1]; // Player owns the property
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[1].position = 36;
+                game_state.game.players[1].cash = 50;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            roll(guest_accounts, (1, 1)).unwrap();
+            assert_eq!(phase(), TurnPhase::AwaitingDebtResolution);
 
+            let result = process_pay_debt(&program_id, guest_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientBalance.into());
 
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            let result = process_pay_rent(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::OwnProperty.into());
+            process_mortgage(&program_id, guest_accounts, 6).unwrap();
+            process_pay_debt(&program_id, guest_accounts).unwrap();
+            // Paid late, the tax still feeds the Free Parking pot
+            let free_parking = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game.free_parking;
+            assert_eq!(free_parking, LUXURY_TAX);
+            // The tax came on doubles, so another roll is owed
+            assert_eq!(phase(), TurnPhase::AwaitingRoll);
         }
 
-        // Test case 6: Missing player signature
+        // Test case 5: Bankruptcy leaves one player and the game is over
         {
-            game.players[0].properties = vec![]; // Remove property ownership
-            game.players[1].properties = vec![1]; // Owner has property
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[1].position = 1;
+                game_state.game.players[1].cash = 0;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            roll(guest_accounts, (1, 2)).unwrap();
+            assert_eq!(phase(), TurnPhase::AwaitingDebtResolution);
+
+            // No jail card is held, so neither deck account is read
+            let bankruptcy_accounts = &[game_account.clone(), guest_account.clone(), game_account.clone(), game_account.clone()];
+            process_declare_bankruptcy(&program_id, bankruptcy_accounts).unwrap();
+            assert_eq!(phase(), TurnPhase::AwaitingRoll);
+
+            process_check_winner(&program_id, creator_accounts).unwrap();
+            assert_eq!(phase(), TurnPhase::GameOver);
+            let result = roll(creator_accounts, (1, 2));
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+        }
+    }
 
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
+    #[test]
+    fn test_buy_property_board_audit() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
 
-            let accounts = &[game_account.clone(), unsigned_player_account, owner_account.clone()];
-            let result = process_pay_rent(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
-        }
+        let mut game_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
 
-        // Test case 7: Not current player's turn
-        {
-            game.current_player = 1; // Switch to player 2's turn
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
 
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            let result = process_pay_rent(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let creator_account = AccountInfo::new(
+            &creator_key,
+            true,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        let guest_accounts = &[game_account.clone(), guest_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, guest_accounts, String::from("Player 2")).unwrap();
+        process_start_game(&program_id, creator_accounts).unwrap();
+
+        let stand_on = |position: u8| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.players[0].position = position;
+            game_state.game.players[0].cash = 1500;
+            game_state.game.turn_phase = TurnPhase::ResolvingTile;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+        let board_len = create_board().len() as u8;
+        assert_eq!(board_len, 40);
+
+        // Test case 1: Every tile other than the one the player stands on is rejected
+        for position in 0..board_len {
+            stand_on(position);
+            for property_index in (0..board_len).filter(|&index| index != position) {
+                let result = process_buy_property(&program_id, creator_accounts, property_index);
+                assert_eq!(
+                    result.unwrap_err(),
+                    MonopolyError::InvalidPropertyIndex.into(),
+                    "bought tile {} while standing on {}",
+                    property_index,
+                    position,
+                );
+            }
         }
 
-        // Test case 8: Not on a property space
-        {
-            game.current_player = 0;
-            game.players[0].position = 0; // GO space
-            let game_state = GameAccount {
-                is_initialized: true,
-                game: game.clone(),
-            };
-            game_state.pack_into_slice(&mut game_data);
+        // Test case 2: The tile underfoot can be bought exactly when it is an unowned property
+        for position in 0..board_len {
+            stand_on(position);
+            let is_property = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game.board
+                [position as usize]
+                .as_property()
+                .is_some();
+
+            let result = process_buy_property(&program_id, creator_accounts, position);
+            if is_property {
+                result.unwrap();
+                let game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game;
+                assert!(game.players[0].properties.contains(&position));
+            } else {
+                assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+            }
+        }
 
-            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
-            let result = process_pay_rent(&program_id, accounts, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::NotAProperty.into());
+        // Test case 3: The other player cannot buy on the current player's behalf
+        {
+            stand_on(1);
+            let result = process_buy_property(&program_id, guest_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
         }
     }
 
     #[test]
-    fn test_process_next_turn() {
+    fn test_house_rules() {
+        use solana_program::sysvar::clock;
+
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let clock_key = clock::ID;
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut clock_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut clock_data = bincode::serialize(&Clock::default()).unwrap();
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -933,169 +1046,152 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        // Test case 1: Normal turn progression with no bankrupt players
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let clock_account = AccountInfo::new(
+            &clock_key,
+            false,
+            false,
+            &mut clock_lamports,
+            &mut clock_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let rules = GameRules {
+            starting_cash: 1000,
+            go_salary: 300,
+            jail_fine: 25,
+            free_parking_jackpot: false,
+            double_go_salary: true,
+            no_rent_in_jail: true,
+            auction_on_decline: false,
+        };
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        let guest_accounts = &[game_account.clone(), guest_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, rules.clone()).unwrap();
+        process_join_game(&program_id, guest_accounts, String::from("Player 2")).unwrap();
+        process_start_game(&program_id, creator_accounts).unwrap();
+
+        let roll = |accounts: &[AccountInfo], from: u8, dice: (u8, u8)| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            let current_player = game_state.game.current_player as usize;
+            game_state.game.players[current_player].position = from;
+            game_state.game.rolled_dice = Some(dice);
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            process_move_player(&program_id, accounts)
+        };
+        let load_game = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game;
+
+        // Test case 1: Every player starts with the configured cash
         {
-            let game = Game {
-                board: create_board(),
-                players: vec![
-                    Player {
-                        name: String::from("Player 1"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {
-                        name: String::from("Player 2"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                ],
-                current_player: 0,
-                free_parking: 0,
-                initialized: true,
-            };
+            let game = load_game();
+            assert_eq!(game.rules, rules);
+            assert!(game.players.iter().all(|p| p.cash == 1000));
+        }
 
-            let game_state = GameAccount {
-                is_initialized: true,
-                game,
-            };
-            game_state.pack_into_slice(&mut game_data);
+        // Test case 2: Without the jackpot, taxes go to the bank rather than Free Parking
+        {
+            roll(creator_accounts, 1, (1, 2)).unwrap();
+            let game = load_game();
+            assert_eq!(game.players[0].position, 4);
+            assert_eq!(game.players[0].cash, 1000 - 200);
+            assert_eq!(game.free_parking, 0);
+            process_next_turn(&program_id, creator_accounts).unwrap();
+        }
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            process_next_turn(&program_id, accounts).unwrap();
+        // Test case 3: Landing exactly on GO pays double the configured salary
+        {
+            roll(guest_accounts, 37, (1, 2)).unwrap();
+            let game = load_game();
+            assert_eq!(game.players[1].position, 0);
+            assert_eq!(game.players[1].cash, 1000 + 600);
+            process_next_turn(&program_id, guest_accounts).unwrap();
+        }
 
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.current_player, 1, "Turn should advance to next player");
+        // Test case 4: A declined property is not auctioned and stays with the bank
+        {
+            roll(creator_accounts, 0, (1, 2)).unwrap();
+            assert_eq!(load_game().pending_action, Some(PendingAction::BuyProperty { property_index: 3 }));
+
+            let auction_accounts = &[game_account.clone(), creator_account.clone(), clock_account.clone()];
+            let result = process_start_auction(&program_id, auction_accounts, 3);
+            assert_eq!(result.unwrap_err(), MonopolyError::AuctionDisabled.into());
+
+            process_next_turn(&program_id, creator_accounts).unwrap();
+            let game = load_game();
+            assert_eq!(game.current_player, 1);
+            assert_eq!(game.board[3].as_property().unwrap().owner, None);
+            assert_eq!(game.pending_action, None);
         }
 
-        // Test case 2: Skip bankrupt players
+        // Test case 5: An owner sitting in jail collects no rent
         {
-            let game = Game {
-                board: create_board(),
-                players: vec![
-                    Player {
-                        name: String::from("Player 1"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {  // Bankrupt player
-                        name: String::from("Player 2"),
-                        cash: 0,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {
-                        name: String::from("Player 3"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                ],
-                current_player: 0,
-                free_parking: 0,
-                initialized: true,
-            };
-
-            let game_state = GameAccount {
-                is_initialized: true,
-                game,
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            process_next_turn(&program_id, accounts).unwrap();
-
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.current_player, 2, "Turn should skip bankrupt player");
-        }
-
-        // Test case 3: Wrap around to first non-bankrupt player
-        {
-            let game = Game {
-                board: create_board(),
-                players: vec![
-                    Player {
-                        name: String::from("Player 1"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {  // Bankrupt player
-                        name: String::from("Player 2"),
-                        cash: 0,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                ],
-                current_player: 0,
-                free_parking: 0,
-                initialized: true,
-            };
-
-            let game_state = GameAccount {
-                is_initialized: true,
-                game,
-            };
-            game_state.pack_into_slice(&mut game_data);
-
-            let accounts = &[game_account.clone(), player_account.clone()];
-            process_next_turn(&program_id, accounts).unwrap();
-
-            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert_eq!(updated_game.game.current_player, 0, "Turn should wrap around to first non-bankrupt player");
+            roll(guest_accounts, 0, (1, 2)).unwrap();
+            process_buy_property(&program_id, guest_accounts, 3).unwrap();
+            process_next_turn(&program_id, guest_accounts).unwrap();
+
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                send_to_jail(&mut game_state.game.players[1]);
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            roll(creator_accounts, 0, (1, 2)).unwrap();
+            let game = load_game();
+            assert_eq!(game.pending_action, None);
+            assert_eq!(game.turn_phase, TurnPhase::PostRoll);
+            process_next_turn(&program_id, creator_accounts).unwrap();
         }
 
-        // Test case 4: Missing player signature
+        // Test case 6: Bail costs the configured jail fine
         {
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
-
-            let accounts = &[game_account.clone(), unsigned_player_account];
-            let result = process_next_turn(&program_id, accounts);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+            let cash = load_game().players[1].cash;
+            process_pay_bail(&program_id, guest_accounts).unwrap();
+            let game = load_game();
+            assert_eq!(game.players[1].cash, cash - 25);
+            assert!(!game.players[1].in_jail());
         }
     }
 
     #[test]
-    fn test_process_check_winner() {
+    fn test_card_effects() {
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let third_key = Pubkey::new_unique();
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut third_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut third_data = vec![0; 32];
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -1108,175 +1204,231 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        // Test case 1: No winner yet (multiple active players)
-        {
-            let game = Game {
-                board: create_board(),
-                players: vec![
-                    Player {
-                        name: String::from("Player 1"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {
-                        name: String::from("Player 2"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                ],
-                current_player: 0,
-                free_parking: 0,
-                initialized: true,
-            };
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
 
-            let game_state = GameAccount {
-                is_initialized: true,
-                game,
-            };
-            game_state.pack_into_slice(&mut game_data);
+        let third_account = AccountInfo::new(
+            &third_key,
+            true,
+            true,
+            &mut third_lamports,
+            &mut third_data,
+            &program_id,
+            false,
+            0,
+        );
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_check_winner(&program_id, accounts).unwrap();
-            assert!(result.is_none(), "Should not have a winner yet");
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 3, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), guest_account.clone()], String::from("Player 2")).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), third_account.clone()], String::from("Player 3")).unwrap();
+        process_start_game(&program_id, creator_accounts).unwrap();
+
+        // Put the creator on `position` with fresh cash and apply `card` as if just drawn
+        let draw = |position: u8, card: Card| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            for player in game_state.game.players.iter_mut() {
+                player.cash = 1500;
+            }
+            game_state.game.players[0].position = position;
+            game_state.game.pending_action = None;
+            game_state.game.debt = None;
+            apply_card(&mut game_state.game, 0, &card, DeckType::Chance).unwrap();
+            update_turn_phase(&mut game_state.game);
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+        let give = |property_index: usize, owner: u8, houses: u8| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            if let TileType::Property(prop) = &mut game_state.game.board[property_index] {
+                prop.owner = Some(owner);
+                prop.houses = houses;
+            }
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+        let load_game = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game;
+
+        // Test case 1: Going back never collects the GO salary but still resolves the tile
+        {
+            draw(7, Card::MoveBack(3));
+            let game = load_game();
+            assert_eq!(game.players[0].position, 4);
+            assert_eq!(game.players[0].cash, 1500 - 200);
+
+            draw(1, Card::MoveBack(3));
+            let game = load_game();
+            assert_eq!(game.players[0].position, 38);
+            assert_eq!(game.players[0].cash, 1500 - 100);
+
+            draw(36, Card::MoveBack(3));
+            let game = load_game();
+            assert_eq!(game.players[0].position, 33);
+            assert_eq!(game.pending_action, Some(PendingAction::DrawCard { deck_type: DeckType::CommunityChest }));
+            assert_eq!(game.turn_phase, TurnPhase::ResolvingTile);
         }
 
-        // Test case 2: One winner (all other players bankrupt)
+        // Test case 2: Advancing to an unowned railroad past GO pays salary and offers the railroad
         {
-            let game = Game {
-                board: create_board(),
-                players: vec![
-                    Player {
-                        name: String::from("Winner"),
-                        cash: 1500,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {  // Bankrupt player
-                        name: String::from("Loser 1"),
-                        cash: 0,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {  // Bankrupt player
-                        name: String::from("Loser 2"),
-                        cash: 0,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                ],
-                current_player: 0,
-                free_parking: 0,
-                initialized: true,
-            };
+            draw(36, Card::AdvanceToNearest(TileKind::Railroad));
+            let game = load_game();
+            assert_eq!(game.players[0].position, 5);
+            assert_eq!(game.players[0].cash, 1500 + 200);
+            assert_eq!(game.pending_action, Some(PendingAction::BuyProperty { property_index: 5 }));
+            assert_eq!(game.turn_phase, TurnPhase::ResolvingTile);
+        }
 
-            let game_state = GameAccount {
-                is_initialized: true,
-                game,
-            };
-            game_state.pack_into_slice(&mut game_data);
+        // Test case 3: An owned railroad charges double rent
+        {
+            give(15, 1, 0);
+            draw(7, Card::AdvanceToNearest(TileKind::Railroad));
+            assert_eq!(
+                load_game().pending_action,
+                Some(PendingAction::PayRent { property_index: 15, owner: 1, card_rent: true })
+            );
+
+            let rent_accounts = &[game_account.clone(), creator_account.clone(), guest_account.clone()];
+            process_pay_rent(&program_id, rent_accounts, 15).unwrap();
+            let game = load_game();
+            assert_eq!(game.players[0].cash, 1500 - 50);
+            assert_eq!(game.players[1].cash, 1500 + 50);
+        }
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_check_winner(&program_id, accounts).unwrap();
-            assert!(result.is_some(), "Should have a winner");
-            assert_eq!(result.unwrap(), 0, "Player 0 should be the winner");
+        // Test case 4: An owned utility charges ten times the dice
+        {
+            give(12, 1, 0);
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.last_roll = 4;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            draw(7, Card::AdvanceToNearest(TileKind::Utility));
+            let rent_accounts = &[game_account.clone(), creator_account.clone(), guest_account.clone()];
+            process_pay_rent(&program_id, rent_accounts, 12).unwrap();
+            assert_eq!(load_game().players[0].cash, 1500 - 40);
         }
 
-        // Test case 3: Missing player signature
+        // Test case 5: Repairs charge per house and per hotel
         {
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
+            give(1, 0, 3);
+            give(3, 0, 5);
+            draw(7, Card::PayPerHouseAndHotel { house: 25, hotel: 100 });
+            assert_eq!(load_game().players[0].cash, 1500 - 3 * 25 - 100);
+        }
 
-            let accounts = &[game_account.clone(), unsigned_player_account];
-            let result = process_check_winner(&program_id, accounts);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        // Test case 6: Every other player pays the drawer, and a short player owes the rest
+        {
+            draw(2, Card::CollectFromEachPlayer(10));
+            let game = load_game();
+            assert_eq!(game.players[0].cash, 1500 + 20);
+            assert_eq!(game.players[1].cash, 1500 - 10);
+            assert_eq!(game.players[2].cash, 1500 - 10);
+
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[0].position = 2;
+                game_state.game.players[2].cash = 5;
+                game_state.game.pending_action = None;
+                apply_card(&mut game_state.game, 0, &Card::CollectFromEachPlayer(10), DeckType::Chance).unwrap();
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            let game = load_game();
+            assert_eq!(game.players[2].cash, 5);
+            assert_eq!(game.debt, Some(Debt { debtor: 2, creditor: Some(0), amount: 10 }));
         }
 
-        // Test case 4: All players bankrupt (should never happen in normal gameplay)
+        // Test case 7: The drawer pays every other player until the cash runs out
         {
-            let game = Game {
-                board: create_board(),
-                players: vec![
-                    Player {
-                        name: String::from("Bankrupt 1"),
-                        cash: 0,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                    Player {
-                        name: String::from("Bankrupt 2"),
-                        cash: 0,
-                        position: 0,
-                        jail_turns: 0,
-                        properties: vec![],
-                        get_out_of_jail_cards: 0,
-                    },
-                ],
-                current_player: 0,
-                free_parking: 0,
-                initialized: true,
-            };
+            draw(7, Card::PayEachPlayer(50));
+            let game = load_game();
+            assert_eq!(game.players[0].cash, 1500 - 100);
+            assert_eq!(game.players[1].cash, 1500 + 50);
+            assert_eq!(game.players[2].cash, 1500 + 50);
+
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[0].cash = 60;
+                apply_card(&mut game_state.game, 0, &Card::PayEachPlayer(50), DeckType::CommunityChest).unwrap();
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            let game = load_game();
+            assert_eq!(game.players[0].cash, 10);
+            assert_eq!(game.debt, Some(Debt { debtor: 0, creditor: Some(2), amount: 50 }));
+        }
 
-            let game_state = GameAccount {
-                is_initialized: true,
-                game,
-            };
-            game_state.pack_into_slice(&mut game_data);
+        // Test case 8: Advancing onto an owned property leaves normal rent to pay
+        {
+            give(39, 1, 0);
+            draw(36, Card::Move(39));
+            let game = load_game();
+            assert_eq!(game.players[0].position, 39);
+            assert_eq!(game.players[0].cash, 1500);
+            assert_eq!(
+                game.pending_action,
+                Some(PendingAction::PayRent { property_index: 39, owner: 1, card_rent: false })
+            );
+            assert_eq!(game.turn_phase, TurnPhase::ResolvingTile);
+        }
 
-            let accounts = &[game_account.clone(), player_account.clone()];
-            let result = process_check_winner(&program_id, accounts).unwrap();
-            assert!(result.is_none(), "Should not have a winner when all players are bankrupt");
+        // Test case 9: Going to jail by card never passes GO
+        {
+            draw(36, Card::GoToJail);
+            let game = load_game();
+            assert_eq!(game.players[0].position, JAIL_POSITION);
+            assert!(game.players[0].in_jail());
+            assert_eq!(game.players[0].cash, 1500);
+            assert_eq!(game.pending_action, None);
+
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[0].jail_turns = 0;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            draw(22, Card::Move(30));
+            let game = load_game();
+            assert_eq!(game.players[0].position, JAIL_POSITION);
+            assert!(game.players[0].in_jail());
+            assert_eq!(game.players[0].cash, 1500);
         }
     }
 
     #[test]
-    fn test_process_draw_card() {
-        use solana_program::clock::Clock;
-        use solana_program::account_info::IntoAccountInfo;
-        use solana_program::sysvar::clock;
-        use std::cell::RefCell;
+    fn test_card_draw_pile() {
+        use crate::account::CardDeck;
 
         // Create test accounts
-        let program_id = Pubkey::new_unique();
+        let program_id = crate::id();
         let game_key = Pubkey::new_unique();
-        let player_key = Pubkey::new_unique();
-        let card_deck_key = Pubkey::new_unique();
-        let clock_key = clock::ID;
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let deck_key = Pubkey::new_unique();
 
         let mut game_lamports = 0;
-        let mut player_lamports = 0;
-        let mut card_deck_lamports = 0;
-        let mut clock_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut deck_lamports = 0;
 
-        let mut game_data = vec![0; 8192];
-        let mut player_data = vec![0; 32];
-        let mut card_deck_data = vec![0; 1024];
-        let mut clock_data = vec![0; std::mem::size_of::<Clock>()];
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut deck_data = vec![0; CardDeck::LEN];
 
         let game_account = AccountInfo::new(
             &game_key,
@@ -1289,604 +1441,5233 @@ mod tests {
             0,
         );
 
-        let player_account = AccountInfo::new(
-            &player_key,
-            true,  // Player must be signer
+        let creator_account = AccountInfo::new(
+            &creator_key,
             true,
-            &mut player_lamports,
-            &mut player_data,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
             &program_id,
             false,
             0,
         );
 
-        let card_deck_account = AccountInfo::new(
-            &card_deck_key,
-            false,
+        let guest_account = AccountInfo::new(
+            &guest_key,
             true,
-            &mut card_deck_lamports,
-            &mut card_deck_data,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
             &program_id,
             false,
             0,
         );
 
-        let clock_account = AccountInfo::new(
-            &clock_key,
+        let deck_account = AccountInfo::new(
+            &deck_key,
             false,
             true,
-            &mut clock_lamports,
-            &mut clock_data,
+            &mut deck_lamports,
+            &mut deck_data,
             &program_id,
             false,
             0,
         );
 
-        // Initialize game state
-        let mut game = Game {
-            board: create_board(),
-            players: vec![Player {
-                name: String::from("Test Player"),
-                cash: 1500,
-                position: 0,
-                jail_turns: 0,
-                properties: vec![],
-                get_out_of_jail_cards: 0,
-            }],
-            current_player: 0,
-            free_parking: 0,
-            initialized: true,
+        let creator_accounts = &[game_account.clone(), creator_account.clone()];
+        process_create_game(&program_id, creator_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), guest_account.clone()], String::from("Player 2")).unwrap();
+        process_start_game(&program_id, creator_accounts).unwrap();
+
+        // A seeded game reads nothing from the randomness account
+        let draw_accounts = &[game_account.clone(), creator_account.clone(), deck_account.clone(), game_account.clone()];
+        let draw_from = |accounts: &[AccountInfo]| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            for player in game_state.game.players.iter_mut() {
+                player.cash = 1500;
+                player.jail_turns = 0;
+            }
+            game_state.game.players[0].position = 7;
+            game_state.game.debt = None;
+            game_state.game.pending_action = Some(PendingAction::DrawCard { deck_type: DeckType::Chance });
+            game_state.game.turn_phase = TurnPhase::ResolvingTile;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            process_draw_card(&program_id, accounts, DeckType::Chance)
+        };
+        let draw = || draw_from(draw_accounts);
+        let load_deck = || CardDeck::unpack_from_slice(&deck_account.data.borrow()).unwrap();
+        let nonce = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game.randomness_nonce;
+
+        // Test case 1: The first draw sets up and shuffles the deck
+        {
+            draw().unwrap();
+            let card_deck = load_deck();
+            assert!(card_deck.is_initialized);
+            assert_eq!(card_deck.game, game_key);
+            assert_eq!(card_deck.deck_type, DeckType::Chance);
+            assert_eq!(card_deck.cards.len(), 16);
+            assert_eq!(card_deck.current_card_index, 1);
+            assert_eq!(nonce(), 1);
+            let game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game;
+            assert_eq!(game.chance_deck, Some(deck_key));
+            assert_eq!(game.community_chest_deck, None);
+        }
+
+        // Test case 2: The rest of the pile is drawn in order without new randomness
+        {
+            for _ in 1..16 {
+                draw().unwrap();
+            }
+            let card_deck = load_deck();
+            assert_eq!(card_deck.current_card_index, 16);
+            assert!(card_deck.jail_card_held);
+            assert_eq!(nonce(), 1);
+        }
+
+        // Test case 3: An exhausted pile is reshuffled on the next draw
+        {
+            draw().unwrap();
+            let card_deck = load_deck();
+            assert!(card_deck.current_card_index >= 1);
+            assert_eq!(nonce(), 2);
+        }
+
+        // Test case 4: A deck belonging to another game is refused
+        {
+            let mut card_deck = load_deck();
+            card_deck.game = Pubkey::new_unique();
+            card_deck.pack_into_slice(&mut deck_account.data.borrow_mut());
+            let result = draw();
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidCardDeck.into());
+        }
+
+        // Test case 5: Once a deck is bound, a fresh deck account is refused
+        {
+            let other_key = Pubkey::new_unique();
+            let mut other_lamports = 0;
+            let mut other_data = vec![0; CardDeck::LEN];
+            let other_deck = AccountInfo::new(&other_key, false, true, &mut other_lamports, &mut other_data, &program_id, false, 0);
+            let accounts = &[game_account.clone(), creator_account.clone(), other_deck.clone(), game_account.clone()];
+            let result = draw_from(accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidCardDeck.into());
+            assert!(other_deck.data.borrow().iter().all(|&byte| byte == 0));
+        }
+    }
+
+    #[test]
+    fn test_migrate_account() {
+        // Create test accounts
+        let program_id = crate::id();
+        let request_key = Pubkey::new_unique();
+        let payer_key = Pubkey::new_unique();
+        let outsider_key = Pubkey::new_unique();
+        let (program_data_key, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+        let rent_key = solana_program::sysvar::rent::id();
+        let system_key = solana_program::system_program::id();
+        let sysvar_owner = solana_program::sysvar::id();
+        let native_loader = Pubkey::default();
+        let upgradeable_loader = bpf_loader_upgradeable::id();
+
+        let mut request_lamports = 0;
+        let mut payer_lamports = 1_000_000;
+        let mut program_data_lamports = 0;
+        let mut rent_lamports = 0;
+        let mut system_lamports = 0;
+
+        let mut request_data = vec![0; RandomnessRequest::LEN];
+        let mut payer_data = vec![];
+        let mut program_data_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(payer_key),
+        })
+        .unwrap();
+        let mut rent_data = bincode::serialize(&Rent::default()).unwrap();
+        let mut system_data = vec![];
+
+        let request_account = AccountInfo::new(
+            &request_key,
+            false,
+            true,
+            &mut request_lamports,
+            &mut request_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let payer_account = AccountInfo::new(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_key,
+            false,
+            0,
+        );
+
+        let program_data_account = AccountInfo::new(
+            &program_data_key,
+            false,
+            false,
+            &mut program_data_lamports,
+            &mut program_data_data,
+            &upgradeable_loader,
+            false,
+            0,
+        );
+
+        let rent_account = AccountInfo::new(
+            &rent_key,
+            false,
+            false,
+            &mut rent_lamports,
+            &mut rent_data,
+            &sysvar_owner,
+            false,
+            0,
+        );
+
+        let system_account = AccountInfo::new(
+            &system_key,
+            false,
+            false,
+            &mut system_lamports,
+            &mut system_data,
+            &native_loader,
+            true,
+            0,
+        );
+
+        let accounts = &[request_account.clone(), payer_account.clone(), program_data_account.clone(), rent_account.clone(), system_account.clone()];
+
+        // Test case 1: An account already at the current size, or of a kind with no version 1 layout, is refused
+        {
+            RandomnessRequest {
+                is_initialized: true,
+                game: Pubkey::new_unique(),
+                nonce: 0,
+                fulfilled: false,
+                output: [0; 32],
+            }
+            .pack_into_slice(&mut request_account.data.borrow_mut());
+            let result = process_migrate_account(&program_id, accounts, AccountKind::RandomnessRequest);
+            assert_eq!(result.unwrap_err(), MonopolyError::UnsupportedAccountVersion.into());
+
+            // Nor is its size right for any other kind
+            let result = process_migrate_account(&program_id, accounts, AccountKind::Game);
+            assert_eq!(result.unwrap_err(), MonopolyError::UnsupportedAccountVersion.into());
+        }
+
+        // Test case 2: The program's upgrade authority must sign
+        {
+            let mut unsigned_payer = payer_account.clone();
+            unsigned_payer.is_signer = false;
+            let accounts = &[request_account.clone(), unsigned_payer, program_data_account.clone(), rent_account.clone(), system_account.clone()];
+            let result = process_migrate_account(&program_id, accounts, AccountKind::RandomnessRequest);
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+
+            let mut outsider = payer_account.clone();
+            outsider.key = &outsider_key;
+            let accounts = &[request_account.clone(), outsider, program_data_account.clone(), rent_account.clone(), system_account.clone()];
+            let result = process_migrate_account(&program_id, accounts, AccountKind::RandomnessRequest);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotUpgradeAuthority.into());
+
+            // The authority is read from this program's ProgramData account only
+            let mut forged = program_data_account.clone();
+            forged.key = &outsider_key;
+            let accounts = &[request_account.clone(), payer_account.clone(), forged, rent_account.clone(), system_account.clone()];
+            let result = process_migrate_account(&program_id, accounts, AccountKind::RandomnessRequest);
+            assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+        }
+
+        // Test case 3: Only this program's accounts are migrated
+        {
+            let accounts = &[payer_account.clone(), payer_account.clone(), program_data_account.clone(), rent_account.clone(), system_account.clone()];
+            let result = process_migrate_account(&program_id, accounts, AccountKind::RandomnessRequest);
+            assert_eq!(result.unwrap_err(), ProgramError::IncorrectProgramId);
+        }
+
+        // Test case 4: Wallets taking version 1 seats must sign, one per seat
+        {
+            // A blank version 1 game account decodes as a game with no seats
+            let game_key = Pubkey::new_unique();
+            let wallet_key = Pubkey::new_unique();
+            let mut game_lamports = 0;
+            let mut wallet_lamports = 0;
+            let mut game_data = vec![0; account_v1::GAME_ACCOUNT_LEN];
+            let mut wallet_data = vec![];
+            let game_account = AccountInfo::new(&game_key, false, true, &mut game_lamports, &mut game_data, &program_id, false, 0);
+            let mut wallet = AccountInfo::new(&wallet_key, false, false, &mut wallet_lamports, &mut wallet_data, &system_key, false, 0);
+
+            let accounts = &[game_account.clone(), payer_account.clone(), program_data_account.clone(), rent_account.clone(), system_account.clone(), wallet.clone()];
+            let result = process_migrate_account(&program_id, accounts, AccountKind::Game);
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+
+            wallet.is_signer = true;
+            let accounts = &[game_account.clone(), payer_account.clone(), program_data_account.clone(), rent_account.clone(), system_account.clone(), wallet];
+            let result = process_migrate_account(&program_id, accounts, AccountKind::Game);
+            assert_eq!(result.unwrap_err(), ProgramError::InvalidArgument);
+
+            // The account is left untouched
+            assert_eq!(game_account.data_len(), account_v1::GAME_ACCOUNT_LEN);
+            assert!(game_account.data.borrow().iter().all(|&byte| byte == 0));
+        }
+    }
+
+    #[test]
+    fn test_dice_rounds() {
+        use solana_program::sysvar::{clock, slot_hashes};
+
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let creator_key = Pubkey::new_unique();
+        let guest_key = Pubkey::new_unique();
+        let clock_key = clock::ID;
+        let slot_hashes_key = slot_hashes::ID;
+
+        let mut game_lamports = 0;
+        let mut creator_lamports = 0;
+        let mut guest_lamports = 0;
+        let mut clock_lamports = 0;
+        let mut slot_hashes_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut creator_data = vec![0; 32];
+        let mut guest_data = vec![0; 32];
+        let mut clock_data = bincode::serialize(&Clock::default()).unwrap();
+        let slot_hash = [9u8; 32];
+        let mut slot_hashes_data = vec![0u8; 8 + 40 * 2];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let creator_account = AccountInfo::new(
+            &creator_key,
+            true,
+            true,
+            &mut creator_lamports,
+            &mut creator_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let guest_account = AccountInfo::new(
+            &guest_key,
+            true,
+            true,
+            &mut guest_lamports,
+            &mut guest_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let clock_account = AccountInfo::new(
+            &clock_key,
+            false,
+            false,
+            &mut clock_lamports,
+            &mut clock_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let slot_hashes_account = AccountInfo::new(
+            &slot_hashes_key,
+            false,
+            false,
+            &mut slot_hashes_lamports,
+            &mut slot_hashes_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let set_slot = |slot: u64| {
+            let clock = Clock { slot, ..Clock::default() };
+            clock_account.data.borrow_mut().copy_from_slice(&bincode::serialize(&clock).unwrap());
+        };
+        let load_game = || GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap().game;
+        // Record `slot_hash` for `slot`, and some other hash for the slot after it
+        let set_slot_hashes = |slot: u64| {
+            let mut data = slot_hashes_account.data.borrow_mut();
+            data[0..8].copy_from_slice(&2u64.to_le_bytes());
+            data[8..16].copy_from_slice(&(slot + 1).to_le_bytes());
+            data[16..48].copy_from_slice(&[8u8; 32]);
+            data[48..56].copy_from_slice(&slot.to_le_bytes());
+            data[56..88].copy_from_slice(&slot_hash);
+        };
+
+        let lobby_accounts = &[game_account.clone(), creator_account.clone()];
+        process_create_game(&program_id, lobby_accounts, String::from("Player 1"), 2, RandomnessKind::SlotHash, GameRules { free_parking_jackpot: true, ..GameRules::default() }).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), guest_account.clone()], String::from("Player 2")).unwrap();
+        process_start_game(&program_id, lobby_accounts).unwrap();
+
+        let creator_accounts = &[
+            game_account.clone(), creator_account.clone(), clock_account.clone(), slot_hashes_account.clone(),
+        ];
+        let guest_accounts = &[
+            game_account.clone(), guest_account.clone(), clock_account.clone(), slot_hashes_account.clone(),
+        ];
+        let source = SlotHashEntropy { slot_hash };
+        let creator_secret = [1u8; 32];
+        let guest_secret = [2u8; 32];
+        let creator_commitment = dice_commitment(&creator_secret, &creator_key);
+        let guest_commitment = dice_commitment(&guest_secret, &guest_key);
+
+        // Test case 1: Nothing can be revealed before a round opens
+        {
+            let result = process_reveal_dice(&program_id, creator_accounts, creator_secret);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidDicePhase.into());
+        }
+
+        // Test case 2: The first commitment opens the round and cannot be repeated
+        {
+            process_commit_dice(&program_id, creator_accounts, creator_commitment).unwrap();
+            let game = load_game();
+            assert_eq!(game.dice_round, Some(DiceRound { phase: DicePhase::Commit, deadline_slot: DICE_COMMIT_SLOTS }));
+            assert_eq!(game.players[0].dice_commitment, Some(creator_commitment));
+
+            let result = process_commit_dice(&program_id, creator_accounts, creator_commitment);
+            assert_eq!(result.unwrap_err(), MonopolyError::DiceAlreadyCommitted.into());
+        }
+
+        // Test case 3: The last commitment opens the reveal phase
+        {
+            set_slot(10);
+            process_commit_dice(&program_id, guest_accounts, guest_commitment).unwrap();
+            let game = load_game();
+            assert_eq!(game.dice_round, Some(DiceRound { phase: DicePhase::Reveal, deadline_slot: 10 + DICE_REVEAL_SLOTS }));
+        }
+
+        // Test case 4: A secret must match its commitment
+        {
+            let result = process_reveal_dice(&program_id, creator_accounts, guest_secret);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidDiceReveal.into());
+        }
+
+        // Test case 5: The round cannot be settled while reveals are still open
+        {
+            process_reveal_dice(&program_id, creator_accounts, creator_secret).unwrap();
+            let result = process_settle_dice(&program_id, creator_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::DiceRoundNotExpired.into());
+        }
+
+        // Test case 6: The last reveal rolls from every secret and the hash of the slot the commits closed on
+        {
+            set_slot_hashes(8);
+            let result = process_reveal_dice(&program_id, guest_accounts, guest_secret);
+            assert_eq!(result.unwrap_err(), MonopolyError::RandomnessUnavailable.into());
+            // The failed reveal would be rolled back on chain
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.players[1].dice_secret = None;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            set_slot(12);
+            set_slot_hashes(10);
+            process_reveal_dice(&program_id, guest_accounts, guest_secret).unwrap();
+            let game = load_game();
+            assert_eq!(game.rolled_dice, Some(roll_dice(&[creator_secret, guest_secret], &source, 0)));
+            assert_eq!(game.randomness_nonce, 1);
+            assert_eq!(game.dice_round, None);
+            assert!(game.players.iter().all(|p| p.dice_commitment.is_none() && p.dice_secret.is_none()));
+
+            let result = process_commit_dice(&program_id, creator_accounts, creator_commitment);
+            assert_eq!(result.unwrap_err(), MonopolyError::DiceAlreadyRolled.into());
+        }
+
+        // Test case 7: A committed player who never reveals is fined once the window closes
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.rolled_dice = None;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            set_slot(100);
+            process_commit_dice(&program_id, creator_accounts, creator_commitment).unwrap();
+            process_commit_dice(&program_id, guest_accounts, guest_commitment).unwrap();
+            process_reveal_dice(&program_id, creator_accounts, creator_secret).unwrap();
+            assert_eq!(load_game().rolled_dice, None);
+
+            set_slot(101 + DICE_REVEAL_SLOTS);
+            set_slot_hashes(100);
+            process_settle_dice(&program_id, creator_accounts).unwrap();
+            let game = load_game();
+            assert_eq!(game.rolled_dice, Some(roll_dice(&[creator_secret], &source, 1)));
+            assert_eq!(game.players[0].cash, 1500);
+            assert_eq!(game.players[1].cash, 1500 - DICE_TIMEOUT_PENALTY);
+            assert_eq!(game.free_parking, DICE_TIMEOUT_PENALTY);
+        }
+
+        // Test case 8: A player who misses the commit window is fined when the round settles, and the
+        // timed-out commits close on their deadline slot rather than the slot of the next call
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.rolled_dice = None;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            set_slot(1_000);
+            process_commit_dice(&program_id, creator_accounts, creator_commitment).unwrap();
+            set_slot(1_001 + DICE_COMMIT_SLOTS);
+            let result = process_commit_dice(&program_id, guest_accounts, guest_commitment);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidDicePhase.into());
+
+            set_slot(1_010 + DICE_COMMIT_SLOTS);
+            set_slot_hashes(1_000 + DICE_COMMIT_SLOTS);
+            process_reveal_dice(&program_id, creator_accounts, creator_secret).unwrap();
+            let game = load_game();
+            assert_eq!(game.rolled_dice, Some(roll_dice(&[creator_secret], &source, 2)));
+            assert_eq!(game.players[1].cash, 1500 - 2 * DICE_TIMEOUT_PENALTY);
+        }
+    }
+
+    #[test]
+    fn test_process_mortgage() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let other_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut owner_lamports = 0;
+        let mut other_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut owner_data = vec![0; 32];
+        let mut other_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let owner_account = AccountInfo::new(
+            &owner_key,
+            true,
+            true,
+            &mut owner_lamports,
+            &mut owner_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let other_account = AccountInfo::new(
+            &other_key,
+            true,
+            true,
+            &mut other_lamports,
+            &mut other_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let owner_accounts = &[game_account.clone(), owner_account.clone()];
+        let other_accounts = &[game_account.clone(), other_account.clone()];
+        process_create_game(&program_id, owner_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, other_accounts, String::from("Player 2")).unwrap();
+        process_start_game(&program_id, owner_accounts).unwrap();
+
+        // Give the first player the whole brown group
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            for index in [1u8, 3] {
+                if let TileType::Property(prop) = &mut game_state.game.board[index as usize] {
+                    prop.owner = Some(0);
+                }
+                game_state.game.players[0].properties.push(index);
+            }
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        }
+
+        // Test case 1: Only the owner can mortgage
+        {
+            let result = process_mortgage(&program_id, other_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPropertyOwner.into());
+        }
+
+        // Test case 2: Mortgage pays half the cost
+        {
+            process_mortgage(&program_id, owner_accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players[0].cash, 1530);
+            match &game_state.game.board[1] {
+                TileType::Property(prop) => assert!(prop.mortgaged),
+                _ => panic!("Expected property"),
+            }
+
+            let result = process_mortgage(&program_id, owner_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::PropertyMortgaged.into());
+        }
+
+        // Test case 3: Cannot build on a color group with a mortgage
+        {
+            let result = process_build_house(&program_id, owner_accounts, 3);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+
+        // Test case 4: Mortgaged property collects no rent
+        {
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.current_player = 1;
+                game_state.game.players[1].position = 1;
+                land_on_tile(&mut game_state.game, 1).unwrap();
+                update_turn_phase(&mut game_state.game);
+                assert_eq!(game_state.game.pending_action, None);
+                assert_eq!(game_state.game.turn_phase, TurnPhase::PostRoll);
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            let accounts = &[game_account.clone(), other_account.clone(), owner_account.clone()];
+            let result = process_pay_rent(&program_id, accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players[1].cash, 1500);
+        }
+
+        // Test case 5: Unmortgage charges 10% interest
+        {
+            process_unmortgage(&program_id, owner_accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players[0].cash, 1497);
+            match &game_state.game.board[1] {
+                TileType::Property(prop) => assert!(!prop.mortgaged),
+                _ => panic!("Expected property"),
+            }
+
+            let result = process_unmortgage(&program_id, owner_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::PropertyNotMortgaged.into());
+        }
+    }
+
+    #[test]
+    fn test_build_and_sell_houses() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+        let other_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut owner_lamports = 0;
+        let mut other_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut owner_data = vec![0; 32];
+        let mut other_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let owner_account = AccountInfo::new(
+            &owner_key,
+            true,
+            true,
+            &mut owner_lamports,
+            &mut owner_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let other_account = AccountInfo::new(
+            &other_key,
+            true,
+            true,
+            &mut other_lamports,
+            &mut other_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let owner_accounts = &[game_account.clone(), owner_account.clone()];
+        let other_accounts = &[game_account.clone(), other_account.clone()];
+        process_create_game(&program_id, owner_accounts, String::from("Player 1"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, other_accounts, String::from("Player 2")).unwrap();
+        process_start_game(&program_id, owner_accounts).unwrap();
+
+        // Give the first player the whole brown group
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            for index in [1u8, 3] {
+                if let TileType::Property(prop) = &mut game_state.game.board[index as usize] {
+                    prop.owner = Some(0);
+                }
+                game_state.game.players[0].properties.push(index);
+            }
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        }
+
+        let houses_at = |game_state: &GameAccount, index: usize| match &game_state.game.board[index] {
+            TileType::Property(prop) => prop.houses,
+            _ => panic!("Expected property"),
+        };
+        let set_houses = |game_account: &AccountInfo, houses: u8, houses_remaining: u8, hotels_remaining: u8| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            for index in [1, 3] {
+                if let TileType::Property(prop) = &mut game_state.game.board[index] {
+                    prop.houses = houses;
+                }
+            }
+            game_state.game.houses_remaining = houses_remaining;
+            game_state.game.hotels_remaining = hotels_remaining;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+
+        // Test case 1: Building takes a house from the bank
+        {
+            process_build_house(&program_id, owner_accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players[0].cash, 1450);
+            assert_eq!(houses_at(&game_state, 1), 1);
+            assert_eq!(game_state.game.houses_remaining, BANK_HOUSES - 1);
+        }
+
+        // Test case 2: Houses must be built evenly across the group
+        {
+            let result = process_build_house(&program_id, owner_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+            process_build_house(&program_id, owner_accounts, 3).unwrap();
+        }
+
+        // Test case 3: No houses left in the bank
+        {
+            set_houses(&game_account, 2, 0, BANK_HOTELS);
+            let result = process_build_house(&program_id, owner_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::HousingShortage.into());
+        }
+
+        // Test case 4: A hotel needs a hotel from the bank and returns four houses
+        {
+            set_houses(&game_account, 4, 0, 0);
+            let result = process_build_house(&program_id, owner_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::HousingShortage.into());
+
+            set_houses(&game_account, 4, 0, BANK_HOTELS);
+            process_build_house(&program_id, owner_accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(houses_at(&game_state, 1), 5);
+            assert_eq!(game_state.game.houses_remaining, 4);
+            assert_eq!(game_state.game.hotels_remaining, BANK_HOTELS - 1);
+            assert_eq!(game_state.game.players[0].cash, 1350);
+        }
+
+        // Test case 5: Only the owner can sell, and the hotel must come down first
+        {
+            let result = process_sell_house(&program_id, other_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPropertyOwner.into());
+
+            let result = process_sell_house(&program_id, owner_accounts, 3);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+
+        // Test case 6: Breaking a hotel needs four houses in the bank
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.houses_remaining = 3;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            let result = process_sell_house(&program_id, owner_accounts, 1);
+            assert_eq!(result.unwrap_err(), MonopolyError::HousingShortage.into());
+
+            game_state.game.houses_remaining = 4;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            process_sell_house(&program_id, owner_accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(houses_at(&game_state, 1), 4);
+            assert_eq!(game_state.game.houses_remaining, 0);
+            assert_eq!(game_state.game.hotels_remaining, BANK_HOTELS);
+            assert_eq!(game_state.game.players[0].cash, 1375);
+        }
+
+        // Test case 7: Selling a house refunds half its cost, even out of turn
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.current_player = 1;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            process_sell_house(&program_id, owner_accounts, 3).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(houses_at(&game_state, 3), 3);
+            assert_eq!(game_state.game.houses_remaining, 1);
+            assert_eq!(game_state.game.players[0].cash, 1400);
+        }
+    }
+
+    #[test]
+    fn test_trade_flow() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let trade_key = Pubkey::new_unique();
+        let alice_key = Pubkey::new_unique();
+        let bob_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut trade_lamports = 0;
+        let mut alice_lamports = 0;
+        let mut bob_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut trade_data = vec![0; TradeOffer::LEN];
+        let mut alice_data = vec![0; 32];
+        let mut bob_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let trade_account = AccountInfo::new(
+            &trade_key,
+            false,
+            true,
+            &mut trade_lamports,
+            &mut trade_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let alice_account = AccountInfo::new(
+            &alice_key,
+            true,
+            true,
+            &mut alice_lamports,
+            &mut alice_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let bob_account = AccountInfo::new(
+            &bob_key,
+            true,
+            true,
+            &mut bob_lamports,
+            &mut bob_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let lobby_accounts = &[game_account.clone(), alice_account.clone()];
+        process_create_game(&program_id, lobby_accounts, String::from("Alice"), 2, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), bob_account.clone()], String::from("Bob")).unwrap();
+        process_start_game(&program_id, lobby_accounts).unwrap();
+
+        // Alice owns property 1, Bob holds a Get Out of Jail card
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            if let TileType::Property(prop) = &mut game_state.game.board[1] {
+                prop.owner = Some(0);
+            }
+            game_state.game.players[0].properties.push(1);
+            game_state.game.players[1].get_out_of_jail_cards = 1;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        }
+
+        let alice_accounts = &[game_account.clone(), trade_account.clone(), alice_account.clone()];
+        let bob_accounts = &[game_account.clone(), trade_account.clone(), bob_account.clone()];
+        let offered = TradeTerms { properties: vec![1], cash: 0, jail_cards: 0 };
+        let requested = TradeTerms { properties: vec![], cash: 100, jail_cards: 1 };
+
+        // Test case 1: Cannot offer what you don't hold
+        {
+            let bad_offer = TradeTerms { properties: vec![3], cash: 0, jail_cards: 0 };
+            let result = process_propose_trade(&program_id, alice_accounts, bob_key, bad_offer, requested.clone());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidTrade.into());
+
+            let result = process_propose_trade(&program_id, alice_accounts, alice_key, offered.clone(), requested.clone());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidTrade.into());
+
+            // More properties than the offer account holds
+            let oversized = TradeTerms { properties: vec![1; MAX_TRADE_PROPERTIES + 1], cash: 0, jail_cards: 0 };
+            let result = process_propose_trade(&program_id, alice_accounts, bob_key, offered.clone(), oversized);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidTrade.into());
+        }
+
+        // Test case 2: Propose, then reject and cancel
+        {
+            process_propose_trade(&program_id, alice_accounts, bob_key, offered.clone(), requested.clone()).unwrap();
+            let result = process_propose_trade(&program_id, alice_accounts, bob_key, offered.clone(), requested.clone());
+            assert_eq!(result.unwrap_err(), ProgramError::AccountAlreadyInitialized);
+
+            let result = process_reject_trade(&program_id, alice_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotTradeParty.into());
+            process_reject_trade(&program_id, bob_accounts).unwrap();
+            assert!(is_blank_account(&trade_account.data.borrow()));
+
+            process_propose_trade(&program_id, alice_accounts, bob_key, offered.clone(), requested.clone()).unwrap();
+            let result = process_cancel_trade(&program_id, bob_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotTradeParty.into());
+            process_cancel_trade(&program_id, alice_accounts).unwrap();
+        }
+
+        // Test case 3: Offer goes stale when holdings change
+        {
+            process_propose_trade(&program_id, alice_accounts, bob_key, offered.clone(), requested.clone()).unwrap();
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[1].get_out_of_jail_cards = 0;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            let result = process_accept_trade(&program_id, bob_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::StaleTradeOffer.into());
+        }
+
+        // Test case 4: Accepting swaps both sides
+        {
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[1].get_out_of_jail_cards = 1;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            let result = process_accept_trade(&program_id, alice_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotTradeParty.into());
+
+            process_accept_trade(&program_id, bob_accounts).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            let alice = &game_state.game.players[0];
+            let bob = &game_state.game.players[1];
+            assert_eq!(alice.cash, 1600);
+            assert_eq!(alice.get_out_of_jail_cards, 1);
+            assert!(alice.properties.is_empty());
+            assert_eq!(bob.cash, 1400);
+            assert_eq!(bob.get_out_of_jail_cards, 0);
+            assert_eq!(bob.properties, vec![1]);
+            match &game_state.game.board[1] {
+                TileType::Property(prop) => assert_eq!(prop.owner, Some(1)),
+                _ => panic!("Expected property"),
+            }
+            assert!(is_blank_account(&trade_account.data.borrow()));
+        }
+    }
+
+    #[test]
+    fn test_is_player_bankrupt() {
+        // Test case 1: Player with cash and properties is not bankrupt
+        let mut player = Player {
+            name: String::from("Rich Player"),
+            wallet: Pubkey::default(),
+            delegate: None,
+            cash: 500,
+            position: 0,
+            jail_turns: 0,
+            properties: vec![1, 2, 3],
+            get_out_of_jail_cards: 0,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: None,
+            dice_secret: None,
+        };
+        assert!(!is_player_bankrupt(&player));
+
+        // Test case 2: Player with no cash and no properties is still in the game
+        player.cash = 0;
+        player.properties.clear();
+        assert!(!is_player_bankrupt(&player));
+
+        // Test case 3: Player who declared bankruptcy is out
+        player.eliminated = true;
+        assert!(is_player_bankrupt(&player));
+    }
+
+    #[test]
+    fn test_bankruptcy_flow() {
+        use crate::account::CardDeck;
+        use solana_program::sysvar::clock;
+
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let alice_key = Pubkey::new_unique();
+        let bob_key = Pubkey::new_unique();
+        let carol_key = Pubkey::new_unique();
+        let clock_key = clock::ID;
+        let deck_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut alice_lamports = 0;
+        let mut bob_lamports = 0;
+        let mut carol_lamports = 0;
+        let mut clock_lamports = 0;
+        let mut deck_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut alice_data = vec![0; 32];
+        let mut bob_data = vec![0; 32];
+        let mut carol_data = vec![0; 32];
+        let mut clock_data = bincode::serialize(&Clock::default()).unwrap();
+        let mut deck_data = vec![0; CardDeck::LEN];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let alice_account = AccountInfo::new(
+            &alice_key,
+            true,
+            true,
+            &mut alice_lamports,
+            &mut alice_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let bob_account = AccountInfo::new(
+            &bob_key,
+            true,
+            true,
+            &mut bob_lamports,
+            &mut bob_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let carol_account = AccountInfo::new(
+            &carol_key,
+            true,
+            true,
+            &mut carol_lamports,
+            &mut carol_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let clock_account = AccountInfo::new(
+            &clock_key,
+            false,
+            false,
+            &mut clock_lamports,
+            &mut clock_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let deck_account = AccountInfo::new(
+            &deck_key,
+            false,
+            true,
+            &mut deck_lamports,
+            &mut deck_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let alice_accounts = &[game_account.clone(), alice_account.clone()];
+        let bob_accounts = &[game_account.clone(), bob_account.clone()];
+        process_create_game(&program_id, alice_accounts, String::from("Alice"), 3, RandomnessKind::Seeded { seed: 0 }, GameRules::default()).unwrap();
+        process_join_game(&program_id, bob_accounts, String::from("Bob")).unwrap();
+        process_join_game(&program_id, &[game_account.clone(), carol_account.clone()], String::from("Carol")).unwrap();
+        process_start_game(&program_id, alice_accounts).unwrap();
+        // Neither player holds a Community Chest card, so the game account fills that slot
+        let alice_bankruptcy_accounts = &[game_account.clone(), alice_account.clone(), game_account.clone(), deck_account.clone()];
+        let bob_bankruptcy_accounts = &[game_account.clone(), bob_account.clone(), game_account.clone(), deck_account.clone()];
+
+        // Bob owns property 1 with a house; Alice owns property 3 with a hotel and is nearly broke
+        {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            if let TileType::Property(prop) = &mut game_state.game.board[1] {
+                prop.owner = Some(1);
+                prop.houses = 1;
+            }
+            if let TileType::Property(prop) = &mut game_state.game.board[3] {
+                prop.owner = Some(0);
+                prop.houses = 5;
+            }
+            game_state.game.players[1].properties.push(1);
+            game_state.game.players[0].properties.push(3);
+            game_state.game.players[0].cash = 5;
+            game_state.game.players[0].get_out_of_jail_cards = 1;
+            game_state.game.players[0].chance_jail_card = true;
+            game_state.game.chance_deck = Some(deck_key);
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            // Alice's jail card is out of the Chance pile
+            CardDeck {
+                is_initialized: true,
+                cards: create_chance_cards(),
+                game: game_key,
+                deck_type: DeckType::Chance,
+                current_card_index: 0,
+                jail_card_held: true,
+            }.pack_into_slice(&mut deck_account.data.borrow_mut());
+        }
+        let land_on_bobs_property = || {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.pending_action = Some(PendingAction::PayRent { property_index: 1, owner: 1, card_rent: false });
+            game_state.game.turn_phase = TurnPhase::ResolvingTile;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+
+        // Test case 1: Unpaid rent becomes a debt that blocks play
+        {
+            land_on_bobs_property();
+            let accounts = &[game_account.clone(), alice_account.clone(), bob_account.clone()];
+            process_pay_rent(&program_id, accounts, 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.players[0].cash, 5);
+            assert_eq!(game_state.game.debt, Some(Debt { debtor: 0, creditor: Some(1), amount: 10 }));
+
+            let result = process_next_turn(&program_id, alice_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::DebtOutstanding.into());
+
+            let result = process_pay_debt(&program_id, alice_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientBalance.into());
+
+            let result = process_declare_bankruptcy(&program_id, bob_bankruptcy_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotDebtor.into());
+        }
+
+        // Test case 2: Raising funds and paying clears the debt
+        {
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[0].cash = 15;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            process_pay_debt(&program_id, alice_accounts).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert!(game_state.game.debt.is_none());
+            assert_eq!(game_state.game.players[0].cash, 5);
+            assert_eq!(game_state.game.players[1].cash, 1510);
+
+            let result = process_pay_debt(&program_id, alice_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NoOutstandingDebt.into());
+        }
+
+        // Test case 3: Bankruptcy hands everything to the creditor
+        {
+            land_on_bobs_property();
+            let accounts = &[game_account.clone(), alice_account.clone(), bob_account.clone()];
+            process_pay_rent(&program_id, accounts, 1).unwrap();
+            process_declare_bankruptcy(&program_id, alice_bankruptcy_accounts).unwrap();
+
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            let alice = &game_state.game.players[0];
+            let bob = &game_state.game.players[1];
+            assert!(alice.eliminated);
+            assert_eq!(alice.cash, 0);
+            assert!(alice.properties.is_empty());
+            assert_eq!(bob.cash, 1510 + 5 + 125); // cash plus the hotel and its four houses at SellHouse prices
+            assert_eq!(bob.properties, vec![1, 3]);
+            assert_eq!(bob.get_out_of_jail_cards, 1);
+            assert!(bob.chance_jail_card);
+            assert!(!alice.chance_jail_card);
+            match &game_state.game.board[3] {
+                TileType::Property(prop) => {
+                    assert_eq!(prop.owner, Some(1));
+                    assert_eq!(prop.houses, 0);
+                },
+                _ => panic!("Expected property"),
+            }
+            assert!(game_state.game.debt.is_none());
+            assert!(game_state.game.auction_queue.is_empty());
+            assert_eq!(game_state.game.hotels_remaining, BANK_HOTELS + 1);
+            assert_eq!(game_state.game.current_player, 1);
+        }
+
+        // Test case 4: Bankruptcy to the bank returns properties unowned
+        {
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[1].cash = 0;
+                if let TileType::Property(prop) = &mut game_state.game.board[3] {
+                    prop.mortgaged = true;
+                }
+                game_state.game.debt = Some(Debt { debtor: 1, creditor: None, amount: 200 });
+                game_state.game.turn_phase = TurnPhase::AwaitingDebtResolution;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            // The jail card has to go back to the game's own Chance deck
+            let wrong_deck_accounts = &[game_account.clone(), bob_account.clone(), game_account.clone(), game_account.clone()];
+            let result = process_declare_bankruptcy(&program_id, wrong_deck_accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidCardDeck.into());
+
+            process_declare_bankruptcy(&program_id, bob_bankruptcy_accounts).unwrap();
+
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert!(game_state.game.players[1].eliminated);
+            assert_eq!(game_state.game.players[1].get_out_of_jail_cards, 0);
+            assert!(!game_state.game.players[1].chance_jail_card);
+            let card_deck = CardDeck::unpack_from_slice(&deck_account.data.borrow()).unwrap();
+            assert!(!card_deck.jail_card_held);
+            for index in [1, 3] {
+                match &game_state.game.board[index] {
+                    TileType::Property(prop) => {
+                        assert_eq!(prop.owner, None);
+                        assert!(!prop.mortgaged);
+                    },
+                    _ => panic!("Expected property"),
+                }
+            }
+            assert_eq!(game_state.game.current_player, 2);
+            assert_eq!(game_state.game.auction_queue, vec![1, 3]);
+        }
+
+        // Test case 5: The next player can auction what the bank took back, but nothing else
+        {
+            let carol_accounts = &[game_account.clone(), carol_account.clone(), clock_account.clone()];
+            let result = process_start_auction(&program_id, carol_accounts, 6);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+
+            process_start_auction(&program_id, carol_accounts, 3).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.auction.map(|a| a.property_index), Some(3));
+            assert_eq!(game_state.game.auction_queue, vec![1, 3]);
+        }
+
+        // Test case 6: An auction nobody bid in leaves the property queued for another
+        {
+            let clock = Clock { slot: AUCTION_DURATION_SLOTS + 1, ..Clock::default() };
+            clock_account.data.borrow_mut().copy_from_slice(&bincode::serialize(&clock).unwrap());
+            let carol_accounts = &[game_account.clone(), carol_account.clone(), clock_account.clone()];
+            process_settle_auction(&program_id, carol_accounts).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert!(game_state.game.auction.is_none());
+            assert_eq!(game_state.game.auction_queue, vec![1, 3]);
+        }
+
+        // Test case 7: Buying a queued property by landing on it takes it off the queue
+        {
+            {
+                let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+                game_state.game.players[2].position = 1;
+                game_state.game.pending_action = Some(PendingAction::BuyProperty { property_index: 1 });
+                game_state.game.turn_phase = TurnPhase::ResolvingTile;
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            }
+            process_buy_property(&program_id, &[game_account.clone(), carol_account.clone()], 1).unwrap();
+            let game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_state.game.board[1].as_property().unwrap().owner, Some(2));
+            assert_eq!(game_state.game.auction_queue, vec![3]);
+        }
+    }
+
+    #[test]
+    fn test_process_move_player() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Initialize game state
+        let mut game = Game {
+            board: create_board(),
+            players: vec![
+                Player {
+                    name: String::from("Player 1"),
+                    wallet: player_key,
+                    delegate: None,
+                    cash: 1500,
+                    position: 0,
+                    jail_turns: 0,
+                    properties: vec![],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+                Player {
+                    name: String::from("Player 2"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
+                    cash: 1500,
+                    position: 0,
+                    jail_turns: 0,
+                    properties: vec![],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+            ],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules { free_parking_jackpot: true, ..GameRules::default() },
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+
+        let game_state = GameAccount {
+            is_initialized: true,
+            game: game.clone(),
+        };
+        game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+        // Test case 1: Normal movement with settled dice
+        {
+            game.rolled_dice = Some((2, 4));
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_move_player(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].position, 6);
+            assert_eq!(updated_game.game.players[0].cash, 1500); // No change in cash
+            assert_eq!(updated_game.game.last_dice, (2, 4));
+            assert!(!updated_game.game.last_doubles);
+            assert_eq!(updated_game.game.turn_phase, TurnPhase::ResolvingTile);
+            assert_eq!(updated_game.game.rolled_dice, None);
+        }
+
+        // Test case 2: Passing GO (collect $200)
+        {
+            game.players[0].position = 39; // Last space before GO
+            game.rolled_dice = Some((1, 2));
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_move_player(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].position, 2); // Wrapped around to position 2
+            assert_eq!(updated_game.game.players[0].cash, 1700); // Collected $200 for passing GO
+        }
+
+        // Test case 3: Landing on Income Tax
+        {
+            game.players[0].position = 0;
+            game.players[0].cash = 1500;
+            game.rolled_dice = Some((1, 3)); // Land on Income Tax
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_move_player(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].position, 4);
+            assert_eq!(updated_game.game.players[0].cash, 1300); // Paid $200 income tax
+            assert_eq!(updated_game.game.free_parking, 200); // Tax goes to Free Parking
+        }
+
+        // Test case 4: Landing on Luxury Tax with doubles earns another roll
+        {
+            game.players[0].position = 36;
+            game.players[0].cash = 1500;
+            game.free_parking = 0;
+            game.rolled_dice = Some((1, 1)); // Land on Luxury Tax
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_move_player(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].position, 38);
+            assert_eq!(updated_game.game.players[0].cash, 1400); // Paid $100 luxury tax
+            assert_eq!(updated_game.game.free_parking, 100); // Tax goes to Free Parking
+            assert!(updated_game.game.last_doubles);
+            assert_eq!(updated_game.game.turn_phase, TurnPhase::AwaitingRoll);
+            assert_eq!(updated_game.game.doubles_rolled, 1);
+        }
+
+        // Test case 5: Missing player signature
+        {
+            let mut unsigned_player_account = player_account.clone();
+            unsigned_player_account.is_signer = false;
+
+            let accounts = &[game_account.clone(), unsigned_player_account];
+            let result = process_move_player(&program_id, accounts);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test case 6: Without a settled round only a seeded game rolls on the spot
+        {
+            game.players[0].position = 0;
+            game.rolled_dice = None;
+            game.doubles_rolled = 0;
+            for randomness in [RandomnessKind::SlotHash, RandomnessKind::Oracle { oracle: Pubkey::new_unique() }] {
+                let game_state = GameAccount {
+                    is_initialized: true,
+                    game: Game { randomness, ..game.clone() },
+                };
+                game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+                let accounts = &[game_account.clone(), player_account.clone(), player_account.clone()];
+                let result = process_move_player(&program_id, accounts);
+                assert_eq!(result.unwrap_err(), MonopolyError::InvalidDicePhase.into());
+            }
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_move_player(&program_id, accounts).unwrap();
+
+            let (die1, die2) = roll_dice(&[], &SeededRandomness { seed: 0 }, 0);
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.last_dice, (die1, die2));
+            assert_eq!(updated_game.game.last_roll, die1 + die2);
+            assert_eq!(updated_game.game.players[0].position, die1 + die2);
+            assert_eq!(updated_game.game.randomness_nonce, 1);
+        }
+
+        // Test case 7: A roll without doubles cannot be repeated this turn
+        {
+            game.rolled_dice = Some((1, 3));
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_move_player(&program_id, accounts).unwrap();
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.turn_phase, TurnPhase::PostRoll);
+            let result = process_move_player(&program_id, accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+        }
+    }
+
+    #[test]
+    fn test_process_buy_property() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Initialize game state with a player on a property space
+        let mut game = Game {
+            board: create_board(),
+            players: vec![
+                Player {
+                    name: String::from("Player 1"),
+                    wallet: player_key,
+                    delegate: None,
+                    cash: 1500,
+                    position: 1, // Mediterranean Avenue
+                    jail_turns: 0,
+                    properties: vec![],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+                Player {
+                    name: String::from("Player 2"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
+                    cash: 1500,
+                    position: 0,
+                    jail_turns: 0,
+                    properties: vec![],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+            ],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::ResolvingTile,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+
+        let game_state = GameAccount {
+            is_initialized: true,
+            game: game.clone(),
+        };
+        game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+        // Test case 1: Successful property purchase
+        {
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_buy_property(&program_id, accounts, 1).unwrap(); // Mediterranean Avenue
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].cash, 1440); // 1500 - 60 (Mediterranean Ave cost)
+            assert_eq!(updated_game.game.players[0].properties.len(), 1);
+            assert_eq!(updated_game.game.players[0].properties[0], 1); // Mediterranean Ave index
+        }
+
+        // Test case 2: Insufficient funds
+        {
+            game.players[0].cash = 50; // Less than property cost
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let result = process_buy_property(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientBalance.into());
+        }
+
+        // Test case 3: Property already owned
+        {
+            game.players[0].cash = 1500;
+            game.players[0].properties = vec![1]; // Already owns Mediterranean Ave
+            if let TileType::Property(property) = &mut game.board[1] {
+                property.owner = Some(0);
+            }
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let result = process_buy_property(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::PropertyAlreadyOwned.into());
+        }
+
+        // Test case 4: Not on a property space
+        {
+            game.players[0].position = 0; // GO space
+            game.players[0].properties = vec![];
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let result = process_buy_property(&program_id, accounts, 0);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+
+        // Test case 5: Missing player signature
+        {
+            game.players[0].position = 1; // Back to Mediterranean Ave
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+
+            let mut unsigned_player_account = player_account.clone();
+            unsigned_player_account.is_signer = false;
+
+            let accounts = &[game_account.clone(), unsigned_player_account];
+            let result = process_buy_property(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test case 6: Not current player's turn
+        {
+            game.current_player = 1; // Switch to player 2's turn
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let result = process_buy_property(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+        }
+    }
+
+    #[test]
+    fn test_process_build_house() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Initialize game state with a player owning a complete color set
+        let mut game = Game {
+            board: create_board(),
+            players: vec![
+                Player {
+                    name: String::from("Player 1"),
+                    wallet: player_key,
+                    delegate: None,
+                    cash: 1500,
+                    position: 1,
+                    jail_turns: 0,
+                    properties: vec![1, 3], // Mediterranean and Baltic (complete brown set)
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+                Player {
+                    name: String::from("Player 2"),
+                    wallet: Pubkey::default(),
+                    delegate: None,
+                    cash: 1500,
+                    position: 0,
+                    jail_turns: 0,
+                    properties: vec![],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+            ],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+        for index in [1, 3] {
+            if let TileType::Property(property) = &mut game.board[index] {
+                property.owner = Some(0);
+            }
+        }
+
+        let game_state = GameAccount {
+            is_initialized: true,
+            game: game.clone(),
+        };
+        game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+        // Test case 1: Successful house build
+        {
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 1; // Mediterranean Avenue
+            process_build_house(&program_id, accounts, property_index).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].cash, 1450); // 1500 - 50 (house cost)
+            assert!(matches!(&updated_game.game.board[property_index as usize], TileType::Property(p) if p.houses == 1));
+        }
+
+        // Test case 2: Insufficient funds
+        {
+            game.players[0].cash = 40; // Less than house cost (50)
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 1;
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientBalance.into());
+        }
+
+        // Test case 3: Property not owned by player
+        {
+            game.players[0].cash = 1500;
+            game.players[0].properties = vec![1]; // Only owns Mediterranean
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 3; // Baltic Avenue (not owned)
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+
+        // Test case 4: Incomplete color set
+        {
+            game.players[0].properties = vec![1]; // Only owns Mediterranean
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 1;
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+
+        // Test case 5: Maximum houses reached
+        {
+            game.players[0].properties = vec![1, 3]; // Complete brown set
+            for index in [1, 3] {
+                if let TileType::Property(ref mut prop) = game.board[index] {
+                    prop.houses = 5; // Hotels on the whole set
+                }
+            }
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 1;
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+
+        // Test case 6: Missing player signature
+        {
+            for index in [1, 3] {
+                if let TileType::Property(ref mut prop) = game.board[index] {
+                    prop.houses = 0;
+                }
+            }
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let mut unsigned_player_account = player_account.clone();
+            unsigned_player_account.is_signer = false;
+
+            let accounts = &[game_account.clone(), unsigned_player_account];
+            let property_index = 1;
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test case 7: Not current player's turn
+        {
+            game.current_player = 1; // Switch to player 2's turn
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 1;
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+        }
+
+        // Test case 8: Invalid property index
+        {
+            game.current_player = 0;
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let property_index = 40; // Invalid index
+            let result = process_build_house(&program_id, accounts, property_index);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidProperty.into());
+        }
+    }
+
+    #[test]
+    fn test_process_pay_rent() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+        let owner_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+        let mut owner_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+        let mut owner_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let owner_account = AccountInfo::new(
+            &owner_key,
+            false,
+            true,
+            &mut owner_lamports,
+            &mut owner_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Initialize game state with property owned by player 2
+        let mut game = Game {
+            board: create_board(),
+            players: vec![
+                Player {
+                    name: String::from("Player 1"),
+                    wallet: player_key,
+                    delegate: None,
+                    cash: 1500,
+                    position: 1, // Mediterranean Avenue
+                    jail_turns: 0,
+                    properties: vec![],
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+                Player {
+                    name: String::from("Player 2"),
+                    wallet: owner_key,
+                    delegate: None,
+                    cash: 1500,
+                    position: 0,
+                    jail_turns: 0,
+                    properties: vec![1], // Owns Mediterranean Avenue
+                    get_out_of_jail_cards: 0,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment: None,
+                    dice_secret: None,
+                },
+            ],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::ResolvingTile,
+            pending_action: Some(PendingAction::PayRent { property_index: 1, owner: 1, card_rent: false }),
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+        if let TileType::Property(property) = &mut game.board[1] {
+            property.owner = Some(1);
+        }
+
+        let game_state = GameAccount {
+            is_initialized: true,
+            game: game.clone(),
+        };
+        game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+        // Test case 1: Successful rent payment
+        {
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            process_pay_rent(&program_id, accounts, 1).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].cash, 1498); // 1500 - 2 (base rent)
+            assert_eq!(updated_game.game.players[1].cash, 1502); // 1500 + 2 (received rent)
+        }
+
+        // Test case 2: Property with houses (increased rent)
+        {
+            if let TileType::Property(ref mut prop) = game.board[1] {
+                prop.houses = 3; // Add 3 houses to increase rent
+            }
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            process_pay_rent(&program_id, accounts, 1).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].cash, 1410); // 1500 - 90 (rent with 3 houses)
+            assert_eq!(updated_game.game.players[1].cash, 1590); // 1500 + 90 (received rent)
+        }
+
+        // Test case 3: Insufficient funds records a debt to the owner
+        {
+            game.players[0].cash = 40; // Less than rent amount
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            process_pay_rent(&program_id, accounts, 1).unwrap();
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.players[0].cash, 40);
+            let debt = updated_game.game.debt.unwrap();
+            assert_eq!(debt.debtor, 0);
+            assert_eq!(debt.creditor, Some(1));
+        }
+
+        // Test case 4: Property not owned
+        {
+            game.players[1].properties = vec![]; // Remove property ownership
+            if let TileType::Property(property) = &mut game.board[1] {
+                property.owner = None;
+            }
+            game.players[0].cash = 1500; // Reset cash
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            let result = process_pay_rent(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::NotRentOwner.into());
+        }
+
+        // Test case 5: Rent is only due for the property the player landed on
+        {
+            game.players[1].properties = vec![1];
+            if let TileType::Property(property) = &mut game.board[1] {
+                property.owner = Some(1);
+            }
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            let result = process_pay_rent(&program_id, accounts, 3);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::NoPendingAction.into());
+        }
+
+        // Test case 6: Missing player signature
+        {
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let mut unsigned_player_account = player_account.clone();
+            unsigned_player_account.is_signer = false;
+
+            let accounts = &[game_account.clone(), unsigned_player_account, owner_account.clone()];
+            let result = process_pay_rent(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test case 7: Not current player's turn
+        {
+            game.current_player = 1; // Switch to player 2's turn
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            let result = process_pay_rent(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+        }
+
+        // Test case 8: No rent pending
+        {
+            game.current_player = 0;
+            game.pending_action = None;
+            let game_state = GameAccount {
+                is_initialized: true,
+                game: game.clone(),
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone(), owner_account.clone()];
+            let result = process_pay_rent(&program_id, accounts, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::NoPendingAction.into());
+        }
+    }
+
+    #[test]
+    fn test_process_next_turn() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Test case 1: Normal turn progression with no bankrupt players
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Player 1"),
+                        wallet: player_key,
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {
+                        name: String::from("Player 2"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::PostRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_next_turn(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.current_player, 1, "Turn should advance to next player");
+        }
+
+        // Test case 2: Skip bankrupt players
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Player 1"),
+                        wallet: player_key,
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {  // Bankrupt player
+                        name: String::from("Player 2"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 0,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: true,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {
+                        name: String::from("Player 3"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::PostRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_next_turn(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.current_player, 2, "Turn should skip bankrupt player");
+        }
+
+        // Test case 3: Wrap around to first non-bankrupt player
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Player 1"),
+                        wallet: player_key,
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {  // Bankrupt player
+                        name: String::from("Player 2"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 0,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: true,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::PostRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_next_turn(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.current_player, 0, "Turn should wrap around to first non-bankrupt player");
+        }
+
+        // Test case 4: Missing player signature
+        {
+            let mut unsigned_player_account = player_account.clone();
+            unsigned_player_account.is_signer = false;
+
+            let accounts = &[game_account.clone(), unsigned_player_account];
+            let result = process_next_turn(&program_id, accounts);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test case 5: Signer does not control the current player
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Player 1"),
+                        wallet: Pubkey::new_unique(),
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {
+                        name: String::from("Player 2"),
+                        wallet: player_key,
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::PostRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let result = process_next_turn(&program_id, accounts);
+            assert_eq!(result.unwrap_err(), MonopolyError::NotPlayerTurn.into());
+        }
+
+        // Test case 6: Delegate may act for the current player
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Player 1"),
+                        wallet: Pubkey::new_unique(),
+                        delegate: Some(player_key),
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {
+                        name: String::from("Player 2"),
+                        wallet: Pubkey::new_unique(),
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::PostRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_next_turn(&program_id, accounts).unwrap();
+
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.current_player, 1, "Delegate should be able to end the turn");
+        }
+    }
+
+    #[test]
+    fn test_process_check_winner() {
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Test case 1: No winner yet (multiple active players)
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Player 1"),
+                        wallet: player_key,
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {
+                        name: String::from("Player 2"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::AwaitingRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_check_winner(&program_id, accounts).unwrap();
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.status, GameStatus::Started, "Should not have a winner yet");
+        }
+
+        // Test case 2: One winner (all other players bankrupt)
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Winner"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 1500,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {  // Bankrupt player
+                        name: String::from("Loser 1"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 0,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: true,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {  // Bankrupt player
+                        name: String::from("Loser 2"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 0,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: true,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::AwaitingRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_check_winner(&program_id, accounts).unwrap();
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.status, GameStatus::Finished, "Should have a winner");
+        }
+
+        // Test case 3: A finished game is not checked again
+        {
+            let accounts = &[game_account.clone(), player_account.clone()];
+            let result = process_check_winner(&program_id, accounts);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+        }
+
+        // Test case 4: All players bankrupt (should never happen in normal gameplay)
+        {
+            let game = Game {
+                board: create_board(),
+                players: vec![
+                    Player {
+                        name: String::from("Bankrupt 1"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 0,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                    Player {
+                        name: String::from("Bankrupt 2"),
+                        wallet: Pubkey::default(),
+                        delegate: None,
+                        cash: 0,
+                        position: 0,
+                        jail_turns: 0,
+                        properties: vec![],
+                        get_out_of_jail_cards: 0,
+                        community_chest_jail_card: false,
+                        chance_jail_card: false,
+                        eliminated: false,
+                        dice_commitment: None,
+                        dice_secret: None,
+                    },
+                ],
+                current_player: 0,
+                free_parking: 0,
+                initialized: true,
+                creator: Pubkey::default(),
+                status: GameStatus::Started,
+                max_players: 8,
+                auction: None,
+                debt: None,
+                last_roll: 0,
+                houses_remaining: BANK_HOUSES,
+                hotels_remaining: BANK_HOTELS,
+                doubles_rolled: 0,
+                dice_round: None,
+                rolled_dice: None,
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                randomness_nonce: 0,
+                last_dice: (0, 0),
+                last_doubles: false,
+                turn_phase: TurnPhase::AwaitingRoll,
+                pending_action: None,
+                rules: GameRules::default(),
+                auction_queue: vec![],
+                randomness_request: None,
+                community_chest_deck: None,
+                chance_deck: None,
+            };
+
+            let game_state = GameAccount {
+                is_initialized: true,
+                game,
+            };
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+
+            let accounts = &[game_account.clone(), player_account.clone()];
+            process_check_winner(&program_id, accounts).unwrap();
+            let updated_game = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(updated_game.game.status, GameStatus::Started, "Should not have a winner when all players are bankrupt");
+        }
+    }
+
+    #[test]
+    fn test_process_draw_card() {
+        use solana_program::clock::Clock;
+        use solana_program::sysvar::clock;
+        use crate::account::CardDeck;
+
+        // Create test accounts
+        let program_id = crate::id();
+        let game_key = Pubkey::new_unique();
+        let player_key = Pubkey::new_unique();
+        let card_deck_key = Pubkey::new_unique();
+        let clock_key = clock::ID;
+
+        let mut game_lamports = 0;
+        let mut player_lamports = 0;
+        let mut card_deck_lamports = 0;
+        let mut clock_lamports = 0;
+
+        let mut game_data = vec![0; GameAccount::LEN];
+        let mut player_data = vec![0; 32];
+        let mut card_deck_data = vec![0; CardDeck::LEN];
+        let mut clock_data = vec![0; std::mem::size_of::<Clock>()];
+
+        let game_account = AccountInfo::new(
+            &game_key,
+            false,
+            true,
+            &mut game_lamports,
+            &mut game_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let player_account = AccountInfo::new(
+            &player_key,
+            true,  // Player must be signer
+            true,
+            &mut player_lamports,
+            &mut player_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let card_deck_account = AccountInfo::new(
+            &card_deck_key,
+            false,
+            true,
+            &mut card_deck_lamports,
+            &mut card_deck_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        let clock_account = AccountInfo::new(
+            &clock_key,
+            false,
+            true,
+            &mut clock_lamports,
+            &mut clock_data,
+            &program_id,
+            false,
+            0,
+        );
+
+        // Initialize game state
+        let game = Game {
+            board: create_board(),
+            players: vec![Player {
+                name: String::from("Test Player"),
+                wallet: player_key,
+                delegate: None,
+                cash: 1500,
+                position: 0,
+                jail_turns: 0,
+                properties: vec![],
+                get_out_of_jail_cards: 0,
+                community_chest_jail_card: false,
+                chance_jail_card: false,
+                eliminated: false,
+                dice_commitment: None,
+                dice_secret: None,
+            }],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        };
+
+        let game_state = GameAccount {
+            is_initialized: true,
+            game,
+        };
+        game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        let land_on = |deck_type: DeckType| {
+            let mut game_state = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_state.game.pending_action = Some(PendingAction::DrawCard { deck_type });
+            game_state.game.turn_phase = TurnPhase::ResolvingTile;
+            game_state.pack_into_slice(&mut game_account.data.borrow_mut());
+        };
+
+        let accounts = &[
+            game_account.clone(),
+            player_account.clone(),
+            card_deck_account.clone(),
+            clock_account.clone(),
+        ];
+        let stack_deck = |cards: Vec<Card>| {
+            let mut card_deck = CardDeck::unpack_from_slice(&card_deck_account.data.borrow()).unwrap();
+            card_deck.current_card_index = 0;
+            card_deck.cards = cards;
+            card_deck.pack_into_slice(&mut card_deck_account.data.borrow_mut());
+        };
+
+        // Test Community Chest card draw
+        {
+            let result = process_draw_card(&program_id, accounts, DeckType::CommunityChest);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidGameState.into());
+
+            land_on(DeckType::CommunityChest);
+            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
+
+            // Verify the deck was set up for this game and the action resolved
+            let card_deck = CardDeck::unpack_from_slice(&card_deck_account.data.borrow()).unwrap();
+            assert!(card_deck.is_initialized);
+            assert_eq!(card_deck.deck_type, DeckType::CommunityChest);
+            assert_eq!(card_deck.current_card_index, 1);
+            let game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            assert_eq!(game_data.game.pending_action, None);
+        }
+
+        // Test a Chance draw cannot use the Community Chest deck
+        {
+            land_on(DeckType::Chance);
+            let result = process_draw_card(&program_id, accounts, DeckType::Chance);
+            assert_eq!(result.unwrap_err(), MonopolyError::InvalidCardDeck.into());
+        }
+
+        // Test error case - missing player signature
+        {
+            let mut unsigned_player_account = player_account.clone();
+            unsigned_player_account.is_signer = false;
+
+            let accounts = &[
+                game_account.clone(),
+                unsigned_player_account,
+                card_deck_account.clone(),
+                clock_account.clone(),
+            ];
+
+            let result = process_draw_card(&program_id, accounts, DeckType::CommunityChest);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
+        }
+
+        // Test insufficient balance for PayMoney card records a debt to the bank
+        {
+            let mut game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_data.game.players[0].cash = 0;
+            game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+            stack_deck(vec![Card::PayMoney(50)]);
+
+            land_on(DeckType::CommunityChest);
+            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
+            let game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            let debt = game_data.game.debt.unwrap();
+            assert_eq!(debt.debtor, 0);
+            assert_eq!(debt.creditor, None);
+        }
+
+        // Test card rotation
+        {
+            let mut game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+            game_data.game.players[0].cash = 1500;
+            game_data.game.debt = None;
+            game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+            stack_deck(vec![Card::CollectMoney(10), Card::CollectMoney(20)]);
+
+            land_on(DeckType::CommunityChest);
+            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
+            let first_effect = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+
+            // Second draw should get the next card in the pile
+            land_on(DeckType::CommunityChest);
+            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
+            let second_effect = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+
+            assert_eq!(first_effect.game.players[0].cash, 1510);
+            assert_eq!(second_effect.game.players[0].cash, 1530, "Card was not rotated properly");
+        }
+    }
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: MonopolyInstruction,
+) -> ProgramResult {
+    match instruction {
+        MonopolyInstruction::CreateGame { player_name, max_players, randomness, rules } => {
+            msg!("Instruction: CreateGame");
+            process_create_game(program_id, accounts, player_name, max_players, randomness, rules)?
+        }
+        MonopolyInstruction::MovePlayer => {
+            msg!("Instruction: MovePlayer");
+            process_move_player(program_id, accounts)?
+        }
+        MonopolyInstruction::BuyProperty { property_index } => {
+            msg!("Instruction: BuyProperty");
+            process_buy_property(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::BuildHouse { property_index } => {
+            msg!("Instruction: BuildHouse");
+            process_build_house(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::PayRent { property_index } => {
+            msg!("Instruction: PayRent");
+            process_pay_rent(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::NextTurn => {
+            msg!("Instruction: NextTurn");
+            process_next_turn(program_id, accounts)?
+        }
+        MonopolyInstruction::CheckWinner => {
+            msg!("Instruction: CheckWinner");
+            process_check_winner(program_id, accounts)?
+        }
+        MonopolyInstruction::DrawCard { deck_type } => {
+            msg!("Instruction: DrawCard");
+            process_draw_card(program_id, accounts, deck_type)?
+        }
+        MonopolyInstruction::SetDelegate { delegate } => {
+            msg!("Instruction: SetDelegate");
+            process_set_delegate(program_id, accounts, delegate)?
+        }
+        MonopolyInstruction::JoinGame { player_name } => {
+            msg!("Instruction: JoinGame");
+            process_join_game(program_id, accounts, player_name)?
+        }
+        MonopolyInstruction::LeaveGame => {
+            msg!("Instruction: LeaveGame");
+            process_leave_game(program_id, accounts)?
+        }
+        MonopolyInstruction::StartGame => {
+            msg!("Instruction: StartGame");
+            process_start_game(program_id, accounts)?
+        }
+        MonopolyInstruction::StartAuction { property_index } => {
+            msg!("Instruction: StartAuction");
+            process_start_auction(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::PlaceBid { amount } => {
+            msg!("Instruction: PlaceBid");
+            process_place_bid(program_id, accounts, amount)?
+        }
+        MonopolyInstruction::SettleAuction => {
+            msg!("Instruction: SettleAuction");
+            process_settle_auction(program_id, accounts)?
+        }
+        MonopolyInstruction::Mortgage { property_index } => {
+            msg!("Instruction: Mortgage");
+            process_mortgage(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::Unmortgage { property_index } => {
+            msg!("Instruction: Unmortgage");
+            process_unmortgage(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::ProposeTrade { counterparty, offered, requested } => {
+            msg!("Instruction: ProposeTrade");
+            process_propose_trade(program_id, accounts, counterparty, offered, requested)?
+        }
+        MonopolyInstruction::AcceptTrade => {
+            msg!("Instruction: AcceptTrade");
+            process_accept_trade(program_id, accounts)?
+        }
+        MonopolyInstruction::RejectTrade => {
+            msg!("Instruction: RejectTrade");
+            process_reject_trade(program_id, accounts)?
+        }
+        MonopolyInstruction::CancelTrade => {
+            msg!("Instruction: CancelTrade");
+            process_cancel_trade(program_id, accounts)?
+        }
+        MonopolyInstruction::PayDebt => {
+            msg!("Instruction: PayDebt");
+            process_pay_debt(program_id, accounts)?
+        }
+        MonopolyInstruction::DeclareBankruptcy => {
+            msg!("Instruction: DeclareBankruptcy");
+            process_declare_bankruptcy(program_id, accounts)?
+        }
+        MonopolyInstruction::SellHouse { property_index } => {
+            msg!("Instruction: SellHouse");
+            process_sell_house(program_id, accounts, property_index)?
+        }
+        MonopolyInstruction::PayBail => {
+            msg!("Instruction: PayBail");
+            process_pay_bail(program_id, accounts)?
+        }
+        MonopolyInstruction::UseJailCard => {
+            msg!("Instruction: UseJailCard");
+            process_use_jail_card(program_id, accounts)?
+        }
+        MonopolyInstruction::CommitDice { commitment } => {
+            msg!("Instruction: CommitDice");
+            process_commit_dice(program_id, accounts, commitment)?
+        }
+        MonopolyInstruction::RevealDice { secret } => {
+            msg!("Instruction: RevealDice");
+            process_reveal_dice(program_id, accounts, secret)?
+        }
+        MonopolyInstruction::SettleDice => {
+            msg!("Instruction: SettleDice");
+            process_settle_dice(program_id, accounts)?
+        }
+        MonopolyInstruction::RequestRandomness => {
+            msg!("Instruction: RequestRandomness");
+            process_request_randomness(program_id, accounts)?
+        }
+        MonopolyInstruction::FulfillRandomness { output } => {
+            msg!("Instruction: FulfillRandomness");
+            process_fulfill_randomness(program_id, accounts, output)?
+        }
+        MonopolyInstruction::MigrateAccount { kind } => {
+            msg!("Instruction: MigrateAccount");
+            process_migrate_account(program_id, accounts, kind)?
+        }
+    }
+    Ok(())
+}
+
+fn process_set_delegate(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    delegate: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let wallet_account = next_account_info(accounts_iter)?;
+
+    if !wallet_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // No phase check: a delegate only changes who may act for the seat, and a player must be
+    // able to revoke one at any point, including mid-turn or with a debt outstanding
+    let mut game = load_game_account_mut(game_account)?;
+
+    // Only the wallet itself may change its delegate
+    let player = game.players_mut().iter_mut()
+        .find(|p| p.wallet == *wallet_account.key)
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+    player.set_delegate(delegate);
+    Ok(())
+}
+
+fn process_draw_card(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    deck_type: DeckType,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let card_deck_account = next_account_info(accounts_iter)?;
+    let randomness_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    check_player_turn(&game_data.game, player_account)?;
+    check_turn_phase(&game_data.game, &[TurnPhase::ResolvingTile])?;
+    if game_data.game.pending_action != Some(PendingAction::DrawCard { deck_type }) {
+        return Err(MonopolyError::NoPendingAction.into());
+    }
+    game_data.game.pending_action = None;
+
+    // The first draw of each type binds its deck account to the game; every later draw must use
+    // it, so a player cannot swap in a fresh deck or pick among several
+    let bound_deck = bound_card_deck(&mut game_data.game, deck_type);
+    match bound_deck {
+        Some(key) if key != card_deck_account.key => return Err(MonopolyError::InvalidCardDeck.into()),
+        Some(_) => {},
+        None => *bound_deck = Some(*card_deck_account.key),
+    }
+    let mut card_deck = match load_card_deck(card_deck_account)? {
+        Some(card_deck) if card_deck.deck_type != deck_type || card_deck.game != *game_account.key => {
+            return Err(MonopolyError::InvalidCardDeck.into());
+        },
+        Some(card_deck) => card_deck,
+        // Set up a blank deck account; it starts exhausted so the first draw shuffles it
+        None => {
+            let cards = match deck_type {
+                DeckType::CommunityChest => create_community_chest(),
+                DeckType::Chance => create_chance_cards(),
+            };
+            CardDeck {
+                is_initialized: true,
+                current_card_index: cards.len() as u8,
+                cards,
+                game: *game_account.key,
+                deck_type,
+                jail_card_held: false,
+            }
+        },
+    };
+
+    // Draw the top card, reshuffling with the game's randomness source once the pile runs out
+    let card = match card_deck.next_card() {
+        Some(card) => card,
+        None => {
+            let source = load_randomness(
+                &game_data.game.randomness,
+                game_data.game.randomness_nonce,
+                game_account.key,
+                game_data.game.randomness_request.as_ref(),
+                randomness_account,
+            )?;
+            card_deck.shuffle(source.as_ref(), game_data.game.randomness_nonce);
+            game_data.game.randomness_nonce += 1;
+            game_data.game.randomness_request = None;
+            card_deck.next_card().ok_or(MonopolyError::InvalidCardDeck)?
+        },
+    };
+
+    // Apply the card to the current player
+    let current_player = game_data.game.current_player as usize;
+    apply_card(&mut game_data.game, current_player, &card, deck_type)?;
+    update_turn_phase(&mut game_data.game);
+
+    // Save state
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    card_deck.pack_into_slice(&mut card_deck_account.data.borrow_mut());
+
+    Ok(())
+}
+
+// The game's slot for the deck account of `deck_type`
+fn bound_card_deck(game: &mut Game, deck_type: DeckType) -> &mut Option<Pubkey> {
+    match deck_type {
+        DeckType::CommunityChest => &mut game.community_chest_deck,
+        DeckType::Chance => &mut game.chance_deck,
+    }
+}
+
+// Whether `player` holds the jail card of the `deck_type` deck
+fn jail_card_from(player: &mut Player, deck_type: DeckType) -> &mut bool {
+    match deck_type {
+        DeckType::CommunityChest => &mut player.community_chest_jail_card,
+        DeckType::Chance => &mut player.chance_jail_card,
+    }
+}
+
+// Carry out the effect of a drawn card from the `deck_type` deck for the player at `current_player`
+fn apply_card(game: &mut Game, current_player: usize, card: &Card, deck_type: DeckType) -> ProgramResult {
+    match card {
+        Card::CollectMoney(amount) => {
+            game.players[current_player].cash += amount;
+            msg!("Player collected {} from card", amount);
+        },
+        Card::PayMoney(amount) => {
+            if charge_player(game, current_player, None, *amount) {
+                pay_to_free_parking(game, *amount)?;
+                msg!("Player paid {}", amount);
+            } else {
+                msg!("Player owes {} to the bank", amount);
+            }
+        },
+        Card::Move(position) => {
+            move_player_to(game, current_player, *position)?;
+            land_on_tile(game, current_player)?;
+            msg!("Player moved to position {}", position);
+        },
+        Card::GetOutOfJail => {
+            game.players[current_player].get_out_of_jail_cards += 1;
+            *jail_card_from(&mut game.players[current_player], deck_type) = true;
+            msg!("Player received Get Out of Jail Free card");
+        },
+        Card::GoToJail => {
+            send_to_jail(&mut game.players[current_player]);
+            msg!("Player was sent to jail");
+        },
+        Card::MoveBack(spaces) => {
+            let player = &mut game.players[current_player];
+            let board_len = game.board.len() as u8;
+            // Moving backwards never passes GO
+            player.position = (player.position + board_len - spaces % board_len) % board_len;
+            msg!("Player moved back to position {}", player.position);
+            land_on_tile(game, current_player)?;
+        },
+        Card::AdvanceToNearest(kind) => {
+            let from = game.players[current_player].position;
+            if let Some(position) = nearest_of_kind(&game.board, from, *kind) {
+                move_player_to(game, current_player, position)?;
+                land_on_tile(game, current_player)?;
+                if let Some(PendingAction::PayRent { card_rent, .. }) = &mut game.pending_action {
+                    *card_rent = true;
+                }
+                msg!("Player advanced to position {}", position);
+            }
+        },
+        Card::PayPerHouseAndHotel { house, hotel } => {
+            let (houses, hotels) = count_buildings(&game.board, current_player as u8);
+            let amount = houses * house + hotels * hotel;
+            if charge_player(game, current_player, None, amount) {
+                pay_to_free_parking(game, amount)?;
+                msg!("Player paid {} for repairs", amount);
+            } else {
+                msg!("Player owes {} to the bank for repairs", amount);
+            }
+        },
+        Card::CollectFromEachPlayer(amount) => {
+            // Only one debt can be outstanding; once a player falls short, anyone
+            // else who cannot cover the amount hands over what they have
+            for payer in 0..game.players.len() {
+                if payer == current_player || game.players[payer].eliminated {
+                    continue;
+                }
+                if game.debt.is_none() {
+                    charge_player(game, payer, Some(current_player), *amount);
+                } else {
+                    let paid = game.players[payer].cash.min(*amount);
+                    game.players[payer].cash -= paid;
+                    game.players[current_player].cash += paid;
+                }
+            }
+            msg!("Player collected {} from each player", amount);
+        },
+        Card::PayEachPlayer(amount) => {
+            // Players are paid in seat order; a shortfall becomes a debt to the first player left unpaid
+            for payee in 0..game.players.len() {
+                if payee == current_player || game.players[payee].eliminated {
+                    continue;
+                }
+                if !charge_player(game, current_player, Some(payee), *amount) {
+                    break;
+                }
+            }
+            msg!("Player paid {} to each player", amount);
+        },
+    }
+    Ok(())
+}
+
+    // Helper function to check if a player is bankrupt
+    fn is_player_bankrupt(player: &Player) -> bool {
+        player.eliminated
+    }
+
+    // Helper function to check that the signer controls the player whose turn it is
+    fn check_player_turn<G: GameState>(game: &G, signer: &AccountInfo) -> ProgramResult {
+        if game.status()? != GameStatus::Started {
+            return Err(MonopolyError::InvalidGameState.into());
+        }
+        // Nothing else happens until an outstanding debt is paid or written off
+        if game.has_debt() {
+            return Err(MonopolyError::DebtOutstanding.into());
+        }
+        let player = game.players().get(game.current_player() as usize)
+            .ok_or(MonopolyError::InvalidGameState)?;
+        if !player.is_controlled_by(signer.key) {
+            return Err(MonopolyError::NotPlayerTurn.into());
+        }
+        Ok(())
+    }
+
+    // Helper function to lock a player in jail
+    fn send_to_jail<P: PlayerState>(player: &mut P) {
+        player.set_position(JAIL_POSITION);
+        player.set_jail_turns(1);
+    }
+
+    // Helper function to hand the turn to the next player still in the game
+    fn advance_turn<G: GameState>(game: &mut G) {
+        game.set_doubles_rolled(0);
+        game.set_rolled_dice(None);
+        game.set_pending_action(None);
+        game.set_turn_phase(TurnPhase::AwaitingRoll);
+
+        // Move to next player, skipping bankrupt ones
+        let seats = game.players().len() as u8;
+        let mut next = (game.current_player() + 1) % seats;
+        while game.players()[next as usize].is_eliminated() {
+            next = (next + 1) % seats;
+        }
+        game.set_current_player(next);
+    }
+
+    // Phases in which property management and trades may happen outside the turn order
+    const ACTIVE_PHASES: &[TurnPhase] = &[
+        TurnPhase::AwaitingRoll,
+        TurnPhase::ResolvingTile,
+        TurnPhase::PostRoll,
+        TurnPhase::AwaitingDebtResolution,
+    ];
+
+    // Helper function to reject instructions that do not fit the current turn phase
+    fn check_turn_phase<G: GameState>(game: &G, allowed: &[TurnPhase]) -> ProgramResult {
+        if !allowed.contains(&game.turn_phase()?) {
+            return Err(MonopolyError::InvalidGameState.into());
+        }
+        Ok(())
+    }
+
+    // Helper function to pick the phase once something from the current roll has been resolved.
+    // Doubles earn another roll unless they came with a trip to jail or a release from it.
+    fn update_turn_phase<G: GameState>(game: &mut G) {
+        let player = &game.players()[game.current_player() as usize];
+        let turn_phase = if game.has_debt() {
+            TurnPhase::AwaitingDebtResolution
+        } else if game.has_pending_action() {
+            TurnPhase::ResolvingTile
+        } else if game.last_doubles() && game.doubles_rolled() > 0 && !player.in_jail() {
+            TurnPhase::AwaitingRoll
+        } else {
+            TurnPhase::PostRoll
+        };
+        game.set_turn_phase(turn_phase);
+    }
+
+    // Helper function to collect a payment, recording a debt when the player cannot cover it.
+    // Returns true if the payment was made.
+    fn charge_player<G: GameState>(game: &mut G, debtor: usize, creditor: Option<usize>, amount: u64) -> bool {
+        let cash = game.players()[debtor].cash();
+        if cash < amount {
+            game.set_debt(Some(Debt {
+                debtor: debtor as u8,
+                creditor: creditor.map(|c| c as u8),
+                amount,
+            }));
+            return false;
+        }
+
+        game.players_mut()[debtor].set_cash(cash - amount);
+        if let Some(creditor) = creditor {
+            let creditor = &mut game.players_mut()[creditor];
+            creditor.set_cash(creditor.cash() + amount);
+        }
+        true
+    }
+
+    fn process_next_turn(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let game_account = next_account_info(accounts_iter)?;
+        let player_account = next_account_info(accounts_iter)?;
+
+        if !player_account.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mut game = load_game_account_mut(game_account)?;
+        check_player_turn(&*game, player_account)?;
+        if game.has_auction() {
+            return Err(MonopolyError::AuctionInProgress.into());
+        }
+        // Without auction on decline, an unbought property simply stays with the bank
+        if !game.rules()?.auction_on_decline
+            && matches!(game.pending_action()?, Some(PendingAction::BuyProperty { .. }))
+        {
+            game.set_pending_action(None);
+            update_turn_phase(&mut *game);
+        }
+        if game.has_pending_action() {
+            return Err(MonopolyError::PendingActionUnresolved.into());
+        }
+        check_turn_phase(&*game, &[TurnPhase::PostRoll])?;
+
+        advance_turn(&mut *game);
+        Ok(())
+    }
+
+    fn process_check_winner(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let game_account = next_account_info(accounts_iter)?;
+
+        let mut game_data = validate_game_account(game_account)?;
+        if game_data.game.status != GameStatus::Started {
+            return Err(MonopolyError::InvalidGameState.into());
+        }
+        
+        // Count non-bankrupt players
+        let active_players: Vec<_> = game_data.game.players.iter()
+            .enumerate()
+            .filter(|(_, player)| !is_player_bankrupt(player))
+            .collect();
+
+        if active_players.len() == 1 {
+            msg!("Game Over! Winner: {}", active_players[0].1.name);
+            game_data.game.status = GameStatus::Finished;
+            game_data.game.turn_phase = TurnPhase::GameOver;
+            game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+            return Ok(());
+        }
+
+        // If more than one player is active, compare total worth
+        let mut max_worth = 0;
+        let mut winner_index = 0;
+        
+        for (i, player) in game_data.game.players.iter().enumerate() {
+            if is_player_bankrupt(player) {
+                continue;
+            }
+
+            let mut total_worth = player.cash;
+            
+            // Add property values
+            for &prop_idx in &player.properties {
+                if let TileType::Property(property) = &game_data.game.board[prop_idx as usize] {
+                    total_worth += property.cost;
+                    total_worth += property.house_cost * property.houses as u64;
+                }
+            }
+
+            if total_worth > max_worth {
+                max_worth = total_worth;
+                winner_index = i;
+            }
+        }
+
+        msg!("Current leader: {} with total worth: {}", 
+            game_data.game.players[winner_index].name,
+            max_worth
+        );
+
+        Ok(())
+    }
+
+fn new_player(name: String, wallet: Pubkey, rules: &GameRules) -> Player {
+    Player {
+        name,
+        wallet,
+        delegate: None,
+        cash: rules.starting_cash,
+        position: 0,
+        jail_turns: 0,
+        properties: vec![],
+        get_out_of_jail_cards: 0,
+        community_chest_jail_card: false,
+        chance_jail_card: false,
+        eliminated: false,
+        dice_commitment: None,
+        dice_secret: None,
+    }
+}
+
+fn process_create_game(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    player_name: String,
+    max_players: u8,
+    randomness: RandomnessKind,
+    rules: GameRules,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let creator = next_account_info(accounts_iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&max_players) {
+        return Err(MonopolyError::InvalidPlayerCount.into());
+    }
+
+    if player_name.len() > MAX_NAME_LEN {
+        return Err(MonopolyError::NameTooLong.into());
+    }
+
+    if [rules.starting_cash, rules.go_salary, rules.jail_fine].iter().any(|&amount| amount > MAX_RULE_AMOUNT) {
+        return Err(MonopolyError::InvalidGameRules.into());
+    }
+
+    let data = game_account.data.borrow();
+    if !is_blank_account(&data) && GameAccount::unpack_unchecked(&data)?.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    drop(data);
+
+    let game_data = GameAccount {
+        is_initialized: true,
+        game: Game {
+            board: create_board(),
+            players: vec![new_player(player_name, *creator.key, &rules)],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: *creator.key,
+            status: GameStatus::Open,
+            max_players,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness,
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules,
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        },
+    };
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_join_game(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    player_name: String,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let wallet_account = next_account_info(accounts_iter)?;
+
+    if !wallet_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    if game_data.game.status != GameStatus::Open {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+
+    if game_data.game.players.iter().any(|p| p.wallet == *wallet_account.key) {
+        return Err(MonopolyError::PlayerAlreadyJoined.into());
+    }
+
+    if game_data.game.players.len() >= game_data.game.max_players as usize {
+        return Err(MonopolyError::InvalidPlayerCount.into());
+    }
+
+    if player_name.len() > MAX_NAME_LEN {
+        return Err(MonopolyError::NameTooLong.into());
+    }
+
+    let player = new_player(player_name, *wallet_account.key, &game_data.game.rules);
+    game_data.game.players.push(player);
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_leave_game(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let wallet_account = next_account_info(accounts_iter)?;
+
+    if !wallet_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    if game_data.game.status != GameStatus::Open {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+
+    let seat = game_data.game.players.iter()
+        .position(|p| p.wallet == *wallet_account.key)
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+    game_data.game.players.remove(seat);
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_start_game(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let creator = next_account_info(accounts_iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    if game_data.game.creator != *creator.key {
+        return Err(MonopolyError::NotGameCreator.into());
+    }
+
+    if game_data.game.status != GameStatus::Open {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+
+    let player_count = game_data.game.players.len();
+    if player_count < MIN_PLAYERS as usize || player_count > MAX_PLAYERS as usize {
+        return Err(MonopolyError::InvalidPlayerCount.into());
+    }
+
+    game_data.game.status = GameStatus::Started;
+    game_data.game.current_player = 0;
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_move_player(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game = load_game_account_mut(game_account)?;
+    check_player_turn(&*game, player_account)?;
+    if game.has_pending_action() {
+        return Err(MonopolyError::PendingActionUnresolved.into());
+    }
+    if game.has_auction() {
+        return Err(MonopolyError::AuctionInProgress.into());
+    }
+    check_turn_phase(&*game, &[TurnPhase::AwaitingRoll])?;
+    if game.dice_round()?.is_some() {
+        return Err(MonopolyError::InvalidDicePhase.into());
+    }
+
+    // Take the dice settled by a commit–reveal round. Rolling here from the source alone would
+    // let the current player grind the outcome, so only a seeded game, which is predictable
+    // anyway, may skip the round.
+    let (die1, die2) = match (game.rolled_dice(), game.randomness()?) {
+        (Some(dice), _) => dice,
+        (None, RandomnessKind::Seeded { seed }) => {
+            let nonce = game.randomness_nonce();
+            game.set_randomness_nonce(nonce + 1);
+            roll_dice(&[], &SeededRandomness { seed }, nonce)
+        },
+        (None, _) => return Err(MonopolyError::InvalidDicePhase.into()),
+    };
+    game.set_rolled_dice(None);
+
+    let rules = game.rules()?;
+    let current_player = game.current_player as usize;
+
+    let total_move = die1 + die2;
+    let doubles = die1 == die2;
+    game.last_roll = total_move;
+    game.last_dice = [die1, die2];
+    game.last_doubles = doubles as u8;
+
+    // Handle jail logic
+    if game.players[current_player].in_jail() {
+        let player = &mut game.players[current_player];
+        if doubles {
+            // Doubles open the cell; the player moves by the roll but does not roll again
+            player.jail_turns = 0;
+            msg!("{} rolled doubles and left jail", player.name());
+        } else if player.jail_turns >= MAX_JAIL_ROLLS {
+            // Out of attempts: the fine is forced and the player moves
+            player.jail_turns = 0;
+            if charge_player(&mut *game, current_player, None, rules.jail_fine) {
+                pay_to_free_parking(&mut *game, rules.jail_fine)?;
+            }
+        } else {
+            player.jail_turns += 1;
+            update_turn_phase(&mut *game);
+            return Ok(());
+        }
+    } else if doubles {
+        game.doubles_rolled += 1;
+        if game.doubles_rolled >= MAX_DOUBLES {
+            // Speeding: three doubles in a row goes straight to jail without moving
+            game.doubles_rolled = 0;
+            send_to_jail(&mut game.players[current_player]);
+            update_turn_phase(&mut *game);
+            return Ok(());
+        }
+    } else {
+        game.doubles_rolled = 0;
+    }
+    // Move player
+    let new_position = (game.players[current_player].position + total_move) % 40;
+    move_player_to(&mut *game, current_player, new_position)?;
+
+    land_on_tile(&mut *game, current_player)?;
+    update_turn_phase(&mut *game);
+    Ok(())
+}
+
+// Resolve the tile the player at `player_index` now stands on. Taxes, Free Parking and
+// Go To Jail settle immediately; anything needing another instruction becomes the pending action.
+fn land_on_tile<G: GameState>(game: &mut G, player_index: usize) -> ProgramResult {
+    let rules = game.rules()?;
+    let position = game.players()[player_index].position();
+    let pending_action = match game.tile(position).ok_or(MonopolyError::InvalidGameState)? {
+        TileView::Special(special) => match special.clone() {
+            SpecialTile::GoToJail => {
+                send_to_jail(&mut game.players_mut()[player_index]);
+                None
+            },
+            SpecialTile::IncomeTax => {
+                if charge_player(game, player_index, None, INCOME_TAX) {
+                    pay_to_free_parking(game, INCOME_TAX)?;
+                }
+                None
+            },
+            SpecialTile::LuxuryTax => {
+                if charge_player(game, player_index, None, LUXURY_TAX) {
+                    pay_to_free_parking(game, LUXURY_TAX)?;
+                }
+                None
+            },
+            SpecialTile::FreeParking if rules.free_parking_jackpot => {
+                let pot = game.free_parking();
+                let player = &mut game.players_mut()[player_index];
+                player.set_cash(player.cash() + pot);
+                msg!("{} collected {} from Free Parking", player.name(), pot);
+                game.set_free_parking(0);
+                None
+            },
+            SpecialTile::CommunityChest => Some(PendingAction::DrawCard { deck_type: DeckType::CommunityChest }),
+            SpecialTile::Chance => Some(PendingAction::DrawCard { deck_type: DeckType::Chance }),
+            // Landing on the jail itself is just visiting
+            SpecialTile::Go | SpecialTile::Jail | SpecialTile::FreeParking => None,
+        },
+        TileView::Property(property) => match property.owner {
+            None => Some(PendingAction::BuyProperty { property_index: position }),
+            // No rent is due on your own or on mortgaged property
+            Some(owner) if owner as usize == player_index || property.mortgaged => None,
+            Some(owner) => Some(owner)
+                .filter(|&owner| !game.players()[owner as usize].is_eliminated())
+                .filter(|&owner| !(rules.no_rent_in_jail && game.players()[owner as usize].in_jail()))
+                .map(|owner| PendingAction::PayRent { property_index: position, owner, card_rent: false }),
+        },
+    };
+    game.set_pending_action(pending_action);
+    Ok(())
+}
+
+// Move the player at `player_index` forward to `new_position`, paying the GO salary if they pass it
+fn move_player_to<G: GameState>(game: &mut G, player_index: usize, new_position: u8) -> ProgramResult {
+    let rules = game.rules()?;
+    let player = &mut game.players_mut()[player_index];
+    if new_position < player.position() {
+        let salary = if new_position == 0 && rules.double_go_salary {
+            rules.go_salary * 2
+        } else {
+            rules.go_salary
         };
+        player.set_cash(player.cash() + salary);
+    }
+    player.set_position(new_position);
+    Ok(())
+}
+
+// Money paid to the bank feeds the Free Parking pot when the jackpot rule is on
+fn pay_to_free_parking<G: GameState>(game: &mut G, amount: u64) -> ProgramResult {
+    if game.rules()?.free_parking_jackpot {
+        game.set_free_parking(game.free_parking() + amount);
+    }
+    Ok(())
+}
+
+fn process_pay_bail(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    check_player_turn(&game_data.game, player_account)?;
+    check_turn_phase(&game_data.game, &[TurnPhase::AwaitingRoll])?;
+    let fine = game_data.game.rules.jail_fine;
+    let player = &mut game_data.game.players[game_data.game.current_player as usize];
+
+    if !player.in_jail() {
+        return Err(MonopolyError::NotInJail.into());
+    }
+    if player.cash < fine {
+        return Err(MonopolyError::InsufficientBalance.into());
+    }
+
+    player.cash -= fine;
+    player.jail_turns = 0;
+    pay_to_free_parking(&mut game_data.game, fine)?;
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_use_jail_card(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let card_deck_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    check_player_turn(&game_data.game, player_account)?;
+    check_turn_phase(&game_data.game, &[TurnPhase::AwaitingRoll])?;
+    let current_player = game_data.game.current_player as usize;
+
+    if !game_data.game.players[current_player].in_jail() {
+        return Err(MonopolyError::NotInJail.into());
+    }
+    if game_data.game.players[current_player].get_out_of_jail_cards == 0 {
+        return Err(MonopolyError::NoJailCard.into());
+    }
+
+    // Only the game's own deck the held card was drawn from can take it back
+    let mut card_deck = validate_card_deck(card_deck_account)?;
+    if !card_deck.is_initialized || card_deck.game != *game_account.key || !card_deck.jail_card_held {
+        return Err(MonopolyError::InvalidCardDeck.into());
+    }
+    if *bound_card_deck(&mut game_data.game, card_deck.deck_type) != Some(*card_deck_account.key) {
+        return Err(MonopolyError::InvalidCardDeck.into());
+    }
+    let player = &mut game_data.game.players[current_player];
+    let from_deck = jail_card_from(player, card_deck.deck_type);
+    if !*from_deck {
+        return Err(MonopolyError::InvalidCardDeck.into());
+    }
+
+    *from_deck = false;
+    player.get_out_of_jail_cards -= 1;
+    player.jail_turns = 0;
+    card_deck.jail_card_held = false;
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    card_deck.pack_into_slice(&mut card_deck_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_buy_property(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    property_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game = load_game_account_mut(game_account)?;
+    check_player_turn(&*game, player_account)?;
+    if game.has_auction() {
+        return Err(MonopolyError::AuctionInProgress.into());
+    }
+    check_turn_phase(&*game, &[TurnPhase::ResolvingTile])?;
+    let current_player = game.current_player as usize;
+    if game.players[current_player].position != property_index {
+        return Err(MonopolyError::InvalidPropertyIndex.into());
+    }
+
+    let price = game.property(property_index).ok_or(MonopolyError::InvalidProperty)?.cost;
+    transfer_property_from_bank(&mut *game, current_player, property_index, price)?;
+
+    if game.pending_action()? == Some(PendingAction::BuyProperty { property_index }) {
+        game.set_pending_action(None);
+    }
+    update_turn_phase(&mut *game);
+    Ok(())
+}
+
+// Sell an unowned property to a player, debiting `price` from their cash
+fn transfer_property_from_bank<G: GameState>(
+    game: &mut G,
+    player_index: usize,
+    property_index: u8,
+    price: u64,
+) -> ProgramResult {
+    match game.property(property_index) {
+        Some(property) if property.owner.is_some() => return Err(MonopolyError::PropertyAlreadyOwned.into()),
+        Some(_) => {},
+        None => return Err(MonopolyError::InvalidProperty.into()),
+    }
+
+    let player = &mut game.players_mut()[player_index];
+    if player.cash() < price {
+        return Err(MonopolyError::InsufficientBalance.into());
+    }
+    player.set_cash(player.cash() - price);
+    player.push_property(property_index)?;
+    game.set_owner(property_index, Some(player_index as u8));
+    // Sold by auction or bought by landing on it, a property the bank took back no longer waits for auction
+    game.remove_from_auction_queue(property_index);
+    Ok(())
+}
+
+fn process_start_auction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    property_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let clock_sysvar = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    check_player_turn(&game_data.game, player_account)?;
+    if game_data.game.auction.is_some() {
+        return Err(MonopolyError::AuctionInProgress.into());
+    }
+
+    // Properties a bankruptcy handed to the bank can be auctioned on any turn;
+    // otherwise only the tile the current player landed on can be. A queued property
+    // stays queued until it sells, so an auction without a winner can be run again.
+    if game_data.game.auction_queue.contains(&property_index) {
+        check_turn_phase(&game_data.game, ACTIVE_PHASES)?;
+    } else {
+        check_turn_phase(&game_data.game, &[TurnPhase::ResolvingTile])?;
+        if !game_data.game.rules.auction_on_decline {
+            return Err(MonopolyError::AuctionDisabled.into());
+        }
+
+        let player = &game_data.game.players[game_data.game.current_player as usize];
+        if player.position != property_index {
+            return Err(MonopolyError::InvalidPropertyIndex.into());
+        }
+    }
+
+    match game_data.game.board.get(property_index as usize) {
+        Some(TileType::Property(property)) => {
+            if property.owner.is_some() {
+                return Err(MonopolyError::PropertyAlreadyOwned.into());
+            }
+        },
+        _ => return Err(MonopolyError::InvalidProperty.into()),
+    }
+
+    let clock = Clock::from_account_info(clock_sysvar)?;
+    if game_data.game.pending_action == Some(PendingAction::BuyProperty { property_index }) {
+        game_data.game.pending_action = None;
+    }
+    game_data.game.auction = Some(Auction {
+        property_index,
+        highest_bid: 0,
+        highest_bidder: None,
+        deadline_slot: clock.slot + AUCTION_DURATION_SLOTS,
+    });
+    update_turn_phase(&mut game_data.game);
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_place_bid(_program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let clock_sysvar = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    if game_data.game.status != GameStatus::Started {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    check_turn_phase(&game_data.game, ACTIVE_PHASES)?;
+
+    let bidder_index = game_data.game.players.iter()
+        .position(|p| p.is_controlled_by(player_account.key))
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+    let bidder = &game_data.game.players[bidder_index];
+
+    let clock = Clock::from_account_info(clock_sysvar)?;
+    let auction = game_data.game.auction.as_mut().ok_or(MonopolyError::NoActiveAuction)?;
+    if clock.slot > auction.deadline_slot {
+        return Err(MonopolyError::AuctionEnded.into());
+    }
+
+    // The opening bid only has to clear the increment; later bids must beat the leader by it
+    if amount < auction.highest_bid + MIN_BID_INCREMENT {
+        return Err(MonopolyError::BidTooLow.into());
+    }
+
+    if bidder.cash < amount {
+        return Err(MonopolyError::InsufficientBalance.into());
+    }
+
+    auction.highest_bid = amount;
+    auction.highest_bidder = Some(bidder_index as u8);
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_settle_auction(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let clock_sysvar = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    check_turn_phase(&game_data.game, ACTIVE_PHASES)?;
+    if !game_data.game.players.iter().any(|p| p.is_controlled_by(player_account.key)) {
+        return Err(MonopolyError::PlayerNotInGame.into());
+    }
+
+    let clock = Clock::from_account_info(clock_sysvar)?;
+    let auction = game_data.game.auction.clone().ok_or(MonopolyError::NoActiveAuction)?;
+    if clock.slot <= auction.deadline_slot {
+        return Err(MonopolyError::AuctionNotEnded.into());
+    }
+
+    game_data.game.auction = None;
+    if let Some(winner) = auction.highest_bidder {
+        // A winner who can no longer cover the bid forfeits; the property stays with the bank
+        let game = &mut game_data.game;
+        if game.players[winner as usize].cash >= auction.highest_bid {
+            transfer_property_from_bank(game, winner as usize, auction.property_index, auction.highest_bid)?;
+        } else {
+            msg!("Winning bidder cannot cover {}, property stays with the bank", auction.highest_bid);
+        }
+    }
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_build_house(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    property_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    check_player_turn(&game_data.game, player_account)?;
+    check_turn_phase(&game_data.game, &[TurnPhase::AwaitingRoll, TurnPhase::PostRoll])?;
+    let current_player = game_data.game.current_player as usize;
+    let player = &mut game_data.game.players[current_player];
+
+    if !can_build_house(property_index, &player.properties, &game_data.game.board) {
+        return Err(MonopolyError::InvalidProperty.into());
+    }
+
+    let property_index_usize = property_index as usize;
+    match &mut game_data.game.board[property_index_usize] {
+        TileType::Property(property) => {
+            if property.owner != Some(current_player as u8) {
+                return Err(MonopolyError::NotPropertyOwner.into());
+            }
+
+            if property.houses >= 5 {
+                return Err(MonopolyError::MaximumHousesReached.into());
+            }
+
+            // The fifth building is a hotel, which hands its four houses back to the bank
+            let building_hotel = property.houses == 4;
+            let available = if building_hotel {
+                game_data.game.hotels_remaining
+            } else {
+                game_data.game.houses_remaining
+            };
+            if available == 0 {
+                return Err(MonopolyError::HousingShortage.into());
+            }
+
+            let cost = if building_hotel { hotel_upgrade_cost(property) } else { property.house_cost };
+            if player.cash < cost {
+                return Err(MonopolyError::InsufficientBalance.into());
+            }
+
+            player.cash -= cost;
+            property.houses += 1;
+            if building_hotel {
+                game_data.game.hotels_remaining -= 1;
+                game_data.game.houses_remaining += 4;
+            } else {
+                game_data.game.houses_remaining -= 1;
+            }
+        },
+        _ => return Err(MonopolyError::InvalidProperty.into()),
+    }
 
-        let game_state = GameAccount {
-            is_initialized: true,
-            game,
-        };
-        game_state.pack_into_slice(&mut game_data);
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
 
-        // Test Community Chest card draw
-        {
-            let accounts = &[
-                game_account.clone(),
-                player_account.clone(),
-                card_deck_account.clone(),
-                clock_account.clone(),
-            ];
+fn process_sell_house(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    property_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-            // Set clock data to control card selection
-            let mut clock = Clock::default();
-            clock.slot = 0; // Will draw first card
-            let clock_bytes = bincode::serialize(&clock).unwrap();
-            clock_data.copy_from_slice(&clock_bytes);
+    let mut game_data = validate_game_account(game_account)?;
+    let player_index = find_property_owner(&game_data.game, player_account, property_index)?;
 
-            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
+    if !can_sell_house(property_index, &game_data.game.board) {
+        return Err(MonopolyError::InvalidProperty.into());
+    }
 
-            // Verify card was drawn and effects applied
-            let game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            assert!(game_data.game.players[0].cash != 1500, "Card effect not applied");
+    let game = &mut game_data.game;
+    if let TileType::Property(property) = &mut game.board[property_index as usize] {
+        // Breaking a hotel back down needs four houses from the bank
+        let refund = building_sell_price(property);
+        if property.houses == 5 {
+            if game.houses_remaining < 4 {
+                return Err(MonopolyError::HousingShortage.into());
+            }
+            game.houses_remaining -= 4;
+            game.hotels_remaining += 1;
+        } else {
+            game.houses_remaining += 1;
         }
 
-        // Test Chance card draw
-        {
-            let accounts = &[
-                game_account.clone(),
-                player_account.clone(),
-                card_deck_account.clone(),
-                clock_account.clone(),
-            ];
+        property.houses -= 1;
+        game.players[player_index].cash += refund;
+    }
 
-            // Set clock data for different card
-            let mut clock = Clock::default();
-            clock.slot = 1; // Will draw second card
-            let clock_bytes = bincode::serialize(&clock).unwrap();
-            clock_data.copy_from_slice(&clock_bytes);
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
 
-            process_draw_card(&program_id, accounts, DeckType::Chance).unwrap();
+fn process_pay_rent(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    property_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let owner_account = next_account_info(accounts_iter)?;
 
-            // Verify card was drawn and effects applied
-            let game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            let player = &game_data.game.players[0];
-            assert!(player.cash != 1500 || player.position != 0 || player.get_out_of_jail_cards != 0,
-                "Card effect not applied");
-        }
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-        // Test error case - missing player signature
-        {
-            let mut unsigned_player_account = player_account.clone();
-            unsigned_player_account.is_signer = false;
+    let mut game = load_game_account_mut(game_account)?;
+    check_player_turn(&*game, player_account)?;
+    check_turn_phase(&*game, &[TurnPhase::ResolvingTile])?;
+    let current_player = game.current_player as usize;
 
-            let accounts = &[
-                game_account.clone(),
-                unsigned_player_account,
-                card_deck_account.clone(),
-                clock_account.clone(),
-            ];
+    // Rent is only due for the property the player landed on
+    let (owner_index, card_rent) = match game.pending_action()? {
+        Some(PendingAction::PayRent { property_index: pending, owner, card_rent }) if pending == property_index => {
+            (owner as usize, card_rent)
+        },
+        _ => return Err(MonopolyError::NoPendingAction.into()),
+    };
 
+    let property = game.property(property_index).ok_or(MonopolyError::InvalidProperty)?;
+    let owner_wallet = property.owner.map(|owner| game.players[owner as usize].wallet);
+    if owner_wallet != Some(*owner_account.key) {
+        return Err(MonopolyError::NotRentOwner.into());
+    }
+    let rent = if card_rent {
+        calculate_card_rent(&*game, property_index, game.last_roll)
+    } else {
+        calculate_tile_rent(&*game, property_index, game.last_roll)
+    };
 
-            let result = process_draw_card(&program_id, accounts, DeckType::CommunityChest);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), ProgramError::MissingRequiredSignature);
-        }
+    // Charge the rent; a shortfall becomes a debt to the owner
+    game.set_pending_action(None);
+    charge_player(&mut *game, current_player, Some(owner_index), rent);
+    update_turn_phase(&mut *game);
+    Ok(())
+}
 
-        // Test error case - insufficient balance for PayMoney card
-        {
-            // Set player's cash to 0
-            let mut game_data = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            game_data.game.players[0].cash = 0;
-            game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+// Find the player controlled by `signer` and check they own the property at `property_index`
+fn find_property_owner(game: &Game, signer: &AccountInfo, property_index: u8) -> Result<usize, ProgramError> {
+    if game.status != GameStatus::Started {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    check_turn_phase(game, ACTIVE_PHASES)?;
 
-            // Set clock to draw PayMoney card
-            let mut clock = Clock::default();
-            clock.slot = 2; // Assume this draws a PayMoney card
-            let clock_bytes = bincode::serialize(&clock).unwrap();
-            clock_data.copy_from_slice(&clock_bytes);
+    let player_index = game.players.iter()
+        .position(|p| p.is_controlled_by(signer.key))
+        .ok_or(MonopolyError::PlayerNotInGame)?;
 
-            let accounts = &[
-                game_account.clone(),
-                player_account.clone(),
-                card_deck_account.clone(),
-                clock_account.clone(),
-            ];
+    match game.board.get(property_index as usize) {
+        Some(TileType::Property(property)) => {
+            if property.owner != Some(player_index as u8) {
+                return Err(MonopolyError::NotPropertyOwner.into());
+            }
+        },
+        _ => return Err(MonopolyError::InvalidProperty.into()),
+    }
 
-            let result = process_draw_card(&program_id, accounts, DeckType::CommunityChest);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), MonopolyError::InsufficientBalance.into());
-        }
+    Ok(player_index)
+}
 
-        // Test card rotation
-        {
-            let accounts = &[
-                game_account.clone(),
-                player_account.clone(),
-                card_deck_account.clone(),
-                clock_account.clone(),
-            ];
+fn process_mortgage(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    property_index: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-            // Draw same card twice
-            let mut clock = Clock::default();
-            clock.slot = 0;
-            let clock_bytes = bincode::serialize(&clock).unwrap();
-            clock_data.copy_from_slice(&clock_bytes);
+    let mut game_data = validate_game_account(game_account)?;
+    let player_index = find_property_owner(&game_data.game, player_account, property_index)?;
 
-            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
-            
-            // Second draw should get different card due to rotation
-            let first_effect = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
-            process_draw_card(&program_id, accounts, DeckType::CommunityChest).unwrap();
-            let second_effect = GameAccount::unpack_from_slice(&game_account.data.borrow()).unwrap();
+    let board = &mut game_data.game.board;
+    let (color, kind) = match &board[property_index as usize] {
+        TileType::Property(property) => {
+            if property.mortgaged {
+                return Err(MonopolyError::PropertyMortgaged.into());
+            }
+            (property.color.clone(), property.kind)
+        },
+        _ => return Err(MonopolyError::InvalidProperty.into()),
+    };
 
-            assert!(first_effect.game.players[0].cash != second_effect.game.players[0].cash,
-                "Card was not rotated properly");
-        }
+    // Buildings on the color group must be sold before mortgaging
+    let has_buildings = kind == TileKind::Street && board.iter().any(|tile| match tile {
+        TileType::Property(prop) => prop.kind == TileKind::Street && prop.color == color && prop.houses > 0,
+        _ => false,
+    });
+    if has_buildings {
+        return Err(MonopolyError::InvalidProperty.into());
+    }
+
+    if let TileType::Property(property) = &mut board[property_index as usize] {
+        property.mortgaged = true;
+        game_data.game.players[player_index].cash += mortgage_value(property.cost);
     }
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
 }
 
-pub fn process_instruction(
-    program_id: &Pubkey,
+fn process_unmortgage(
+    _program_id: &Pubkey,
     accounts: &[AccountInfo],
-    instruction: MonopolyInstruction,
+    property_index: u8,
 ) -> ProgramResult {
-    match instruction {
-        MonopolyInstruction::InitGame { player_names } => {
-            msg!("Instruction: InitGame");
-            process_init_game(program_id, accounts, player_names)?
-        }
-        MonopolyInstruction::MovePlayer => {
-            msg!("Instruction: MovePlayer");
-            process_move_player(program_id, accounts)?
-        }
-        MonopolyInstruction::BuyProperty { property_index } => {
-            msg!("Instruction: BuyProperty");
-            process_buy_property(program_id, accounts, property_index)?
-        }
-        MonopolyInstruction::BuildHouse { property_index } => {
-            msg!("Instruction: BuildHouse");
-            process_build_house(program_id, accounts, property_index)?
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    let player_index = find_property_owner(&game_data.game, player_account, property_index)?;
+    let player = &mut game_data.game.players[player_index];
+
+    if let TileType::Property(property) = &mut game_data.game.board[property_index as usize] {
+        if !property.mortgaged {
+            return Err(MonopolyError::PropertyNotMortgaged.into());
         }
-        MonopolyInstruction::PayRent { property_index } => {
-            msg!("Instruction: PayRent");
-            process_pay_rent(program_id, accounts, property_index)?
+
+        let cost = unmortgage_cost(property.cost);
+        if player.cash < cost {
+            return Err(MonopolyError::InsufficientBalance.into());
         }
-        MonopolyInstruction::NextTurn => {
-            msg!("Instruction: NextTurn");
-            process_next_turn(program_id, accounts)?
+
+        player.cash -= cost;
+        property.mortgaged = false;
+    }
+
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
+
+// Check that a player still holds everything one side of a trade promises
+fn can_give_trade_terms(game: &Game, player_index: usize, terms: &TradeTerms) -> bool {
+    let player = &game.players[player_index];
+    if player.cash < terms.cash || player.get_out_of_jail_cards < terms.jail_cards {
+        return false;
+    }
+
+    terms.properties.iter().enumerate().all(|(i, &index)| {
+        // Each property may only be listed once
+        if terms.properties[..i].contains(&index) || !player.properties.contains(&index) {
+            return false;
         }
-        MonopolyInstruction::CheckWinner => {
-            msg!("Instruction: CheckWinner");
-            process_check_winner(program_id, accounts)?
+        // Buildings must be sold before a property changes hands
+        matches!(
+            game.board.get(index as usize),
+            Some(TileType::Property(property)) if property.owner == Some(player_index as u8) && property.houses == 0
+        )
+    })
+}
+
+// Move one side of a trade from one player to another
+fn give_trade_terms(game: &mut Game, from: usize, to: usize, terms: &TradeTerms) {
+    game.players[from].cash -= terms.cash;
+    game.players[to].cash += terms.cash;
+    move_jail_cards(game, from, to, terms.jail_cards);
+
+    for &index in &terms.properties {
+        if let TileType::Property(property) = &mut game.board[index as usize] {
+            property.owner = Some(to as u8);
         }
-        MonopolyInstruction::DrawCard { deck_type } => {
-            msg!("Instruction: DrawCard");
-            process_draw_card(program_id, accounts, deck_type)?
+        game.players[from].properties.retain(|&p| p != index);
+        game.players[to].properties.push(index);
+    }
+}
+
+// Hand `count` jail cards from `from` to `to`, along with which decks they go back to
+fn move_jail_cards(game: &mut Game, from: usize, to: usize, count: u8) {
+    let mut left = count;
+    for deck_type in [DeckType::CommunityChest, DeckType::Chance] {
+        if left > 0 && *jail_card_from(&mut game.players[from], deck_type) {
+            *jail_card_from(&mut game.players[from], deck_type) = false;
+            *jail_card_from(&mut game.players[to], deck_type) = true;
+            left -= 1;
         }
     }
-    Ok(())
+    game.players[from].get_out_of_jail_cards -= count;
+    game.players[to].get_out_of_jail_cards += count;
 }
 
-fn process_draw_card(
+// Find a trade party by wallet and check that `signer` controls them
+fn find_trade_party(game: &Game, wallet: &Pubkey, signer: &AccountInfo) -> Result<usize, ProgramError> {
+    let index = game.players.iter()
+        .position(|p| p.wallet == *wallet)
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+    if !game.players[index].is_controlled_by(signer.key) {
+        return Err(MonopolyError::NotTradeParty.into());
+    }
+    Ok(index)
+}
+
+// Load a trade offer and make sure it belongs to the given game
+fn load_trade_offer(game_account: &AccountInfo, trade_account: &AccountInfo) -> Result<TradeOffer, ProgramError> {
+    let offer = validate_trade_offer(trade_account)?;
+    if offer.game != *game_account.key {
+        return Err(MonopolyError::InvalidTrade.into());
+    }
+    Ok(offer)
+}
+
+fn close_trade_offer(trade_account: &AccountInfo) {
+    trade_account.data.borrow_mut().fill(0);
+}
+
+fn process_propose_trade(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
-    deck_type: DeckType,
+    counterparty: Pubkey,
+    offered: TradeTerms,
+    requested: TradeTerms,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let game_account = next_account_info(accounts_iter)?;
+    let trade_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let game_data = validate_game_account(game_account)?;
+    let game = &game_data.game;
+    if game.status != GameStatus::Started {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    check_turn_phase(game, ACTIVE_PHASES)?;
+
+    if trade_account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = trade_account.data.borrow();
+    if !is_blank_account(&data) && TradeOffer::unpack_unchecked(&data)?.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    drop(data);
+
+    let proposer_index = game.players.iter()
+        .position(|p| p.is_controlled_by(player_account.key))
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+    let counterparty_index = game.players.iter()
+        .position(|p| p.wallet == counterparty)
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+
+    if proposer_index == counterparty_index || (offered == TradeTerms::default() && requested == TradeTerms::default()) {
+        return Err(MonopolyError::InvalidTrade.into());
+    }
+    // The instruction carries the properties as an unbounded vector; the offer account does not
+    if offered.properties.len() > MAX_TRADE_PROPERTIES || requested.properties.len() > MAX_TRADE_PROPERTIES {
+        return Err(MonopolyError::InvalidTrade.into());
+    }
+
+    if !can_give_trade_terms(game, proposer_index, &offered)
+        || !can_give_trade_terms(game, counterparty_index, &requested)
+    {
+        return Err(MonopolyError::InvalidTrade.into());
+    }
+
+    let offer = TradeOffer {
+        is_initialized: true,
+        game: *game_account.key,
+        proposer: game.players[proposer_index].wallet,
+        counterparty,
+        offered,
+        requested,
+    };
+
+    offer.pack_into_slice(&mut trade_account.data.borrow_mut());
+    Ok(())
+}
+
+fn process_accept_trade(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let trade_account = next_account_info(accounts_iter)?;
     let player_account = next_account_info(accounts_iter)?;
-    let card_deck_account = next_account_info(accounts_iter)?;
-    let clock_sysvar = next_account_info(accounts_iter)?;
 
     if !player_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
     let mut game_data = validate_game_account(game_account)?;
-    let mut card_deck = validate_card_deck(card_deck_account)?;
-    
-    // Initialize deck if needed
-    if !card_deck.is_initialized {
-        card_deck.cards = match deck_type {
-            DeckType::CommunityChest => create_community_chest(),
-            DeckType::Chance => create_chance_cards(),
-        };
-        card_deck.is_initialized = true;
-        card_deck.game = *game_account.key;
-        card_deck.deck_type = deck_type;
+    if game_data.game.status != GameStatus::Started {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    check_turn_phase(&game_data.game, ACTIVE_PHASES)?;
+
+    let offer = load_trade_offer(game_account, trade_account)?;
+    let counterparty_index = find_trade_party(&game_data.game, &offer.counterparty, player_account)?;
+    let proposer_index = game_data.game.players.iter()
+        .position(|p| p.wallet == offer.proposer)
+        .ok_or(MonopolyError::PlayerNotInGame)?;
+
+    // Holdings may have changed since the offer was made
+    if !can_give_trade_terms(&game_data.game, proposer_index, &offer.offered)
+        || !can_give_trade_terms(&game_data.game, counterparty_index, &offer.requested)
+    {
+        return Err(MonopolyError::StaleTradeOffer.into());
     }
 
-    // Get current player
-    let current_player = game_data.game.current_player as usize;
-    let player = &mut game_data.game.players[current_player];
+    give_trade_terms(&mut game_data.game, proposer_index, counterparty_index, &offer.offered);
+    give_trade_terms(&mut game_data.game, counterparty_index, proposer_index, &offer.requested);
 
-    // Draw a card using clock as randomization source
-    let clock = Clock::from_account_info(clock_sysvar)?;
-    let card_index = (clock.slot % card_deck.cards.len() as u64) as usize;
-    let card = &card_deck.cards[card_index];
+    close_trade_offer(trade_account);
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
 
-    // Process card effect
-    match card {
-        Card::CollectMoney(amount) => {
-            player.cash += amount;
-            msg!("Player collected {} from card", amount);
-        },
-        Card::PayMoney(amount) => {
-            if player.cash < *amount {
-                return Err(MonopolyError::InsufficientBalance.into());
-            }
-            player.cash -= amount;
-            game_data.game.free_parking += amount;
-            msg!("Player paid {} to free parking", amount);
-        },
-        Card::Move(position) => {
-            // Pay GO bonus if passing GO
-            if *position < player.position {
-                player.cash += 200;
-            }
-            player.position = *position;
-            msg!("Player moved to position {}", position);
-        },
-        Card::GetOutOfJail => {
-            player.get_out_of_jail_cards += 1;
-            msg!("Player received Get Out of Jail Free card");
-        },
+fn process_reject_trade(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let trade_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Rotate card to bottom of deck
-    let card = card_deck.cards.remove(card_index);
-    card_deck.cards.push(card);
+    // No phase check: closing an offer moves nothing, and offers left open when the game
+    // ends must still be closable
+    let game_data = validate_game_account(game_account)?;
+    let offer = load_trade_offer(game_account, trade_account)?;
+    find_trade_party(&game_data.game, &offer.counterparty, player_account)?;
 
-    // Save state
-    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
-    card_deck.pack_into_slice(&mut card_deck_account.data.borrow_mut());
+    close_trade_offer(trade_account);
+    Ok(())
+}
+
+fn process_cancel_trade(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let trade_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // No phase check, as in `process_reject_trade`
+    let game_data = validate_game_account(game_account)?;
+    let offer = load_trade_offer(game_account, trade_account)?;
+    find_trade_party(&game_data.game, &offer.proposer, player_account)?;
 
+    close_trade_offer(trade_account);
     Ok(())
 }
 
-    // Helper function to check if a player is bankrupt
-    fn is_player_bankrupt(player: &Player) -> bool {
-        player.cash == 0 && player.properties.is_empty()
+// Load the outstanding debt and check that `signer` controls the debtor
+fn find_debt(game: &Game, signer: &AccountInfo) -> Result<Debt, ProgramError> {
+    if game.status != GameStatus::Started {
+        return Err(MonopolyError::InvalidGameState.into());
     }
+    let debt = game.debt.clone().ok_or(MonopolyError::NoOutstandingDebt)?;
+    check_turn_phase(game, &[TurnPhase::AwaitingDebtResolution])?;
+    if !game.players[debt.debtor as usize].is_controlled_by(signer.key) {
+        return Err(MonopolyError::NotDebtor.into());
+    }
+    Ok(debt)
+}
 
-    fn process_next_turn(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-        let accounts_iter = &mut accounts.iter();
-        let game_account = next_account_info(accounts_iter)?;
-        let player_account = next_account_info(accounts_iter)?;
+fn process_pay_debt(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
 
-        if !player_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-        let mut game_data = validate_game_account(game_account)?;
-        
-        // Move to next player
-        game_data.game.current_player = (game_data.game.current_player + 1) % game_data.game.players.len() as u8;
-        
-        // Skip bankrupt players
-        while is_player_bankrupt(&game_data.game.players[game_data.game.current_player as usize]) {
-            game_data.game.current_player = (game_data.game.current_player + 1) % game_data.game.players.len() as u8;
-        }
+    let mut game_data = validate_game_account(game_account)?;
+    let debt = find_debt(&game_data.game, player_account)?;
 
-        game_data.pack_into_slice(&mut game_account.data.borrow_mut());
-        Ok(())
+    game_data.game.debt = None;
+    if !charge_player(&mut game_data.game, debt.debtor as usize, debt.creditor.map(|c| c as usize), debt.amount) {
+        return Err(MonopolyError::InsufficientBalance.into());
+    }
+    // Debts to the bank are taxes and fines the player could not cover when charged
+    if debt.creditor.is_none() {
+        pay_to_free_parking(&mut game_data.game, debt.amount)?;
     }
+    update_turn_phase(&mut game_data.game);
 
-    fn process_check_winner(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-        let accounts_iter = &mut accounts.iter();
-        let game_account = next_account_info(accounts_iter)?;
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
 
-        let game_data = validate_game_account(game_account)?;
-        
-        // Count non-bankrupt players
-        let active_players: Vec<_> = game_data.game.players.iter()
-            .enumerate()
-            .filter(|(_, player)| !is_player_bankrupt(player))
-            .collect();
+fn process_declare_bankruptcy(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+    let community_chest_account = next_account_info(accounts_iter)?;
+    let chance_account = next_account_info(accounts_iter)?;
 
-        if active_players.len() == 1 {
-            msg!("Game Over! Winner: {}", active_players[0].1.name);
-            return Ok(());
-        }
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-        // If more than one player is active, compare total worth
-        let mut max_worth = 0;
-        let mut winner_index = 0;
-        
-        for (i, player) in game_data.game.players.iter().enumerate() {
-            if is_player_bankrupt(player) {
-                continue;
+    let mut game_data = validate_game_account(game_account)?;
+    let debt = find_debt(&game_data.game, player_account)?;
+    let game = &mut game_data.game;
+    game.debt = None;
+
+    let debtor = debt.debtor as usize;
+    let creditor = debt.creditor.map(|c| c as usize);
+
+    let properties = std::mem::take(&mut game.players[debtor].properties);
+    let mut cash = std::mem::take(&mut game.players[debtor].cash);
+
+    for &index in &properties {
+        if let TileType::Property(property) = &mut game.board[index as usize] {
+            // Buildings are sold back to the bank one at a time, as SellHouse would
+            if property.houses == 5 {
+                game.hotels_remaining += 1;
+            } else {
+                game.houses_remaining += property.houses;
             }
-
-            let mut total_worth = player.cash;
-            
-            // Add property values
-            for &prop_idx in &player.properties {
-                if let TileType::Property(property) = &game_data.game.board[prop_idx as usize] {
-                    total_worth += property.cost;
-                    total_worth += property.house_cost * property.houses as u64;
+            while property.houses > 0 {
+                cash += building_sell_price(property);
+                property.houses -= 1;
+            }
+            property.owner = debt.creditor;
+            // Properties returned to the bank come back free of their mortgage and go up for auction
+            if creditor.is_none() {
+                property.mortgaged = false;
+                if !game.auction_queue.contains(&index) {
+                    game.auction_queue.push(index);
                 }
             }
+        }
+    }
 
-            if total_worth > max_worth {
-                max_worth = total_worth;
-                winner_index = i;
+    if let Some(creditor) = creditor {
+        let jail_cards = game.players[debtor].get_out_of_jail_cards;
+        move_jail_cards(game, debtor, creditor, jail_cards);
+        let creditor = &mut game.players[creditor];
+        creditor.cash += cash;
+        creditor.properties.extend(properties);
+    } else {
+        // Jail cards held by a player bankrupt to the bank go back to the decks they came from
+        for (deck_type, card_deck_account) in [
+            (DeckType::CommunityChest, community_chest_account),
+            (DeckType::Chance, chance_account),
+        ] {
+            if !std::mem::take(jail_card_from(&mut game.players[debtor], deck_type)) {
+                continue;
+            }
+            if *bound_card_deck(game, deck_type) != Some(*card_deck_account.key) {
+                return Err(MonopolyError::InvalidCardDeck.into());
             }
+            let mut card_deck = validate_card_deck(card_deck_account)?;
+            card_deck.jail_card_held = false;
+            card_deck.pack_into_slice(&mut card_deck_account.data.borrow_mut());
         }
+        game.players[debtor].get_out_of_jail_cards = 0;
+    }
 
-        msg!("Current leader: {} with total worth: {}", 
-            game_data.game.players[winner_index].name,
-            max_worth
-        );
+    game.players[debtor].eliminated = true;
+    msg!("{} is bankrupt", game.players[debtor].name);
 
-        Ok(())
+    if game.current_player as usize == debtor {
+        advance_turn(game);
+    } else {
+        update_turn_phase(game);
     }
 
-fn process_init_game(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    player_names: Vec<String>,
-) -> ProgramResult {
-    let accounts_iter = &mut accounts.iter();
-    let game_account = next_account_info(accounts_iter)?;
-    let rent_payer = next_account_info(accounts_iter)?;
+    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    Ok(())
+}
 
-    if !rent_payer.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+// Helper function to close the commit phase once every active player has committed or time is up.
+// A timed-out phase closes on its deadline slot rather than on whichever slot a later caller picks.
+fn advance_dice_round(game: &mut GameAccountData, slot: u64) -> ProgramResult {
+    let all_committed = game.players().iter()
+        .filter(|p| !p.is_eliminated())
+        .all(|p| p.dice_commitment().is_some());
+    if let Some(mut round) = game.dice_round()? {
+        if round.phase == DicePhase::Commit && (all_committed || slot > round.deadline_slot) {
+            round.phase = DicePhase::Reveal;
+            round.deadline_slot = slot.min(round.deadline_slot) + DICE_REVEAL_SLOTS;
+            game.set_dice_round(Some(round));
+        }
     }
+    Ok(())
+}
 
-    let game_data = GameAccount {
-        is_initialized: true,
-        game: Game {
-            board: create_board(),
-            players: player_names.iter().map(|name| Player {
-                name: name.clone(),
-                cash: 1500,
-                position: 0,
-                jail_turns: 0,
-                properties: vec![],
-                get_out_of_jail_cards: 0,
-            }).collect(),
-            current_player: 0,
-            free_parking: 0,
-            initialized: true,
+// Helper function to build the source that settles a dice round. A slot hash game takes the hash of
+// the slot its commit phase closed on, fixed before any secret was out, so neither the last revealer
+// nor whoever settles can hold out for a slot hash that suits them.
+fn load_dice_randomness(
+    game: &GameAccountData,
+    round: &DiceRound,
+    game_key: &Pubkey,
+    account: &AccountInfo,
+) -> Result<Box<dyn RandomnessSource>, ProgramError> {
+    match game.randomness()? {
+        RandomnessKind::SlotHash => {
+            let closed_slot = round.deadline_slot.saturating_sub(DICE_REVEAL_SLOTS);
+            // Once that hash has aged out of the sysvar, the round rolls from the revealed secrets alone
+            let slot_hash = slot_hash_at(account, closed_slot)?.unwrap_or_default();
+            Ok(Box::new(SlotHashEntropy { slot_hash }))
         },
-    };
+        randomness => load_randomness(&randomness, game.randomness_nonce(), game_key, game.randomness_request().as_ref(), account),
+    }
+}
 
-    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+// Helper function to roll from every reveal, fine players who never revealed and close the round
+fn settle_dice_round(game: &mut GameAccountData, source: &dyn RandomnessSource) -> ProgramResult {
+    let mut reveals = Vec::new();
+    let mut forfeited = 0;
+    for player in game.players_mut().iter_mut().filter(|p| !p.is_eliminated()) {
+        match player.dice_secret() {
+            Some(secret) => reveals.push(secret),
+            None => {
+                let penalty = player.cash().min(DICE_TIMEOUT_PENALTY);
+                player.set_cash(player.cash() - penalty);
+                forfeited += penalty;
+                msg!("{} did not reveal and forfeits {}", player.name(), penalty);
+            }
+        }
+        player.set_dice_secret(None);
+        player.set_dice_commitment(None);
+    }
+
+    pay_to_free_parking(game, forfeited)?;
+    let nonce = game.randomness_nonce();
+    game.set_rolled_dice(Some(roll_dice(&reveals, source, nonce)));
+    game.set_randomness_nonce(nonce + 1);
+    game.set_randomness_request(None);
+    game.set_dice_round(None);
     Ok(())
 }
 
-fn process_move_player(_program_id: &Pubkey, accounts: &[AccountInfo], dice_roll: u8) -> ProgramResult {
+fn process_commit_dice(_program_id: &Pubkey, accounts: &[AccountInfo], commitment: [u8; 32]) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let game_account = next_account_info(accounts_iter)?;
     let player_account = next_account_info(accounts_iter)?;
     let clock_sysvar = next_account_info(accounts_iter)?;
 
-    if dice_roll < 2 || dice_roll > 12 {
-        return Err(MonopolyError::InvalidDiceRoll.into());
-    }
-
     if !player_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut game_data = validate_game_account(game_account)?;
-    let current_player = game_data.game.current_player as usize;
-    let player = &mut game_data.game.players[current_player];
+    // Every player commits each turn, so this only touches the fields it needs in place
+    let mut game = load_game_account_mut(game_account)?;
+    if game.status()? != GameStatus::Started {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    if game.turn_phase()? != TurnPhase::AwaitingRoll {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    if game.has_rolled_dice() {
+        return Err(MonopolyError::DiceAlreadyRolled.into());
+    }
 
-    // Use provided dice roll
-    let total_move = dice_roll;
+    let player_index = game.players().iter()
+        .position(|p| !p.is_eliminated() && p.is_controlled_by(player_account.key))
+        .ok_or(MonopolyError::PlayerNotInGame)?;
 
-    // Handle jail logic
-    if player.jail_turns > 0 {
-        // In jail, can't move unless paying fine
-        player.jail_turns += 1;
-        if player.jail_turns >= 3 {
-            player.cash = player.cash.saturating_sub(50);
-            player.jail_turns = 0;
-        } else {
-            return Ok(());
-        }
+    let clock = Clock::from_account_info(clock_sysvar)?;
+    let mut round = game.dice_round()?.unwrap_or(DiceRound {
+        phase: DicePhase::Commit,
+        deadline_slot: clock.slot + DICE_COMMIT_SLOTS,
+    });
+    if round.phase != DicePhase::Commit || clock.slot > round.deadline_slot {
+        return Err(MonopolyError::InvalidDicePhase.into());
     }
 
-    // Move player
-    let new_position = (player.position + total_move) % 40;
-    
-    // Pass GO
-    if new_position < player.position {
-        player.cash += 200;
-    }
-    
-    player.position = new_position;
-
-    // Process landed tile
-    let new_position_usize = new_position as usize;
-    match &game_data.game.board[new_position_usize] {
-        TileType::Special(special) => match special {
-            SpecialTile::GoToJail => {
-                player.position = 10; // Jail position
-                player.jail_turns = 1;
-            },
-            SpecialTile::IncomeTax => {
-                player.cash = player.cash.saturating_sub(200);
-            },
-            SpecialTile::LuxuryTax => {
-                player.cash = player.cash.saturating_sub(100);
-            },
-            _ => {},
-        },
-        _ => {},
+    let player = &mut game.players_mut()[player_index];
+    if player.dice_commitment().is_some() {
+        return Err(MonopolyError::DiceAlreadyCommitted.into());
     }
-
-    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    player.set_dice_commitment(Some(commitment));
+
+    // Same as `advance_dice_round`: reveals open once everyone still playing has committed
+    let all_committed = game.players().iter()
+        .filter(|p| !p.is_eliminated())
+        .all(|p| p.dice_commitment().is_some());
+    if all_committed {
+        round.phase = DicePhase::Reveal;
+        round.deadline_slot = clock.slot + DICE_REVEAL_SLOTS;
+    }
+    game.set_dice_round(Some(round));
     Ok(())
 }
 
-fn process_buy_property(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    property_index: u8,
-) -> ProgramResult {
+fn process_reveal_dice(_program_id: &Pubkey, accounts: &[AccountInfo], secret: [u8; 32]) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let game_account = next_account_info(accounts_iter)?;
     let player_account = next_account_info(accounts_iter)?;
+    let clock_sysvar = next_account_info(accounts_iter)?;
+    let randomness_account = next_account_info(accounts_iter)?;
 
     if !player_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut game_data = validate_game_account(game_account)?;
-    let current_player = game_data.game.current_player as usize;
-    let player = &mut game_data.game.players[current_player];
-
-    let property_index_usize = property_index as usize;
-    match &mut game_data.game.board[property_index_usize] {
-        TileType::Property(property) => {
-            if property.owner.is_some() {
-                return Err(MonopolyError::PropertyAlreadyOwned.into());
-            }
+    let mut game = load_game_account_mut(game_account)?;
+    check_turn_phase(&*game, &[TurnPhase::AwaitingRoll])?;
+    let player_index = game.players().iter()
+        .position(|p| !p.is_eliminated() && p.is_controlled_by(player_account.key))
+        .ok_or(MonopolyError::PlayerNotInGame)?;
 
-            if player.cash < property.cost {
-                return Err(MonopolyError::InsufficientBalance.into());
-            }
+    let clock = Clock::from_account_info(clock_sysvar)?;
+    advance_dice_round(&mut game, clock.slot)?;
+    let round = match game.dice_round()? {
+        Some(round) if round.phase == DicePhase::Reveal && clock.slot <= round.deadline_slot => round,
+        _ => return Err(MonopolyError::InvalidDicePhase.into()),
+    };
 
-            player.cash -= property.cost;
-            property.owner = Some(player_account.key.clone());
-            player.properties.push(property_index);
-        },
-        _ => return Err(MonopolyError::InvalidProperty.into()),
+    let player = &mut game.players[player_index];
+    if player.dice_secret().is_some() || player.dice_commitment() != Some(dice_commitment(&secret, &player.wallet)) {
+        return Err(MonopolyError::InvalidDiceReveal.into());
+    }
+    player.set_dice_secret(Some(secret));
+
+    // Settle as soon as everyone who committed has revealed
+    let all_revealed = game.players().iter()
+        .filter(|p| p.dice_commitment().is_some())
+        .all(|p| p.dice_secret().is_some());
+    if all_revealed {
+        let source = load_dice_randomness(&game, &round, game_account.key, randomness_account)?;
+        settle_dice_round(&mut game, source.as_ref())?;
     }
-
-    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
     Ok(())
 }
 
-fn process_build_house(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    property_index: u8,
-) -> ProgramResult {
+fn process_settle_dice(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let game_account = next_account_info(accounts_iter)?;
     let player_account = next_account_info(accounts_iter)?;
+    let clock_sysvar = next_account_info(accounts_iter)?;
+    let randomness_account = next_account_info(accounts_iter)?;
 
     if !player_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut game_data = validate_game_account(game_account)?;
-    let current_player = game_data.game.current_player as usize;
-    let player = &mut game_data.game.players[current_player];
+    let mut game = load_game_account_mut(game_account)?;
+    check_turn_phase(&*game, &[TurnPhase::AwaitingRoll])?;
+    if !game.players().iter().any(|p| p.is_controlled_by(player_account.key)) {
+        return Err(MonopolyError::PlayerNotInGame.into());
+    }
 
-    if !can_build_house(property_index, &player.properties, &game_data.game.board) {
-        return Err(MonopolyError::InvalidProperty.into());
+    let clock = Clock::from_account_info(clock_sysvar)?;
+    advance_dice_round(&mut game, clock.slot)?;
+    let round = match game.dice_round()? {
+        Some(round) if round.phase == DicePhase::Reveal => round,
+        _ => return Err(MonopolyError::InvalidDicePhase.into()),
+    };
+    if clock.slot <= round.deadline_slot {
+        return Err(MonopolyError::DiceRoundNotExpired.into());
     }
 
-    let property_index_usize = property_index as usize;
-    match &mut game_data.game.board[property_index_usize] {
-        TileType::Property(property) => {
-            if property.owner != Some(player_account.key.clone()) {
-                return Err(MonopolyError::NotPropertyOwner.into());
-            }
+    let source = load_dice_randomness(&game, &round, game_account.key, randomness_account)?;
+    settle_dice_round(&mut game, source.as_ref())
+}
 
-            if property.houses >= 5 {
-                return Err(MonopolyError::MaximumHousesReached.into());
+// Helper function to build the game's randomness source for draw `nonce` from the account supplied for it.
+// An oracle game only takes the output of `request`, the one request it recorded for the draw.
+fn load_randomness(
+    randomness: &RandomnessKind,
+    nonce: u64,
+    game_key: &Pubkey,
+    request: Option<&Pubkey>,
+    account: &AccountInfo,
+) -> Result<Box<dyn RandomnessSource>, ProgramError> {
+    Ok(match randomness {
+        RandomnessKind::SlotHash => Box::new(SlotHashEntropy::from_account(account)?),
+        RandomnessKind::Oracle { .. } => {
+            if request != Some(account.key) {
+                return Err(MonopolyError::RandomnessUnavailable.into());
             }
-
-            if player.cash < property.house_cost {
-                return Err(MonopolyError::InsufficientBalance.into());
+            let request = validate_randomness_request(account)?;
+            // An output only serves the draw it was requested for
+            if request.game != *game_key || request.nonce != nonce || !request.fulfilled {
+                return Err(MonopolyError::RandomnessUnavailable.into());
             }
-
-            player.cash -= property.house_cost;
-            property.houses += 1;
+            Box::new(OracleOutput { output: request.output })
         },
-        _ => return Err(MonopolyError::InvalidProperty.into()),
+        RandomnessKind::Seeded { seed } => Box::new(SeededRandomness { seed: *seed }),
+    })
+}
+
+fn process_request_randomness(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let request_account = next_account_info(accounts_iter)?;
+    let player_account = next_account_info(accounts_iter)?;
+
+    if !player_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut game_data = validate_game_account(game_account)?;
+    let game = &mut game_data.game;
+    if !matches!(game.randomness, RandomnessKind::Oracle { .. }) {
+        return Err(MonopolyError::InvalidGameState.into());
+    }
+    check_turn_phase(&*game, ACTIVE_PHASES)?;
+    if !game.players.iter().any(|p| p.is_controlled_by(player_account.key)) {
+        return Err(MonopolyError::PlayerNotInGame.into());
+    }
+    // One request per draw, so no one can open several and pick the output they like
+    if game.randomness_request.is_some() {
+        return Err(MonopolyError::InvalidGameState.into());
     }
 
+    if request_account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = request_account.data.borrow();
+    if !is_blank_account(&data) && RandomnessRequest::unpack_unchecked(&data)?.is_initialized {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    drop(data);
+
+    let request = RandomnessRequest {
+        is_initialized: true,
+        game: *game_account.key,
+        nonce: game.randomness_nonce,
+        fulfilled: false,
+        output: [0; 32],
+    };
+    msg!("Requested randomness for draw {}", request.nonce);
+    game.randomness_request = Some(*request_account.key);
+
+    request.pack_into_slice(&mut request_account.data.borrow_mut());
     game_data.pack_into_slice(&mut game_account.data.borrow_mut());
     Ok(())
 }
 
-fn process_pay_rent(
-    _program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    property_index: u8,
-) -> ProgramResult {
+fn process_fulfill_randomness(_program_id: &Pubkey, accounts: &[AccountInfo], output: [u8; 32]) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     let game_account = next_account_info(accounts_iter)?;
-    let player_account = next_account_info(accounts_iter)?;
-    let owner_account = next_account_info(accounts_iter)?;
+    let request_account = next_account_info(accounts_iter)?;
+    let oracle_account = next_account_info(accounts_iter)?;
 
-    if !player_account.is_signer {
+    if !oracle_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut game_data = validate_game_account(game_account)?;
-    let current_player = game_data.game.current_player as usize;
-    let player = &mut game_data.game.players[current_player];
+    // No proof is checked: the output is trusted because the configured oracle signed it
+    let game_data = validate_game_account(game_account)?;
+    match &game_data.game.randomness {
+        RandomnessKind::Oracle { oracle } if oracle == oracle_account.key => {},
+        _ => return Err(MonopolyError::NotRandomnessOracle.into()),
+    }
 
-    let property_index_usize = property_index as usize;
-    match &game_data.game.board[property_index_usize] {
-        TileType::Property(property) => {
-            if property.owner != Some(owner_account.key.clone()) {
-                return Err(MonopolyError::NotRentOwner.into());
-            }
+    let mut request = validate_randomness_request(request_account)?;
+    if request.game != *game_account.key || request.fulfilled {
+        return Err(MonopolyError::InvalidInstruction.into());
+    }
 
-            let rent = calculate_rent(property.rent[0], property.houses);
-            if player.cash < rent {
-                return Err(MonopolyError::InsufficientBalance.into());
-            }
+    request.fulfilled = true;
+    request.output = output;
 
-            player.cash -= rent;
+    request.pack_into_slice(&mut request_account.data.borrow_mut());
+    Ok(())
+}
 
-            // Find owner in players list and add rent
-            for p in game_data.game.players.iter_mut() {
-                if p.properties.contains(&property_index) {
-                    p.cash += rent;
-                    break;
-                }
-            }
-        },
-        _ => return Err(MonopolyError::InvalidProperty.into()),
+fn process_migrate_account(program_id: &Pubkey, accounts: &[AccountInfo], kind: AccountKind) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let account = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+    let program_data = next_account_info(accounts_iter)?;
+    let rent_sysvar = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    check_upgrade_authority(program_id, program_data, authority.key)?;
+    if account.owner != &crate::id() {
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    game_data.pack_into_slice(&mut game_account.data.borrow_mut());
+    // Version 1 seats were not bound to wallets; each wallet taking one must sign
+    let wallets = accounts_iter
+        .map(|wallet| if wallet.is_signer { Ok(*wallet.key) } else { Err(ProgramError::MissingRequiredSignature) })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The account must still be in its kind's version 1 layout
+    let v1_len = account_v1::account_len(kind).ok_or(MonopolyError::UnsupportedAccountVersion)?;
+    if account.data_len() != v1_len {
+        return Err(MonopolyError::UnsupportedAccountVersion.into());
+    }
+    let upgraded = account_v1::upgrade(&account.data.borrow(), kind, &wallets)?;
+
+    let new_len = upgraded.len();
+    let rent = Rent::from_account_info(rent_sysvar)?;
+    let shortfall = rent.minimum_balance(new_len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(authority.key, account.key, shortfall),
+            &[authority.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+
+    account.realloc(new_len, false)?;
+    account.data.borrow_mut().copy_from_slice(&upgraded);
+    msg!("Migrated {:?} account to version {}", kind, ACCOUNT_VERSION);
     Ok(())
 }
+
+/// Check `authority` is the upgrade authority recorded in `program_id`'s ProgramData account.
+/// A program deployed immutable has no authority, so nothing passes.
+fn check_upgrade_authority(program_id: &Pubkey, program_data: &AccountInfo, authority: &Pubkey) -> ProgramResult {
+    let (program_data_key, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if program_data.key != &program_data_key || program_data.owner != &bpf_loader_upgradeable::id() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    let data = program_data.data.borrow();
+    let state: UpgradeableLoaderState = bincode::deserialize(data.get(..metadata_len).ok_or(ProgramError::InvalidAccountData)?)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    match state {
+        UpgradeableLoaderState::ProgramData { upgrade_authority_address: Some(upgrade_authority), .. }
+            if upgrade_authority == *authority => Ok(()),
+        UpgradeableLoaderState::ProgramData { .. } => Err(MonopolyError::NotUpgradeAuthority.into()),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
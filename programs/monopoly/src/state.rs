@@ -1,18 +1,90 @@
 // Removed unused import
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
+    entrypoint::ProgramResult,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
 };
+use crate::account::DeckType;
+use crate::board::create_board;
+use crate::cursor::{Reader, Writer};
 
 impl Sealed for Color {}
+impl Sealed for TileKind {}
 impl Sealed for Property {}
 impl Sealed for SpecialTile {}
 impl Sealed for TileType {}
+impl Sealed for TileState {}
 impl Sealed for Player {}
 impl Sealed for Game {}
+impl Sealed for GameStatus {}
+impl Sealed for TurnPhase {}
+impl Sealed for Auction {}
+impl Sealed for TradeTerms {}
+impl Sealed for Debt {}
+impl Sealed for PendingAction {}
+impl Sealed for DiceRound {}
+impl Sealed for RandomnessKind {}
+impl Sealed for GameRules {}
 impl Sealed for Card {}
 
+/// Minimum number of players required to start a game
+pub const MIN_PLAYERS: u8 = 2;
+/// Maximum number of players a game can seat
+pub const MAX_PLAYERS: u8 = 8;
+/// Number of tiles on the board
+pub const BOARD_SIZE: usize = 40;
+/// Longest player name, in bytes, a game account can hold
+pub const MAX_NAME_LEN: usize = 32;
+/// Most properties one player can own (every ownable tile on the board)
+pub const MAX_PLAYER_PROPERTIES: usize = 28;
+/// Longest rent table of a property: a street's rent without houses, then with 1 to 4 houses and a hotel
+pub const MAX_RENT_LEVELS: usize = 6;
+/// Longest table of rents with buildings: 1 to 4 houses and a hotel
+pub const MAX_HOUSE_RENT_LEVELS: usize = 5;
+/// Cards in a Chance or Community Chest deck
+pub const MAX_DECK_CARDS: usize = 16;
+/// Board id of a game whose board has not been laid out
+pub const BOARD_NONE: u8 = 0;
+/// Board id of the standard board built by `create_board`
+pub const BOARD_STANDARD: u8 = 1;
+/// Number of slots an auction stays open for bidding
+pub const AUCTION_DURATION_SLOTS: u64 = 150;
+/// Smallest amount a bid must raise the current highest bid by
+pub const MIN_BID_INCREMENT: u64 = 10;
+/// Most properties one side of a trade can hold (every ownable tile on the board)
+pub const MAX_TRADE_PROPERTIES: usize = 28;
+/// Houses the bank holds at the start of a game
+pub const BANK_HOUSES: u8 = 32;
+/// Hotels the bank holds at the start of a game
+pub const BANK_HOTELS: u8 = 12;
+/// Board position of the jail; players there with no jail turns are just visiting
+pub const JAIL_POSITION: u8 = 10;
+/// Bail paid to leave jail early, and the fine forced after the last failed doubles attempt
+pub const JAIL_FINE: u64 = 50;
+/// Charged for landing on the Income Tax tile
+pub const INCOME_TAX: u64 = 200;
+/// Charged for landing on the Luxury Tax tile
+pub const LUXURY_TAX: u64 = 100;
+/// Cash each player starts with under the standard rules
+pub const STARTING_CASH: u64 = 1500;
+/// Salary paid for passing GO under the standard rules
+pub const GO_SALARY: u64 = 200;
+/// Largest starting cash, salary or fine a game's rules may set, keeping every sum of them far
+/// from overflow
+pub const MAX_RULE_AMOUNT: u64 = 1_000_000;
+/// Rolls a jailed player may try for doubles before the fine is forced
+pub const MAX_JAIL_ROLLS: u8 = 3;
+/// Consecutive doubles that send a player straight to jail
+pub const MAX_DOUBLES: u8 = 3;
+/// Number of slots players have to commit to a dice round
+pub const DICE_COMMIT_SLOTS: u64 = 150;
+/// Number of slots committed players have to reveal their secrets
+pub const DICE_REVEAL_SLOTS: u64 = 150;
+/// Cash forfeited to Free Parking by a player who lets a dice round time out
+pub const DICE_TIMEOUT_PENALTY: u64 = 50;
+
 impl IsInitialized for Game {
     fn is_initialized(&self) -> bool {
         self.initialized
@@ -31,7 +103,7 @@ impl IsInitialized for Property {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub enum Color {
     Brown,
     LightBlue,
@@ -48,7 +120,7 @@ impl Pack for Color {
     const LEN: usize = 1;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        dst[0] = match self {
+        Writer::new(dst).write_u8(match self {
             Color::Brown => 0,
             Color::LightBlue => 1,
             Color::Pink => 2,
@@ -58,11 +130,11 @@ impl Pack for Color {
             Color::Green => 6,
             Color::Blue => 7,
             Color::DarkBlue => 8,
-        };
+        });
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        Ok(match src[0] {
+        Ok(match Reader::new(src).read_u8()? {
             0 => Color::Brown,
             1 => Color::LightBlue,
             2 => Color::Pink,
@@ -77,133 +149,183 @@ impl Pack for Color {
     }
 }
 
+/// What kind of ownable tile a property is, which decides how its rent is worked out
+#[derive(Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum TileKind {
+    /// Colored street that can be built on
+    Street,
+    /// Rent scales with the number of railroads the owner holds
+    Railroad,
+    /// Rent is a multiple of the last dice total
+    Utility,
+}
+
+impl Pack for TileKind {
+    const LEN: usize = 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        Writer::new(dst).write_u8(match self {
+            TileKind::Street => 0,
+            TileKind::Railroad => 1,
+            TileKind::Utility => 2,
+        });
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Ok(match Reader::new(src).read_u8()? {
+            0 => TileKind::Street,
+            1 => TileKind::Railroad,
+            2 => TileKind::Utility,
+            _ => return Err(ProgramError::InvalidAccountData),
+        })
+    }
+}
+
 impl Property {
     pub fn as_property(&self) -> Option<&Property> {
         Some(self)
     }
+
+    pub fn view(&self) -> PropertyView<'_> {
+        PropertyView {
+            color: &self.color,
+            kind: self.kind,
+            cost: self.cost,
+            rent: &self.rent,
+            house_rent: &self.house_rent,
+            owner: self.owner,
+            houses: self.houses,
+            mortgaged: self.mortgaged,
+        }
+    }
+}
+
+/// The parts of a property the turn rules read, borrowed from an unpacked `Property` or from
+/// the static board table and a game account's tile state
+#[derive(Debug, Clone, Copy)]
+pub struct PropertyView<'a> {
+    pub color: &'a Color,
+    pub kind: TileKind,
+    pub cost: u64,
+    pub rent: &'a [u64],
+    pub house_rent: &'a [u64],
+    pub owner: Option<u8>,
+    pub houses: u8,
+    pub mortgaged: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct Property {
     pub name: String,
+    /// Color group; only meaningful for streets
     pub color: Color,
+    pub kind: TileKind,
     pub cost: u64,
     pub rent: Vec<u64>,
     pub house_cost: u64,
     pub hotel_cost: u64,
     pub house_rent: Vec<u64>,
-    pub owner: Option<Pubkey>,
+    /// Index into `Game::players` of the owner, if any
+    pub owner: Option<u8>,
     pub houses: u8,
+    pub mortgaged: bool,
 }
 
 impl Pack for Property {
-    const LEN: usize = 1024; // Large enough for property data
+    const LEN: usize = 4 + MAX_NAME_LEN + // name
+        Color::LEN +
+        TileKind::LEN +
+        8 + // cost
+        4 + 8 * MAX_RENT_LEVELS + // rent
+        8 + // house_cost
+        8 + // hotel_cost
+        4 + 8 * MAX_HOUSE_RENT_LEVELS + // house_rent
+        1 + // owner
+        1 + // houses
+        1; // mortgaged
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        
+        let mut writer = Writer::new(dst);
+
         // Write name length and bytes
         let name_bytes = self.name.as_bytes();
-        dst[current..current + 4].copy_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-        current += 4;
-        dst[current..current + name_bytes.len()].copy_from_slice(name_bytes);
-        current += name_bytes.len();
-        
-        // Write color
-        self.color.pack_into_slice(&mut dst[current..current + 1]);
-        current += 1;
-        
+        writer.write_u32(name_bytes.len() as u32);
+        writer.write_bytes(name_bytes);
+
+        // Write color and kind
+        writer.write_pack(&self.color);
+        writer.write_pack(&self.kind);
+
         // Write cost
-        dst[current..current + 8].copy_from_slice(&self.cost.to_le_bytes());
-        current += 8;
-        
+        writer.write_u64(self.cost);
+
         // Write rent array
-        dst[current..current + 4].copy_from_slice(&(self.rent.len() as u32).to_le_bytes());
-        current += 4;
+        writer.write_u32(self.rent.len() as u32);
         for rent in &self.rent {
-            dst[current..current + 8].copy_from_slice(&rent.to_le_bytes());
-            current += 8;
+            writer.write_u64(*rent);
         }
-        
+
         // Write house and hotel costs
-        dst[current..current + 8].copy_from_slice(&self.house_cost.to_le_bytes());
-        current += 8;
-        dst[current..current + 8].copy_from_slice(&self.hotel_cost.to_le_bytes());
-        current += 8;
+        writer.write_u64(self.house_cost);
+        writer.write_u64(self.hotel_cost);
 
         // Write house_rent array
-        dst[current..current + 4].copy_from_slice(&(self.house_rent.len() as u32).to_le_bytes());
-        current += 4;
+        writer.write_u32(self.house_rent.len() as u32);
         for rent in &self.house_rent {
-            dst[current..current + 8].copy_from_slice(&rent.to_le_bytes());
-            current += 8;
+            writer.write_u64(*rent);
         }
-        
-        // Write owner
-        dst[current] = self.owner.is_some() as u8;
-        current += 1;
-        if let Some(owner) = &self.owner {
-            dst[current..current + 32].copy_from_slice(&owner.to_bytes());
-            current += 32;
-        }
-        
+
+        // Write owner seat, u8::MAX when unowned
+        writer.write_u8(self.owner.unwrap_or(u8::MAX));
+
         // Write houses
-        dst[current] = self.houses;
+        writer.write_u8(self.houses);
+
+        // Write mortgage flag
+        writer.write_bool(self.mortgaged);
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut current = 0;
-        
-        let name_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-        let name = String::from_utf8(src[current..current + name_len].to_vec())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        current += name_len;
-        
-        let color = Color::unpack_from_slice(&src[current..current + 1])?;
-        current += 1;
-        
-        let cost = u64::from_le_bytes(src[current..current + 8].try_into().unwrap());
-        current += 8;
-        
-        let rent_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
+        let mut reader = Reader::new(src);
+
+        let name_len = reader.read_len_u32(reader.remaining())?;
+        let name = reader.read_string(name_len)?;
+
+        let color = reader.read_pack()?;
+        let kind = reader.read_pack()?;
+
+        let cost = reader.read_u64()?;
+
+        // Lengths are checked against the bytes left so a bad one cannot reserve a huge Vec
+        let rent_len = reader.read_len_u32(reader.remaining() / 8)?;
         let mut rent = Vec::with_capacity(rent_len);
         for _ in 0..rent_len {
-            rent.push(u64::from_le_bytes(src[current..current + 8].try_into().unwrap()));
-            current += 8;
+            rent.push(reader.read_u64()?);
         }
-        
-        let house_cost = u64::from_le_bytes(src[current..current + 8].try_into().unwrap());
-        current += 8;
-        let hotel_cost = u64::from_le_bytes(src[current..current + 8].try_into().unwrap());
-        current += 8;
+
+        let house_cost = reader.read_u64()?;
+        let hotel_cost = reader.read_u64()?;
 
         // Read house_rent array
-        let house_rent_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
+        let house_rent_len = reader.read_len_u32(reader.remaining() / 8)?;
         let mut house_rent = Vec::with_capacity(house_rent_len);
         for _ in 0..house_rent_len {
-            house_rent.push(u64::from_le_bytes(src[current..current + 8].try_into().unwrap()));
-            current += 8;
+            house_rent.push(reader.read_u64()?);
         }
-        
-        let has_owner = src[current] != 0;
-        current += 1;
-        let owner = if has_owner {
-            Some(Pubkey::from(<[u8; 32]>::try_from(&src[current..current + 32]).unwrap()))
-        } else {
-            None
+
+        let owner = match reader.read_u8()? {
+            u8::MAX => None,
+            owner => Some(owner),
         };
-        if has_owner {
-            current += 32;
-        }
-        
-        let houses = src[current];
-        
+
+        let houses = reader.read_u8()?;
+
+        let mortgaged = reader.read_bool()?;
+
         Ok(Property {
             name,
             color,
+            kind,
             cost,
             rent,
             house_cost,
@@ -211,6 +333,7 @@ impl Pack for Property {
             house_rent,
             owner,
             houses,
+            mortgaged,
         })
     }
 }
@@ -231,7 +354,7 @@ impl Pack for SpecialTile {
     const LEN: usize = 1;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        dst[0] = match self {
+        Writer::new(dst).write_u8(match self {
             SpecialTile::Go => 0,
             SpecialTile::Jail => 1,
             SpecialTile::FreeParking => 2,
@@ -240,11 +363,11 @@ impl Pack for SpecialTile {
             SpecialTile::Chance => 5,
             SpecialTile::IncomeTax => 6,
             SpecialTile::LuxuryTax => 7,
-        };
+        });
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        Ok(match src[0] {
+        Ok(match Reader::new(src).read_u8()? {
             0 => SpecialTile::Go,
             1 => SpecialTile::Jail,
             2 => SpecialTile::FreeParking,
@@ -273,105 +396,691 @@ impl TileType {
     }
 }
 
+/// A tile as `Board` hands it out
+#[derive(Debug, Clone, Copy)]
+pub enum TileView<'a> {
+    Property(PropertyView<'a>),
+    Special(&'a SpecialTile),
+}
+
+/// Read access to a board's tiles, whether held as `TileType`s or read in place from a game account
+pub trait Board {
+    /// Number of tiles on the board
+    fn tile_count(&self) -> usize;
+
+    /// The tile at `index`, or `None` past the end of the board
+    fn tile(&self, index: u8) -> Option<TileView<'_>>;
+
+    /// The property at `index`, or `None` if that tile cannot be owned
+    fn property(&self, index: u8) -> Option<PropertyView<'_>> {
+        match self.tile(index)? {
+            TileView::Property(property) => Some(property),
+            TileView::Special(_) => None,
+        }
+    }
+}
+
+impl Board for [TileType] {
+    fn tile_count(&self) -> usize {
+        self.len()
+    }
+
+    fn tile(&self, index: u8) -> Option<TileView<'_>> {
+        Some(match self.get(index as usize)? {
+            TileType::Property(property) => TileView::Property(property.view()),
+            TileType::Special(special) => TileView::Special(special),
+        })
+    }
+}
+
+impl Board for Vec<TileType> {
+    fn tile_count(&self) -> usize {
+        self.as_slice().tile_count()
+    }
+
+    fn tile(&self, index: u8) -> Option<TileView<'_>> {
+        self.as_slice().tile(index)
+    }
+}
+
 impl Pack for TileType {
     const LEN: usize = 1025; // 1 byte for type + max(Property::LEN, SpecialTile::LEN)
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
         match self {
             TileType::Property(property) => {
-                dst[0] = 0;
-                property.pack_into_slice(&mut dst[1..]);
+                writer.write_u8(0);
+                property.pack_into_slice(writer.rest());
             }
             TileType::Special(special) => {
-                dst[0] = 1;
-                special.pack_into_slice(&mut dst[1..]);
+                writer.write_u8(1);
+                special.pack_into_slice(writer.rest());
             }
         }
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        match src[0] {
-            0 => Ok(TileType::Property(Property::unpack_from_slice(&src[1..])?)),
-            1 => Ok(TileType::Special(SpecialTile::unpack_from_slice(&src[1..])?)),
+        let mut reader = Reader::new(src);
+        let tag = reader.read_u8()?;
+        let rest = reader.read_rest();
+        match tag {
+            0 => Ok(TileType::Property(Property::unpack_from_slice(rest)?)),
+            1 => Ok(TileType::Special(SpecialTile::unpack_from_slice(rest)?)),
             _ => Err(ProgramError::InvalidAccountData),
         }
     }
 }
 
+/// The part of a tile that changes during play. A game account stores only this for each tile;
+/// names, prices and rents come from the static board its board id refers to.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TileState {
+    /// Index into `Game::players` of the owner, if any
+    pub owner: Option<u8>,
+    pub houses: u8,
+    pub mortgaged: bool,
+}
+
+impl Pack for TileState {
+    const LEN: usize = 3;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        writer.write_u8(self.owner.unwrap_or(u8::MAX));
+        writer.write_u8(self.houses);
+        writer.write_bool(self.mortgaged);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        let owner = reader.read_u8()?;
+        Ok(TileState {
+            owner: if owner != u8::MAX { Some(owner) } else { None },
+            houses: reader.read_u8()?,
+            mortgaged: reader.read_bool()?,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Player {
     pub name: String,
+    /// Wallet that controls this player
+    pub wallet: Pubkey,
+    /// Optional key allowed to act on the wallet's behalf (e.g. a pilot.buzz agent)
+    pub delegate: Option<Pubkey>,
     pub cash: u64,
     pub position: u8,
+    /// Rolls attempted in jail, counting from 1 on arrival; 0 when free
     pub jail_turns: u8,
     pub properties: Vec<u8>,
     pub get_out_of_jail_cards: u8,
+    /// Whether one of the held jail cards was drawn from Community Chest, and must go back there
+    pub community_chest_jail_card: bool,
+    /// Whether one of the held jail cards was drawn from Chance, and must go back there
+    pub chance_jail_card: bool,
+    /// Set once the player has declared bankruptcy
+    pub eliminated: bool,
+    /// Hash of the player's secret for the current dice round
+    pub dice_commitment: Option<[u8; 32]>,
+    /// Secret revealed for the current dice round
+    pub dice_secret: Option<[u8; 32]>,
 }
 
+/// A seat as the turn rules read and change it, whether unpacked as a `Player` or borrowed in
+/// place from a game account
+pub trait PlayerState {
+    fn name(&self) -> &str;
+    fn cash(&self) -> u64;
+    fn set_cash(&mut self, cash: u64);
+    fn position(&self) -> u8;
+    fn set_position(&mut self, position: u8);
+    fn jail_turns(&self) -> u8;
+    fn set_jail_turns(&mut self, jail_turns: u8);
+    fn is_eliminated(&self) -> bool;
+
+    /// Record a property the player has just taken over
+    fn push_property(&mut self, property_index: u8) -> ProgramResult;
+
+    /// Returns true if `key` is the player's wallet or its registered delegate
+    fn is_controlled_by(&self, key: &Pubkey) -> bool;
+
+    /// Returns true if the player is locked in jail rather than just visiting
+    fn in_jail(&self) -> bool {
+        self.jail_turns() > 0
+    }
+}
+
+impl PlayerState for Player {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn cash(&self) -> u64 {
+        self.cash
+    }
+
+    fn set_cash(&mut self, cash: u64) {
+        self.cash = cash;
+    }
+
+    fn position(&self) -> u8 {
+        self.position
+    }
+
+    fn set_position(&mut self, position: u8) {
+        self.position = position;
+    }
+
+    fn jail_turns(&self) -> u8 {
+        self.jail_turns
+    }
+
+    fn set_jail_turns(&mut self, jail_turns: u8) {
+        self.jail_turns = jail_turns;
+    }
+
+    fn is_eliminated(&self) -> bool {
+        self.eliminated
+    }
+
+    fn push_property(&mut self, property_index: u8) -> ProgramResult {
+        self.properties.push(property_index);
+        Ok(())
+    }
+
+    fn is_controlled_by(&self, key: &Pubkey) -> bool {
+        self.wallet == *key || self.delegate.as_ref() == Some(key)
+    }
+}
 
 impl Pack for Player {
-    const LEN: usize = 512; // Large enough for player data
+    // Fixed width so every seat sits at a known offset in the game account
+    const LEN: usize = 1 + MAX_NAME_LEN + // name
+        32 + // wallet
+        1 + 32 + // delegate
+        8 + // cash
+        1 + // position
+        1 + // jail_turns
+        1 + MAX_PLAYER_PROPERTIES + // properties
+        1 + // get_out_of_jail_cards
+        2 + // community_chest_jail_card, chance_jail_card
+        1 + // eliminated
+        1 + 32 + // dice_commitment
+        1 + 32; // dice_secret
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        
+        let mut writer = Writer::new(dst);
+
         // Write name length and bytes
         let name_bytes = self.name.as_bytes();
-        dst[current..current + 4].copy_from_slice(&(name_bytes.len() as u32).to_le_bytes());
-        current += 4;
-        dst[current..current + name_bytes.len()].copy_from_slice(name_bytes);
-        current += name_bytes.len();
-        
+        writer.write_u8(name_bytes.len() as u8);
+        writer.write_bytes(name_bytes);
+        writer.write_zeros(MAX_NAME_LEN - name_bytes.len());
+
+        // Write wallet and delegate
+        writer.write_pubkey(&self.wallet);
+        writer.write_bool(self.delegate.is_some());
+        writer.write_pubkey(&self.delegate.unwrap_or_default());
+
         // Write player state
-        dst[current..current + 8].copy_from_slice(&self.cash.to_le_bytes());
-        current += 8;
-        dst[current] = self.position;
-        current += 1;
-        dst[current] = self.jail_turns;
-        current += 1;
-        
+        writer.write_u64(self.cash);
+        writer.write_u8(self.position);
+        writer.write_u8(self.jail_turns);
+
         // Write properties
-        dst[current..current + 4].copy_from_slice(&(self.properties.len() as u32).to_le_bytes());
-        current += 4;
-        dst[current..current + self.properties.len()].copy_from_slice(&self.properties);
-        current += self.properties.len();
-        
-        // Write jail cards
-        dst[current] = self.get_out_of_jail_cards;
+        writer.write_u8(self.properties.len() as u8);
+        writer.write_bytes(&self.properties);
+        writer.write_zeros(MAX_PLAYER_PROPERTIES - self.properties.len());
+
+        // Write jail cards and the decks they came from
+        writer.write_u8(self.get_out_of_jail_cards);
+        writer.write_bool(self.community_chest_jail_card);
+        writer.write_bool(self.chance_jail_card);
+
+        writer.write_bool(self.eliminated);
+
+        // Write dice round entries
+        writer.write_bool(self.dice_commitment.is_some());
+        writer.write_bytes(&self.dice_commitment.unwrap_or_default());
+        writer.write_bool(self.dice_secret.is_some());
+        writer.write_bytes(&self.dice_secret.unwrap_or_default());
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut current = 0;
-        
-        let name_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-        let name = String::from_utf8(src[current..current + name_len].to_vec())
-            .map_err(|_| ProgramError::InvalidAccountData)?;
-        current += name_len;
-        
-        let cash = u64::from_le_bytes(src[current..current + 8].try_into().unwrap());
-        current += 8;
-        
-        let position = src[current];
-        current += 1;
-        
-        let jail_turns = src[current];
-        current += 1;
-        
-        let properties_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-        let properties = src[current..current + properties_len].to_vec();
-        current += properties_len;
-        
-        let get_out_of_jail_cards = src[current];
-        
+        let mut reader = Reader::new(src);
+
+        let name_len = reader.read_len_u8(MAX_NAME_LEN)?;
+        let name = reader.read_string(name_len)?;
+        reader.skip(MAX_NAME_LEN - name_len)?;
+
+        let wallet = reader.read_pubkey()?;
+
+        let has_delegate = reader.read_bool()?;
+        let delegate = reader.read_pubkey()?;
+        let delegate = if has_delegate { Some(delegate) } else { None };
+
+        let cash = reader.read_u64()?;
+
+        let position = reader.read_u8()?;
+
+        let jail_turns = reader.read_u8()?;
+
+        let properties_len = reader.read_len_u8(MAX_PLAYER_PROPERTIES)?;
+        let properties = reader.read_slice(properties_len)?.to_vec();
+        reader.skip(MAX_PLAYER_PROPERTIES - properties_len)?;
+
+        let get_out_of_jail_cards = reader.read_u8()?;
+        let community_chest_jail_card = reader.read_bool()?;
+        let chance_jail_card = reader.read_bool()?;
+
+        let eliminated = reader.read_bool()?;
+
+        let has_commitment = reader.read_bool()?;
+        let dice_commitment = reader.read_array::<32>()?;
+        let dice_commitment = if has_commitment { Some(dice_commitment) } else { None };
+
+        let has_secret = reader.read_bool()?;
+        let dice_secret = reader.read_array::<32>()?;
+        let dice_secret = if has_secret { Some(dice_secret) } else { None };
+
         Ok(Player {
             name,
+            wallet,
+            delegate,
             cash,
             position,
             jail_turns,
             properties,
             get_out_of_jail_cards,
+            community_chest_jail_card,
+            chance_jail_card,
+            eliminated,
+            dice_commitment,
+            dice_secret,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum GameStatus {
+    /// Lobby is accepting players
+    Open,
+    Started,
+    Finished,
+}
+
+impl Pack for GameStatus {
+    const LEN: usize = 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        Writer::new(dst).write_u8(match self {
+            GameStatus::Open => 0,
+            GameStatus::Started => 1,
+            GameStatus::Finished => 2,
+        });
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Ok(match Reader::new(src).read_u8()? {
+            0 => GameStatus::Open,
+            1 => GameStatus::Started,
+            2 => GameStatus::Finished,
+            _ => return Err(ProgramError::InvalidAccountData),
+        })
+    }
+}
+
+/// Where the current player is within their turn
+#[derive(Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum TurnPhase {
+    /// The current player has yet to roll, or has earned another roll with doubles
+    AwaitingRoll,
+    /// The tile the player landed on has a pending action to resolve
+    ResolvingTile,
+    /// The roll is done; the player may build, trade or end the turn
+    PostRoll,
+    /// An unpaid debt must be settled or written off through bankruptcy
+    AwaitingDebtResolution,
+    /// A winner has been declared
+    GameOver,
+}
+
+impl Pack for TurnPhase {
+    const LEN: usize = 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        Writer::new(dst).write_u8(match self {
+            TurnPhase::AwaitingRoll => 0,
+            TurnPhase::ResolvingTile => 1,
+            TurnPhase::PostRoll => 2,
+            TurnPhase::AwaitingDebtResolution => 3,
+            TurnPhase::GameOver => 4,
+        });
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Ok(match Reader::new(src).read_u8()? {
+            0 => TurnPhase::AwaitingRoll,
+            1 => TurnPhase::ResolvingTile,
+            2 => TurnPhase::PostRoll,
+            3 => TurnPhase::AwaitingDebtResolution,
+            4 => TurnPhase::GameOver,
+            _ => return Err(ProgramError::InvalidAccountData),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Auction {
+    pub property_index: u8,
+    pub highest_bid: u64,
+    /// Index into `Game::players` of the leading bidder
+    pub highest_bidder: Option<u8>,
+    /// Last slot at which bids are accepted
+    pub deadline_slot: u64,
+}
+
+impl Pack for Auction {
+    const LEN: usize = 1 + 8 + 2 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        writer.write_u8(self.property_index);
+        writer.write_u64(self.highest_bid);
+        writer.write_bool(self.highest_bidder.is_some());
+        writer.write_u8(self.highest_bidder.unwrap_or(0));
+        writer.write_u64(self.deadline_slot);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        let property_index = reader.read_u8()?;
+        let highest_bid = reader.read_u64()?;
+        let has_bidder = reader.read_bool()?;
+        let highest_bidder = reader.read_u8()?;
+        Ok(Auction {
+            property_index,
+            highest_bid,
+            highest_bidder: if has_bidder { Some(highest_bidder) } else { None },
+            deadline_slot: reader.read_u64()?,
+        })
+    }
+}
+
+/// Stage of a commit–reveal dice round
+#[derive(Debug, Clone, Copy, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum DicePhase {
+    /// Players submit hashes of their secrets
+    Commit,
+    /// Players disclose the secrets behind their hashes
+    Reveal,
+}
+
+/// Commit–reveal round producing the current player's dice
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct DiceRound {
+    pub phase: DicePhase,
+    /// Last slot at which the current phase accepts commitments or reveals. A reveal phase
+    /// always ends `DICE_REVEAL_SLOTS` after the slot its commit phase closed on.
+    pub deadline_slot: u64,
+}
+
+impl Pack for DiceRound {
+    const LEN: usize = 1 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        writer.write_u8(match self.phase {
+            DicePhase::Commit => 0,
+            DicePhase::Reveal => 1,
+        });
+        writer.write_u64(self.deadline_slot);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        let phase = match reader.read_u8()? {
+            0 => DicePhase::Commit,
+            1 => DicePhase::Reveal,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(DiceRound {
+            phase,
+            deadline_slot: reader.read_u64()?,
+        })
+    }
+}
+
+/// Randomness backend a game draws its dice and cards from, chosen at creation.
+/// `Pack` pads it to a fixed slot in the game account; instructions carry the bare Borsh encoding.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum RandomnessKind {
+    /// Hash of the most recent slot from the SlotHashes sysvar
+    SlotHash,
+    /// Output posted by the `oracle` key in answer to a randomness request. Nothing on-chain
+    /// proves how the output was made, so every player must trust the oracle: one that answers
+    /// after seeing the revealed dice secrets, or that colludes with a player, can pick the roll.
+    Oracle { oracle: Pubkey },
+    /// Fixed seed, fully predictable; for tests and local play only
+    Seeded { seed: u64 },
+}
+
+impl Pack for RandomnessKind {
+    const LEN: usize = 1 + 32;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        match self {
+            RandomnessKind::SlotHash => {
+                writer.write_u8(0);
+            }
+            RandomnessKind::Oracle { oracle } => {
+                writer.write_u8(1);
+                writer.write_pubkey(oracle);
+            }
+            RandomnessKind::Seeded { seed } => {
+                writer.write_u8(2);
+                writer.write_u64(*seed);
+            }
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        Ok(match reader.read_u8()? {
+            0 => RandomnessKind::SlotHash,
+            1 => RandomnessKind::Oracle {
+                oracle: reader.read_pubkey()?,
+            },
+            2 => RandomnessKind::Seeded {
+                seed: reader.read_u64()?,
+            },
+            _ => return Err(ProgramError::InvalidAccountData),
+        })
+    }
+}
+
+/// House rules chosen when the game is created. Each amount may be at most `MAX_RULE_AMOUNT`.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct GameRules {
+    /// Cash each player joins with
+    pub starting_cash: u64,
+    /// Paid to a player passing GO
+    pub go_salary: u64,
+    /// Bail to leave jail early, and the fine forced after the last failed doubles attempt
+    pub jail_fine: u64,
+    /// Taxes, card payments and penalties fill a pot paid to whoever lands on Free Parking
+    pub free_parking_jackpot: bool,
+    /// Landing exactly on GO pays twice the salary
+    pub double_go_salary: bool,
+    /// Owners sitting in jail collect no rent
+    pub no_rent_in_jail: bool,
+    /// A property the player declines to buy must go to auction; otherwise it stays with the bank
+    pub auction_on_decline: bool,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            starting_cash: STARTING_CASH,
+            go_salary: GO_SALARY,
+            jail_fine: JAIL_FINE,
+            free_parking_jackpot: false,
+            double_go_salary: false,
+            no_rent_in_jail: false,
+            auction_on_decline: true,
+        }
+    }
+}
+
+impl Pack for GameRules {
+    const LEN: usize = 8 + 8 + 8 + 1 + 1 + 1 + 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        writer.write_u64(self.starting_cash);
+        writer.write_u64(self.go_salary);
+        writer.write_u64(self.jail_fine);
+        writer.write_bool(self.free_parking_jackpot);
+        writer.write_bool(self.double_go_salary);
+        writer.write_bool(self.no_rent_in_jail);
+        writer.write_bool(self.auction_on_decline);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        Ok(GameRules {
+            starting_cash: reader.read_u64()?,
+            go_salary: reader.read_u64()?,
+            jail_fine: reader.read_u64()?,
+            free_parking_jackpot: reader.read_bool()?,
+            double_go_salary: reader.read_bool()?,
+            no_rent_in_jail: reader.read_bool()?,
+            auction_on_decline: reader.read_bool()?,
+        })
+    }
+}
+
+/// Payment a player could not cover, blocking play until it is paid or they go bankrupt
+#[derive(Debug, Clone, PartialEq)]
+pub struct Debt {
+    /// Index into `Game::players` of the player who owes
+    pub debtor: u8,
+    /// Index into `Game::players` of the player owed, or `None` for the bank
+    pub creditor: Option<u8>,
+    pub amount: u64,
+}
+
+impl Pack for Debt {
+    const LEN: usize = 1 + 2 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+        writer.write_u8(self.debtor);
+        writer.write_bool(self.creditor.is_some());
+        writer.write_u8(self.creditor.unwrap_or(0));
+        writer.write_u64(self.amount);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+        let debtor = reader.read_u8()?;
+        let has_creditor = reader.read_bool()?;
+        let creditor = reader.read_u8()?;
+        Ok(Debt {
+            debtor,
+            creditor: if has_creditor { Some(creditor) } else { None },
+            amount: reader.read_u64()?,
+        })
+    }
+}
+
+/// What the current player must resolve on the tile they landed on before the turn can end
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PendingAction {
+    /// Pay rent for `property_index` to the player at index `owner`; `card_rent` marks the
+    /// special rent of an advance-to-nearest card
+    PayRent { property_index: u8, owner: u8, card_rent: bool },
+    /// Draw the top card of the given deck
+    DrawCard { deck_type: DeckType },
+    /// Buy the unowned property at `property_index` or put it up for auction
+    BuyProperty { property_index: u8 },
+}
+
+impl Pack for PendingAction {
+    const LEN: usize = 1 + 3;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let (tag, a, b, c) = match *self {
+            PendingAction::PayRent { property_index, owner, card_rent } => (0, property_index, owner, card_rent as u8),
+            PendingAction::DrawCard { deck_type } => (1, match deck_type {
+                DeckType::CommunityChest => 0,
+                DeckType::Chance => 1,
+            }, 0, 0),
+            PendingAction::BuyProperty { property_index } => (2, property_index, 0, 0),
+        };
+        Writer::new(dst).write_bytes(&[tag, a, b, c]);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let [tag, a, b, c] = Reader::new(src).read_array()?;
+        Ok(match tag {
+            0 => PendingAction::PayRent { property_index: a, owner: b, card_rent: c != 0 },
+            1 => PendingAction::DrawCard {
+                deck_type: match a {
+                    0 => DeckType::CommunityChest,
+                    1 => DeckType::Chance,
+                    _ => return Err(ProgramError::InvalidAccountData),
+                },
+            },
+            2 => PendingAction::BuyProperty { property_index: a },
+            _ => return Err(ProgramError::InvalidAccountData),
+        })
+    }
+}
+
+/// One side of a trade between two players.
+/// `Pack` writes the properties into a fixed slot of the trade offer account; instructions
+/// carry them as a Borsh vector.
+#[derive(Debug, Clone, Default, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct TradeTerms {
+    /// Board indices of the properties changing hands
+    pub properties: Vec<u8>,
+    pub cash: u64,
+    pub jail_cards: u8,
+}
+
+impl Pack for TradeTerms {
+    const LEN: usize = 4 + MAX_TRADE_PROPERTIES + 8 + 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+
+        // Properties are written into a fixed-size slot
+        writer.write_u32(self.properties.len() as u32);
+        writer.write_bytes(&self.properties);
+        writer.write_zeros(MAX_TRADE_PROPERTIES - self.properties.len());
+
+        writer.write_u64(self.cash);
+        writer.write_u8(self.jail_cards);
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = Reader::new(src);
+
+        let properties_len = reader.read_len_u32(MAX_TRADE_PROPERTIES)?;
+        let properties = reader.read_slice(properties_len)?.to_vec();
+        reader.skip(MAX_TRADE_PROPERTIES - properties_len)?;
+
+        let cash = reader.read_u64()?;
+        let jail_cards = reader.read_u8()?;
+
+        Ok(TradeTerms {
+            properties,
+            cash,
+            jail_cards,
         })
     }
 }
@@ -383,81 +1092,475 @@ pub struct Game {
     pub current_player: u8,
     pub free_parking: u64,
     pub initialized: bool,
+    /// Wallet that created the game and may start it
+    pub creator: Pubkey,
+    pub status: GameStatus,
+    /// Seats available in the lobby
+    pub max_players: u8,
+    /// Auction for a declined property, if one is running
+    pub auction: Option<Auction>,
+    /// Outstanding payment the debtor must settle before play continues
+    pub debt: Option<Debt>,
+    /// Dice total of the most recent move, used for utility rent
+    pub last_roll: u8,
+    /// Houses still available from the bank
+    pub houses_remaining: u8,
+    /// Hotels still available from the bank
+    pub hotels_remaining: u8,
+    /// Doubles the current player has rolled in a row this turn
+    pub doubles_rolled: u8,
+    /// Commit–reveal round in progress, if any
+    pub dice_round: Option<DiceRound>,
+    /// Dice settled by the last round, waiting for the current player to move
+    pub rolled_dice: Option<(u8, u8)>,
+    /// Where dice and card draws get their randomness
+    pub randomness: RandomnessKind,
+    /// Count of random draws so far; keeps each draw distinct and ties randomness requests to one draw
+    pub randomness_nonce: u64,
+    /// Both dice of the most recent move
+    pub last_dice: (u8, u8),
+    /// Whether the most recent move was a double
+    pub last_doubles: bool,
+    /// Step of the current player's turn, deciding which instructions are accepted
+    pub turn_phase: TurnPhase,
+    /// Tile action the current player still owes from their last move
+    pub pending_action: Option<PendingAction>,
+    /// House rules this game is played under
+    pub rules: GameRules,
+    /// Properties a bankruptcy returned to the bank, waiting to be auctioned
+    pub auction_queue: Vec<u8>,
+    /// Oracle request opened for the current draw; no other request's output is accepted
+    pub randomness_request: Option<Pubkey>,
+    /// Community Chest deck account, bound to the game by its first draw
+    pub community_chest_deck: Option<Pubkey>,
+    /// Chance deck account, bound to the game by its first draw
+    pub chance_deck: Option<Pubkey>,
 }
 
-impl Pack for Game {
-    const LEN: usize = 8192; // Large enough for game state
+/// Turn state the rules engine reads and changes. Both the unpacked `Game` and the zero-copy
+/// `GameAccountData` implement it, so instructions run the same rules whichever they load.
+pub trait GameState: Board {
+    type Player: PlayerState;
+
+    /// Seats taken in the game
+    fn players(&self) -> &[Self::Player];
+    fn players_mut(&mut self) -> &mut [Self::Player];
+    fn current_player(&self) -> u8;
+    fn set_current_player(&mut self, current_player: u8);
+    fn status(&self) -> Result<GameStatus, ProgramError>;
+    fn turn_phase(&self) -> Result<TurnPhase, ProgramError>;
+    fn set_turn_phase(&mut self, turn_phase: TurnPhase);
+    fn rules(&self) -> Result<GameRules, ProgramError>;
+    fn has_debt(&self) -> bool;
+    fn set_debt(&mut self, debt: Option<Debt>);
+    fn has_pending_action(&self) -> bool;
+    fn set_pending_action(&mut self, pending_action: Option<PendingAction>);
+    fn set_rolled_dice(&mut self, dice: Option<(u8, u8)>);
+    fn free_parking(&self) -> u64;
+    fn set_free_parking(&mut self, free_parking: u64);
+    fn last_doubles(&self) -> bool;
+    fn doubles_rolled(&self) -> u8;
+    fn set_doubles_rolled(&mut self, doubles_rolled: u8);
+
+    /// Give the property at `property_index` to seat `owner`, or back to the bank
+    fn set_owner(&mut self, property_index: u8, owner: Option<u8>);
+
+    /// Take `property_index` off the queue of bank properties waiting for an auction
+    fn remove_from_auction_queue(&mut self, property_index: u8);
+}
+
+impl Board for Game {
+    fn tile_count(&self) -> usize {
+        self.board.tile_count()
+    }
+
+    fn tile(&self, index: u8) -> Option<TileView<'_>> {
+        self.board.tile(index)
+    }
+}
+
+impl GameState for Game {
+    type Player = Player;
+
+    fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    fn players_mut(&mut self) -> &mut [Player] {
+        &mut self.players
+    }
+
+    fn current_player(&self) -> u8 {
+        self.current_player
+    }
+
+    fn set_current_player(&mut self, current_player: u8) {
+        self.current_player = current_player;
+    }
+
+    fn status(&self) -> Result<GameStatus, ProgramError> {
+        Ok(self.status)
+    }
+
+    fn turn_phase(&self) -> Result<TurnPhase, ProgramError> {
+        Ok(self.turn_phase)
+    }
+
+    fn set_turn_phase(&mut self, turn_phase: TurnPhase) {
+        self.turn_phase = turn_phase;
+    }
+
+    fn rules(&self) -> Result<GameRules, ProgramError> {
+        Ok(self.rules.clone())
+    }
+
+    fn has_debt(&self) -> bool {
+        self.debt.is_some()
+    }
+
+    fn set_debt(&mut self, debt: Option<Debt>) {
+        self.debt = debt;
+    }
+
+    fn has_pending_action(&self) -> bool {
+        self.pending_action.is_some()
+    }
+
+    fn set_pending_action(&mut self, pending_action: Option<PendingAction>) {
+        self.pending_action = pending_action;
+    }
+
+    fn set_rolled_dice(&mut self, dice: Option<(u8, u8)>) {
+        self.rolled_dice = dice;
+    }
+
+    fn free_parking(&self) -> u64 {
+        self.free_parking
+    }
+
+    fn set_free_parking(&mut self, free_parking: u64) {
+        self.free_parking = free_parking;
+    }
+
+    fn last_doubles(&self) -> bool {
+        self.last_doubles
+    }
+
+    fn doubles_rolled(&self) -> u8 {
+        self.doubles_rolled
+    }
+
+    fn set_doubles_rolled(&mut self, doubles_rolled: u8) {
+        self.doubles_rolled = doubles_rolled;
+    }
+
+    fn set_owner(&mut self, property_index: u8, owner: Option<u8>) {
+        if let Some(TileType::Property(property)) = self.board.get_mut(property_index as usize) {
+            property.owner = owner;
+        }
+    }
+
+    fn remove_from_auction_queue(&mut self, property_index: u8) {
+        self.auction_queue.retain(|&index| index != property_index);
+    }
+}
+
+impl Pack for Game {
+    const LEN: usize = 1 + MAX_PLAYERS as usize * Player::LEN + // players
+        1 + BOARD_SIZE * TileState::LEN + // board id and tile state
+        1 + // current_player
+        8 + // free_parking
+        1 + // initialized
+        32 + // creator
+        1 + // status
+        1 + // max_players
+        1 + Auction::LEN + // auction
+        1 + Debt::LEN + // debt
+        1 + // last_roll
+        2 + // houses_remaining, hotels_remaining
+        1 + // doubles_rolled
+        1 + DiceRound::LEN + // dice_round
+        3 + // rolled_dice
+        RandomnessKind::LEN + // randomness
+        8 + // randomness_nonce
+        2 + // last_dice
+        1 + // last_doubles
+        TurnPhase::LEN + // turn_phase
+        1 + PendingAction::LEN + // pending_action
+        GameRules::LEN + // rules
+        1 + MAX_PLAYER_PROPERTIES + // auction_queue
+        1 + 32 + // randomness_request
+        2 * (1 + 32); // community_chest_deck, chance_deck
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut writer = Writer::new(dst);
+
+        // Write player count and a fixed slot per seat
+        writer.write_u8(self.players.len() as u8);
+        for seat in 0..MAX_PLAYERS as usize {
+            match self.players.get(seat) {
+                Some(player) => writer.write_pack(player),
+                None => writer.write_zeros(Player::LEN),
+            }
+        }
+
+        // Write board id and the mutable state of each tile; the static board is not stored
+        writer.write_u8(if self.board.is_empty() { BOARD_NONE } else { BOARD_STANDARD });
+        for index in 0..BOARD_SIZE {
+            let state = match self.board.get(index) {
+                Some(TileType::Property(property)) => TileState {
+                    owner: property.owner,
+                    houses: property.houses,
+                    mortgaged: property.mortgaged,
+                },
+                _ => TileState::default(),
+            };
+            writer.write_pack(&state);
+        }
+
+        // Write game state
+        writer.write_u8(self.current_player);
+        writer.write_u64(self.free_parking);
+        writer.write_bool(self.initialized);
+
+        // Write lobby state
+        writer.write_pubkey(&self.creator);
+        writer.write_pack(&self.status);
+        writer.write_u8(self.max_players);
+
+        // Write auction
+        writer.write_bool(self.auction.is_some());
+        match &self.auction {
+            Some(auction) => writer.write_pack(auction),
+            None => writer.write_zeros(Auction::LEN),
+        }
+
+        // Write debt
+        writer.write_bool(self.debt.is_some());
+        match &self.debt {
+            Some(debt) => writer.write_pack(debt),
+            None => writer.write_zeros(Debt::LEN),
+        }
+
+        writer.write_u8(self.last_roll);
+
+        // Write bank building supply
+        writer.write_u8(self.houses_remaining);
+        writer.write_u8(self.hotels_remaining);
+
+        writer.write_u8(self.doubles_rolled);
+
+        // Write dice round
+        writer.write_bool(self.dice_round.is_some());
+        match &self.dice_round {
+            Some(dice_round) => writer.write_pack(dice_round),
+            None => writer.write_zeros(DiceRound::LEN),
+        }
+
+        writer.write_bool(self.rolled_dice.is_some());
+        let (die1, die2) = self.rolled_dice.unwrap_or_default();
+        writer.write_u8(die1);
+        writer.write_u8(die2);
 
-    fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut current = 0;
-        
-        // Write board length and tiles
-        dst[current..current + 4].copy_from_slice(&(self.board.len() as u32).to_le_bytes());
-        current += 4;
-        for tile in &self.board {
-            tile.pack_into_slice(&mut dst[current..current + TileType::LEN]);
-            current += TileType::LEN;
+        // Write randomness
+        writer.write_pack(&self.randomness);
+        writer.write_u64(self.randomness_nonce);
+
+        // Write last move
+        writer.write_u8(self.last_dice.0);
+        writer.write_u8(self.last_dice.1);
+        writer.write_bool(self.last_doubles);
+
+        // Write turn phase
+        writer.write_pack(&self.turn_phase);
+
+        // Write pending action
+        writer.write_bool(self.pending_action.is_some());
+        match &self.pending_action {
+            Some(pending_action) => writer.write_pack(pending_action),
+            None => writer.write_zeros(PendingAction::LEN),
         }
-        
-        // Write players length and players
-        dst[current..current + 4].copy_from_slice(&(self.players.len() as u32).to_le_bytes());
-        current += 4;
-        for player in &self.players {
-            player.pack_into_slice(&mut dst[current..current + Player::LEN]);
-            current += Player::LEN;
+
+        // Write house rules
+        writer.write_pack(&self.rules);
+
+        // Write properties waiting for a bank auction
+        writer.write_u8(self.auction_queue.len() as u8);
+        writer.write_bytes(&self.auction_queue);
+        writer.write_zeros(MAX_PLAYER_PROPERTIES - self.auction_queue.len());
+
+        // Write the open randomness request
+        writer.write_bool(self.randomness_request.is_some());
+        writer.write_pubkey(&self.randomness_request.unwrap_or_default());
+
+        // Write the bound card decks
+        for deck in [&self.community_chest_deck, &self.chance_deck] {
+            writer.write_bool(deck.is_some());
+            writer.write_pubkey(&deck.unwrap_or_default());
         }
-        
-        // Write game state
-        dst[current] = self.current_player;
-        current += 1;
-        dst[current..current + 8].copy_from_slice(&self.free_parking.to_le_bytes());
-        current += 8;
-        dst[current] = self.initialized as u8;
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut current = 0;
-        
-        let board_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-        let mut board = Vec::with_capacity(board_len);
-        for _ in 0..board_len {
-            board.push(TileType::unpack_from_slice(&src[current..current + TileType::LEN])?);
-            current += TileType::LEN;
+        let mut reader = Reader::new(src);
+
+        let players_len = reader.read_len_u8(MAX_PLAYERS as usize)?;
+        let mut players: Vec<Player> = Vec::with_capacity(players_len);
+        for seat in 0..MAX_PLAYERS as usize {
+            if seat < players_len {
+                players.push(reader.read_pack()?);
+            } else {
+                reader.skip(Player::LEN)?;
+            }
         }
-        
-        let players_len = u32::from_le_bytes(src[current..current + 4].try_into().unwrap()) as usize;
-        current += 4;
-        let mut players = Vec::with_capacity(players_len);
-        for _ in 0..players_len {
-            players.push(Player::unpack_from_slice(&src[current..current + Player::LEN])?);
-            current += Player::LEN;
+
+        let mut board = match reader.read_u8()? {
+            BOARD_NONE => Vec::new(),
+            BOARD_STANDARD => create_board(),
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        for index in 0..BOARD_SIZE {
+            let state: TileState = reader.read_pack()?;
+            if let Some(TileType::Property(property)) = board.get_mut(index) {
+                if state.owner.is_some_and(|owner| owner as usize >= players.len()) {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                property.owner = state.owner;
+                property.houses = state.houses;
+                property.mortgaged = state.mortgaged;
+            }
         }
-        
-        let current_player = src[current];
-        current += 1;
-        
-        let free_parking = u64::from_le_bytes(src[current..current + 8].try_into().unwrap());
-        current += 8;
-        
-        let initialized = src[current] != 0;
-        
+
+        let current_player = reader.read_u8()?;
+
+        let free_parking = reader.read_u64()?;
+
+        let initialized = reader.read_bool()?;
+
+        let creator = reader.read_pubkey()?;
+
+        let status = reader.read_pack()?;
+
+        let max_players = reader.read_u8()?;
+
+        // Optional fields keep their slot either way; an unset one is skipped, not decoded
+        let has_auction = reader.read_bool()?;
+        let auction = if has_auction {
+            Some(reader.read_pack()?)
+        } else {
+            reader.skip(Auction::LEN)?;
+            None
+        };
+
+        let has_debt = reader.read_bool()?;
+        let debt = if has_debt {
+            Some(reader.read_pack()?)
+        } else {
+            reader.skip(Debt::LEN)?;
+            None
+        };
+
+        let last_roll = reader.read_u8()?;
+
+        let houses_remaining = reader.read_u8()?;
+        let hotels_remaining = reader.read_u8()?;
+
+        let doubles_rolled = reader.read_u8()?;
+
+        let has_dice_round = reader.read_bool()?;
+        let dice_round = if has_dice_round {
+            Some(reader.read_pack()?)
+        } else {
+            reader.skip(DiceRound::LEN)?;
+            None
+        };
+
+        let has_rolled_dice = reader.read_bool()?;
+        let rolled_dice = (reader.read_u8()?, reader.read_u8()?);
+        let rolled_dice = if has_rolled_dice { Some(rolled_dice) } else { None };
+
+        let randomness = reader.read_pack()?;
+        let randomness_nonce = reader.read_u64()?;
+
+        let last_dice = (reader.read_u8()?, reader.read_u8()?);
+        let last_doubles = reader.read_bool()?;
+        let turn_phase = reader.read_pack()?;
+
+        let has_pending_action = reader.read_bool()?;
+        let pending_action = if has_pending_action {
+            Some(reader.read_pack()?)
+        } else {
+            reader.skip(PendingAction::LEN)?;
+            None
+        };
+
+        let rules = reader.read_pack()?;
+
+        let auction_queue_len = reader.read_len_u8(MAX_PLAYER_PROPERTIES)?;
+        let auction_queue = reader.read_slice(auction_queue_len)?.to_vec();
+        reader.skip(MAX_PLAYER_PROPERTIES - auction_queue_len)?;
+
+        let has_randomness_request = reader.read_bool()?;
+        let randomness_request = reader.read_pubkey()?;
+        let randomness_request = if has_randomness_request { Some(randomness_request) } else { None };
+
+        let has_community_chest_deck = reader.read_bool()?;
+        let community_chest_deck = reader.read_pubkey()?;
+        let community_chest_deck = if has_community_chest_deck { Some(community_chest_deck) } else { None };
+        let has_chance_deck = reader.read_bool()?;
+        let chance_deck = reader.read_pubkey()?;
+        let chance_deck = if has_chance_deck { Some(chance_deck) } else { None };
+
         Ok(Game {
             board,
             players,
             current_player,
             free_parking,
             initialized,
+            creator,
+            status,
+            max_players,
+            auction,
+            debt,
+            last_roll,
+            houses_remaining,
+            hotels_remaining,
+            doubles_rolled,
+            dice_round,
+            rolled_dice,
+            randomness,
+            randomness_nonce,
+            last_dice,
+            last_doubles,
+            turn_phase,
+            pending_action,
+            rules,
+            auction_queue,
+            randomness_request,
+            community_chest_deck,
+            chance_deck,
         })
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub enum Card {
     CollectMoney(u64),
     PayMoney(u64),
+    /// Advance to an absolute board position, collecting GO salary when passing it, then resolve that tile
     Move(u8),
     GetOutOfJail,
+    /// Go directly to jail without passing GO
+    GoToJail,
+    /// Go back a number of spaces, then resolve that tile; never passes GO
+    MoveBack(u8),
+    /// Advance to the next railroad or utility; an owned one charges the card's special rent
+    AdvanceToNearest(TileKind),
+    /// Pay the bank for every house and every hotel the player owns
+    PayPerHouseAndHotel { house: u64, hotel: u64 },
+    /// Every other player still in the game pays the drawer
+    CollectFromEachPlayer(u64),
+    /// The drawer pays every other player still in the game
+    PayEachPlayer(u64),
 }
 
 #[cfg(test)]
@@ -504,12 +1607,11 @@ mod tests {
 
     #[test]
     fn test_property_pack() {
-        use solana_program::pubkey::Pubkey;
-
         // Test case 1: Property without owner
         let property1 = Property {
             name: String::from("Boardwalk"),
             color: Color::DarkBlue,
+            kind: TileKind::Street,
             cost: 400,
             rent: vec![50, 200, 600, 1400, 1700, 2000],
             house_cost: 200,
@@ -517,22 +1619,41 @@ mod tests {
             house_rent: vec![200, 600, 1400, 1700, 2000],
             owner: None,
             houses: 0,
+            mortgaged: false,
         };
 
         // Test case 2: Property with owner
-        let owner = Pubkey::new_unique();
+        let owner = 3;
         let property2 = Property {
             name: String::from("Park Place"),
             color: Color::DarkBlue,
+            kind: TileKind::Street,
             cost: 350,
             rent: vec![35, 175, 500, 1100, 1300, 1500],
             house_cost: 200,
             hotel_cost: 200,
+            house_rent: vec![175, 500, 1100, 1300, 1500],
             owner: Some(owner),
             houses: 2,
+            mortgaged: false,
+        };
+
+        // Test case 3: Mortgaged property
+        let property3 = Property {
+            name: String::from("Mediterranean Avenue"),
+            color: Color::Brown,
+            kind: TileKind::Street,
+            cost: 60,
+            rent: vec![2, 10, 30, 90, 160, 250],
+            house_cost: 50,
+            hotel_cost: 50,
+            house_rent: vec![10, 30, 90, 160, 250],
+            owner: Some(owner),
+            houses: 0,
+            mortgaged: true,
         };
 
-        for property in [property1, property2] {
+        for property in [property1, property2, property3] {
             let mut packed = vec![0; Property::LEN];
             property.pack_into_slice(&mut packed);
 
@@ -540,12 +1661,14 @@ mod tests {
             let unpacked = Property::unpack_from_slice(&packed).unwrap();
             assert_eq!(unpacked.name, property.name);
             assert_eq!(unpacked.color, property.color);
+            assert_eq!(unpacked.kind, property.kind);
             assert_eq!(unpacked.cost, property.cost);
             assert_eq!(unpacked.rent, property.rent);
             assert_eq!(unpacked.house_cost, property.house_cost);
             assert_eq!(unpacked.hotel_cost, property.hotel_cost);
             assert_eq!(unpacked.owner, property.owner);
             assert_eq!(unpacked.houses, property.houses);
+            assert_eq!(unpacked.mortgaged, property.mortgaged);
         }
 
         // Test case 3: Error case - invalid UTF-8 in name
@@ -559,20 +1682,25 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
 
-        // Verify LEN is sufficient for maximum property data
+        // Verify LEN fits the largest property exactly
         let max_property = Property {
-            name: "Very Long Property Name That Tests The Size Limit".to_string(),
+            name: "N".repeat(MAX_NAME_LEN),
             color: Color::DarkBlue,
+            kind: TileKind::Street,
             cost: u64::MAX,
-            rent: vec![u64::MAX; 10], // Large rent array
+            rent: vec![u64::MAX; MAX_RENT_LEVELS],
             house_cost: u64::MAX,
             hotel_cost: u64::MAX,
-            owner: Some(Pubkey::new_unique()),
+            house_rent: vec![u64::MAX; MAX_HOUSE_RENT_LEVELS],
+            owner: Some(MAX_PLAYERS - 1),
             houses: u8::MAX,
+            mortgaged: true,
         };
 
         let mut max_packed = vec![0; Property::LEN];
         max_property.pack_into_slice(&mut max_packed);
+        let short = std::panic::catch_unwind(|| max_property.pack_into_slice(&mut [0; Property::LEN - 1]));
+        assert!(short.is_err(), "Property::LEN has slack");
         
         // Verify we can unpack it successfully
         let unpacked = Property::unpack_from_slice(&max_packed).unwrap();
@@ -584,6 +1712,7 @@ mod tests {
         let property = Property {
             name: String::from("Boardwalk"),
             color: Color::DarkBlue,
+            kind: TileKind::Street,
             cost: 400,
             rent: vec![50, 200, 600, 1400, 1700, 2000],
             house_cost: 200,
@@ -591,6 +1720,7 @@ mod tests {
             house_rent: vec![200, 600, 1400, 1700, 2000],
             owner: None,
             houses: 0,
+            mortgaged: false,
         };
 
         // Test that as_property returns Some containing a reference to the same property
@@ -660,12 +1790,11 @@ mod tests {
 
     #[test]
     fn test_tile_type_as_property() {
-        use solana_program::pubkey::Pubkey;
-
         // Create a test property
         let property = Property {
             name: String::from("Boardwalk"),
             color: Color::DarkBlue,
+            kind: TileKind::Street,
             cost: 400,
             rent: vec![50, 200, 600, 1400, 1700, 2000],
             house_cost: 200,
@@ -673,6 +1802,7 @@ mod tests {
             house_rent: vec![200, 600, 1400, 1700, 2000],
             owner: None,
             houses: 0,
+            mortgaged: false,
         };
 
         // Test Property variant
@@ -701,6 +1831,7 @@ mod tests {
         let property = Property {
             name: String::from("Boardwalk"),
             color: Color::DarkBlue,
+            kind: TileKind::Street,
             cost: 400,
             rent: vec![50, 200, 600, 1400, 1700, 2000],
             house_cost: 200,
@@ -708,6 +1839,7 @@ mod tests {
             house_rent: vec![200, 600, 1400, 1700, 2000],
             owner: None,
             houses: 0,
+            mortgaged: false,
         };
         let tile_property = TileType::Property(property.clone());
         let mut packed = vec![0; TileType::LEN];
@@ -740,7 +1872,7 @@ mod tests {
 
         // Test Special variant
         let special = SpecialTile::Go;
-        let tile_special = TileType::Special(special);
+        let tile_special = TileType::Special(special.clone());
         let mut packed = vec![0; TileType::LEN];
         tile_special.pack_into_slice(&mut packed);
 
@@ -770,22 +1902,50 @@ mod tests {
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
 
         // Verify TileType::LEN is sufficient
-        assert!(TileType::LEN >= Property::LEN + 1, 
+        const _: () = assert!(TileType::LEN > Property::LEN,
             "TileType::LEN must accommodate Property variant plus discriminator");
-        assert!(TileType::LEN >= SpecialTile::LEN + 1,
+        const _: () = assert!(TileType::LEN > SpecialTile::LEN,
             "TileType::LEN must accommodate SpecialTile variant plus discriminator");
     }
 
+    #[test]
+    fn test_tile_state_pack() {
+        let states = vec![
+            TileState::default(),
+            TileState { owner: Some(0), houses: 3, mortgaged: false },
+            TileState { owner: Some(7), houses: 0, mortgaged: true },
+            TileState { owner: None, houses: 5, mortgaged: false },
+        ];
+
+        for state in states {
+            let mut packed = vec![0; TileState::LEN];
+            state.pack_into_slice(&mut packed);
+            assert_eq!(TileState::unpack_from_slice(&packed).unwrap(), state);
+        }
+
+        // An unowned tile is marked with u8::MAX rather than seat 0
+        let mut packed = vec![0; TileState::LEN];
+        TileState::default().pack_into_slice(&mut packed);
+        assert_eq!(packed, vec![u8::MAX, 0, 0]);
+    }
+
     #[test]
     fn test_player_pack() {
         // Test basic player
         let player = Player {
             name: String::from("John Doe"),
+            wallet: Pubkey::new_unique(),
+            delegate: Some(Pubkey::new_unique()),
             cash: 1500,
             position: 0,
             jail_turns: 0,
             properties: vec![1, 3, 6, 8, 9],
             get_out_of_jail_cards: 2,
+            community_chest_jail_card: true,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: Some([7; 32]),
+            dice_secret: Some([9; 32]),
         };
         let mut packed = vec![0; Player::LEN];
         player.pack_into_slice(&mut packed);
@@ -793,20 +1953,34 @@ mod tests {
         // Test unpacking
         let unpacked = Player::unpack_from_slice(&packed).unwrap();
         assert_eq!(unpacked.name, player.name);
+        assert_eq!(unpacked.wallet, player.wallet);
+        assert_eq!(unpacked.delegate, player.delegate);
         assert_eq!(unpacked.cash, player.cash);
         assert_eq!(unpacked.position, player.position);
         assert_eq!(unpacked.jail_turns, player.jail_turns);
         assert_eq!(unpacked.properties, player.properties);
         assert_eq!(unpacked.get_out_of_jail_cards, player.get_out_of_jail_cards);
+        assert!(unpacked.community_chest_jail_card);
+        assert!(!unpacked.chance_jail_card);
+        assert_eq!(unpacked.eliminated, player.eliminated);
+        assert_eq!(unpacked.dice_commitment, player.dice_commitment);
+        assert_eq!(unpacked.dice_secret, player.dice_secret);
 
         // Test player with maximum values
         let max_player = Player {
-            name: "A".repeat(256), // Large but reasonable name
+            name: "A".repeat(MAX_NAME_LEN), // Longest name a seat can hold
+            wallet: Pubkey::default(),
+            delegate: None,
             cash: u64::MAX,
             position: u8::MAX,
             jail_turns: u8::MAX,
-            properties: (0..40).collect(), // Maximum possible properties (all board spaces)
+            properties: (0..MAX_PLAYER_PROPERTIES as u8).collect(), // Every ownable tile
             get_out_of_jail_cards: u8::MAX,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: None,
+            dice_secret: None,
         };
         let mut packed = vec![0; Player::LEN];
         max_player.pack_into_slice(&mut packed);
@@ -823,65 +1997,136 @@ mod tests {
         // Test error case - invalid UTF-8 in name
         let mut invalid_packed = vec![0; Player::LEN];
         // Write invalid UTF-8 sequence length
-        invalid_packed[0..4].copy_from_slice(&(4u32).to_le_bytes());
+        invalid_packed[0] = 4;
         // Write invalid UTF-8 sequence
+        invalid_packed[1] = 0xFF;
+        invalid_packed[2] = 0xFF;
+        invalid_packed[3] = 0xFF;
         invalid_packed[4] = 0xFF;
-        invalid_packed[5] = 0xFF;
-        invalid_packed[6] = 0xFF;
-        invalid_packed[7] = 0xFF;
         let result = Player::unpack_from_slice(&invalid_packed);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
 
-        // Verify Player::LEN is sufficient for maximum data
-        let required_len = 4 + // name length
-            256 + // max reasonable name length
-            8 + // cash
-            1 + // position
-            1 + // jail_turns
-            4 + // properties length
-            40 + // max properties
-            1; // get_out_of_jail_cards
-        assert!(Player::LEN >= required_len, 
-            "Player::LEN must be at least {} for maximum data", required_len);
+        // Test error case - lengths past the fixed slots
+        let mut invalid_packed = vec![0; Player::LEN];
+        invalid_packed[0] = MAX_NAME_LEN as u8 + 1;
+        assert_eq!(Player::unpack_from_slice(&invalid_packed).unwrap_err(), ProgramError::InvalidAccountData);
+
+        let mut invalid_packed = packed.clone();
+        invalid_packed[1 + MAX_NAME_LEN + 32 + 33 + 8 + 2] = MAX_PLAYER_PROPERTIES as u8 + 1;
+        assert_eq!(Player::unpack_from_slice(&invalid_packed).unwrap_err(), ProgramError::InvalidAccountData);
+
+        // Every field has a fixed slot, so the packed size does not depend on the player
+        let short_player = Player { name: String::from("B"), properties: vec![], ..max_player.clone() };
+        let mut short_packed = vec![0; Player::LEN];
+        short_player.pack_into_slice(&mut short_packed);
+        let cash_offset = 1 + MAX_NAME_LEN + 32 + 33;
+        assert_eq!(&short_packed[cash_offset..cash_offset + 8], &packed[cash_offset..cash_offset + 8]);
+        assert_eq!(Player::LEN, 207);
     }
 
     #[test]
-    fn test_game_pack() {
-        // Create test property for board
-        let property = Property {
-            name: String::from("Boardwalk"),
-            color: Color::DarkBlue,
-            cost: 400,
-            rent: vec![50, 200, 600, 1400, 1700, 2000],
-            house_cost: 200,
-            hotel_cost: 200,
-            house_rent: vec![200, 600, 1400, 1700, 2000],
-            owner: None,
-            houses: 0,
+    fn test_player_is_controlled_by() {
+        let wallet = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let mut player = Player {
+            name: String::from("John Doe"),
+            wallet,
+            delegate: None,
+            cash: 1500,
+            position: 0,
+            jail_turns: 0,
+            properties: vec![],
+            get_out_of_jail_cards: 0,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: None,
+            dice_secret: None,
         };
+        assert!(player.is_controlled_by(&wallet));
+        assert!(!player.is_controlled_by(&delegate));
 
+        player.delegate = Some(delegate);
+        assert!(player.is_controlled_by(&wallet));
+        assert!(player.is_controlled_by(&delegate));
+        assert!(!player.is_controlled_by(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_game_pack() {
         // Create test player
         let player = Player {
             name: String::from("John Doe"),
+            wallet: Pubkey::default(),
+            delegate: None,
             cash: 1500,
             position: 0,
             jail_turns: 0,
             properties: vec![1, 3],
             get_out_of_jail_cards: 1,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: None,
+            dice_secret: None,
         };
 
+        // Only the mutable state of each tile is stored, so the board is the standard one
+        let mut board = create_board();
+        if let TileType::Property(property) = &mut board[1] {
+            property.owner = Some(0);
+            property.houses = 2;
+        }
+        if let TileType::Property(property) = &mut board[3] {
+            property.owner = Some(0);
+            property.mortgaged = true;
+        }
+
         // Test basic game
         let game = Game {
-            board: vec![
-                TileType::Special(SpecialTile::Go),
-                TileType::Property(property.clone()),
-                TileType::Special(SpecialTile::CommunityChest),
-            ],
+            board,
             players: vec![player.clone()],
             current_player: 0,
             free_parking: 500,
             initialized: true,
+            creator: Pubkey::new_unique(),
+            status: GameStatus::Open,
+            max_players: 8,
+            auction: None,
+            debt: Some(Debt {
+                debtor: 0,
+                creditor: None,
+                amount: 200,
+            }),
+            last_roll: 7,
+            houses_remaining: 20,
+            hotels_remaining: 3,
+            doubles_rolled: 2,
+            dice_round: Some(DiceRound {
+                phase: DicePhase::Reveal,
+                deadline_slot: 300,
+            }),
+            rolled_dice: Some((3, 5)),
+            randomness: RandomnessKind::Oracle { oracle: Pubkey::new_unique() },
+            randomness_nonce: 12,
+            last_dice: (4, 4),
+            last_doubles: true,
+            turn_phase: TurnPhase::ResolvingTile,
+            pending_action: Some(PendingAction::PayRent { property_index: 1, owner: 0, card_rent: false }),
+            rules: GameRules {
+                starting_cash: 2000,
+                go_salary: 400,
+                jail_fine: 100,
+                free_parking_jackpot: true,
+                double_go_salary: true,
+                no_rent_in_jail: true,
+                auction_on_decline: false,
+            },
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
         };
         let mut packed = vec![0; Game::LEN];
         game.pack_into_slice(&mut packed);
@@ -901,6 +2146,7 @@ mod tests {
                     assert_eq!(up.house_rent, og.house_rent);
                     assert_eq!(up.owner, og.owner);
                     assert_eq!(up.houses, og.houses);
+                    assert_eq!(up.mortgaged, og.mortgaged);
                 }
                 (TileType::Special(up), TileType::Special(og)) => {
                     assert_eq!(up, og);
@@ -918,33 +2164,78 @@ mod tests {
         assert_eq!(unpacked.current_player, game.current_player);
         assert_eq!(unpacked.free_parking, game.free_parking);
         assert_eq!(unpacked.initialized, game.initialized);
+        assert_eq!(unpacked.creator, game.creator);
+        assert_eq!(unpacked.status, game.status);
+        assert_eq!(unpacked.max_players, game.max_players);
+        assert_eq!(unpacked.auction, game.auction);
+        assert_eq!(unpacked.debt, game.debt);
+        assert_eq!(unpacked.last_roll, game.last_roll);
+        assert_eq!(unpacked.houses_remaining, game.houses_remaining);
+        assert_eq!(unpacked.hotels_remaining, game.hotels_remaining);
+        assert_eq!(unpacked.doubles_rolled, game.doubles_rolled);
+        assert_eq!(unpacked.dice_round, game.dice_round);
+        assert_eq!(unpacked.rolled_dice, game.rolled_dice);
+        assert_eq!(unpacked.randomness, game.randomness);
+        assert_eq!(unpacked.randomness_nonce, game.randomness_nonce);
+        assert_eq!(unpacked.last_dice, game.last_dice);
+        assert_eq!(unpacked.last_doubles, game.last_doubles);
+        assert_eq!(unpacked.turn_phase, game.turn_phase);
+        assert_eq!(unpacked.pending_action, game.pending_action);
+        assert_eq!(unpacked.rules, game.rules);
 
         // Test maximum size game
-        let max_property = Property {
-            name: "A".repeat(256),
-            color: Color::DarkBlue,
-            cost: u64::MAX,
-            rent: vec![u64::MAX; 6],
-            house_cost: u64::MAX,
-            hotel_cost: u64::MAX,
-            house_rent: vec![u64::MAX; 5],
-            owner: Some(solana_program::pubkey::Pubkey::new_unique()),
-            houses: 5,
-        };
-        let max_player = Player {
-            name: "A".repeat(256),
-            cash: u64::MAX,
-            position: u8::MAX,
-            jail_turns: u8::MAX,
-            properties: (0..40).collect(),
-            get_out_of_jail_cards: u8::MAX,
-        };
+        let max_players: Vec<Player> = (0..MAX_PLAYERS)
+            .map(|_| Player {
+                name: "A".repeat(MAX_NAME_LEN),
+                wallet: Pubkey::new_unique(),
+                delegate: Some(Pubkey::new_unique()),
+                cash: u64::MAX,
+                position: u8::MAX,
+                jail_turns: u8::MAX,
+                properties: (0..MAX_PLAYER_PROPERTIES as u8).collect(),
+                get_out_of_jail_cards: u8::MAX,
+                community_chest_jail_card: false,
+                chance_jail_card: false,
+                eliminated: false,
+                dice_commitment: Some([u8::MAX; 32]),
+                dice_secret: Some([u8::MAX; 32]),
+            })
+            .collect();
+        let mut max_board = create_board();
+        for tile in max_board.iter_mut() {
+            if let TileType::Property(property) = tile {
+                property.owner = Some(MAX_PLAYERS - 1);
+                property.houses = 5;
+            }
+        }
         let max_game = Game {
-            board: vec![TileType::Property(max_property); 40], // Maximum board size
-            players: vec![max_player; 8], // Maximum players
+            board: max_board,
+            players: max_players, // Maximum players
             current_player: u8::MAX,
             free_parking: u64::MAX,
             initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
         };
         let mut packed = vec![0; Game::LEN];
         max_game.pack_into_slice(&mut packed);
@@ -952,43 +2243,294 @@ mod tests {
         // Test unpacking maximum size game
         let unpacked = Game::unpack_from_slice(&packed).unwrap();
         assert_eq!(unpacked.board.len(), max_game.board.len());
+        for (unpacked_tile, original_tile) in unpacked.board.iter().zip(max_game.board.iter()) {
+            if let (TileType::Property(up), TileType::Property(og)) = (unpacked_tile, original_tile) {
+                assert_eq!(up.owner, og.owner);
+                assert_eq!(up.houses, og.houses);
+            }
+        }
         assert_eq!(unpacked.players.len(), max_game.players.len());
+        assert_eq!(unpacked.players[7].wallet, max_game.players[7].wallet);
+        assert_eq!(unpacked.players[7].properties, max_game.players[7].properties);
         assert_eq!(unpacked.current_player, max_game.current_player);
         assert_eq!(unpacked.free_parking, max_game.free_parking);
         assert_eq!(unpacked.initialized, max_game.initialized);
 
-        // Verify Game::LEN is sufficient
-        let required_len = 4 + // board length
-            (40 * TileType::LEN) + // maximum board size
-            4 + // players length
+        // Verify Game::LEN covers exactly the fixed layout
+        let required_len = 1 + // players length
             (8 * Player::LEN) + // maximum players
+            1 + // board id
+            (40 * TileState::LEN) + // tile state
             1 + // current_player
             8 + // free_parking
-            1; // initialized
-        assert!(Game::LEN >= required_len,
-            "Game::LEN must be at least {} for maximum data", required_len);
-
-        // Test error case - invalid board tile
+            1 + // initialized
+            32 + // creator
+            1 + // status
+            1 + // max_players
+            1 + Auction::LEN + // auction
+            1 + Debt::LEN + // debt
+            1 + // last_roll
+            2 + // houses_remaining, hotels_remaining
+            1 + // doubles_rolled
+            1 + DiceRound::LEN + // dice_round
+            3 + // rolled_dice
+            RandomnessKind::LEN + // randomness
+            8 + // randomness_nonce
+            2 + // last_dice
+            1 + // last_doubles
+            TurnPhase::LEN + // turn_phase
+            1 + PendingAction::LEN + // pending_action
+            GameRules::LEN + // rules
+            1 + MAX_PLAYER_PROPERTIES + // auction_queue
+        1 + 32 + // randomness_request
+        2 * (1 + 32); // community_chest_deck, chance_deck
+        assert_eq!(Game::LEN, required_len);
+        assert_eq!(Game::LEN, 2077);
+
+        // Test error case - unknown board id
+        let board_start = 1 + 8 * Player::LEN;
         let mut invalid_packed = packed.clone();
-        // Corrupt first tile's discriminator
-        invalid_packed[4] = 2; // Invalid TileType discriminator
+        invalid_packed[board_start] = 2; // Invalid board id
         let result = Game::unpack_from_slice(&invalid_packed);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
 
+        // Test error case - tile owned by an empty seat
+        let mut invalid_packed = vec![0; Game::LEN];
+        game.pack_into_slice(&mut invalid_packed);
+        invalid_packed[board_start + 1 + TileState::LEN] = 1; // Mediterranean owned by seat 1
+        let result = Game::unpack_from_slice(&invalid_packed);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+
         // Test error case - invalid player data
         let mut invalid_packed = packed.clone();
-        let player_data_start = 4 + (40 * TileType::LEN) + 4; // After board length, board data, and players length
+        let player_data_start = 1; // After players length
         // Corrupt first player's name with invalid UTF-8
-        invalid_packed[player_data_start + 4] = 0xFF; // Invalid UTF-8 byte
+        invalid_packed[player_data_start + 1] = 0xFF; // Invalid UTF-8 byte
         let result = Game::unpack_from_slice(&invalid_packed);
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
     }
 
+    #[test]
+    fn test_game_status_pack() {
+        let statuses = [GameStatus::Open, GameStatus::Started, GameStatus::Finished];
+
+        for (i, status) in statuses.iter().enumerate() {
+            let mut packed = vec![0; GameStatus::LEN];
+            status.pack_into_slice(&mut packed);
+            assert_eq!(packed[0], i as u8);
+            assert_eq!(&GameStatus::unpack_from_slice(&packed).unwrap(), status);
+        }
+
+        let result = GameStatus::unpack_from_slice(&[3]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_turn_phase_pack() {
+        let phases = [
+            TurnPhase::AwaitingRoll,
+            TurnPhase::ResolvingTile,
+            TurnPhase::PostRoll,
+            TurnPhase::AwaitingDebtResolution,
+            TurnPhase::GameOver,
+        ];
+
+        for (i, phase) in phases.iter().enumerate() {
+            let mut packed = vec![0; TurnPhase::LEN];
+            phase.pack_into_slice(&mut packed);
+            assert_eq!(packed[0], i as u8);
+            assert_eq!(&TurnPhase::unpack_from_slice(&packed).unwrap(), phase);
+        }
+
+        let result = TurnPhase::unpack_from_slice(&[5]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_auction_pack() {
+        let auctions = vec![
+            Auction {
+                property_index: 39,
+                highest_bid: 0,
+                highest_bidder: None,
+                deadline_slot: 1_000,
+            },
+            Auction {
+                property_index: 1,
+                highest_bid: u64::MAX,
+                highest_bidder: Some(7),
+                deadline_slot: u64::MAX,
+            },
+        ];
+
+        for auction in auctions {
+            let mut packed = vec![0; Auction::LEN];
+            auction.pack_into_slice(&mut packed);
+            assert_eq!(Auction::unpack_from_slice(&packed).unwrap(), auction);
+        }
+    }
+
+    #[test]
+    fn test_dice_round_pack() {
+        for phase in [DicePhase::Commit, DicePhase::Reveal] {
+            let dice_round = DiceRound { phase, deadline_slot: u64::MAX };
+            let mut packed = vec![0; DiceRound::LEN];
+            dice_round.pack_into_slice(&mut packed);
+            assert_eq!(DiceRound::unpack_from_slice(&packed).unwrap(), dice_round);
+        }
+
+        let result = DiceRound::unpack_from_slice(&[2; DiceRound::LEN]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_randomness_kind_pack() {
+        let kinds = vec![
+            RandomnessKind::SlotHash,
+            RandomnessKind::Oracle { oracle: Pubkey::new_unique() },
+            RandomnessKind::Seeded { seed: u64::MAX },
+        ];
+
+        for kind in kinds {
+            let mut packed = vec![0; RandomnessKind::LEN];
+            kind.pack_into_slice(&mut packed);
+            assert_eq!(RandomnessKind::unpack_from_slice(&packed).unwrap(), kind);
+        }
+
+        let result = RandomnessKind::unpack_from_slice(&[3; RandomnessKind::LEN]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_game_rules_pack() {
+        let defaults = GameRules::default();
+        assert_eq!(defaults.starting_cash, STARTING_CASH);
+        assert_eq!(defaults.go_salary, GO_SALARY);
+        assert_eq!(defaults.jail_fine, JAIL_FINE);
+
+        let rules = GameRules {
+            starting_cash: u64::MAX,
+            go_salary: 1,
+            jail_fine: 0,
+            free_parking_jackpot: true,
+            double_go_salary: false,
+            no_rent_in_jail: true,
+            auction_on_decline: false,
+        };
+        for rules in [defaults, rules] {
+            let mut packed = vec![0; GameRules::LEN];
+            rules.pack_into_slice(&mut packed);
+            assert_eq!(GameRules::unpack_from_slice(&packed).unwrap(), rules);
+        }
+    }
+
+    #[test]
+    fn test_tile_kind_pack() {
+        for kind in [TileKind::Street, TileKind::Railroad, TileKind::Utility] {
+            let mut packed = vec![0; TileKind::LEN];
+            kind.pack_into_slice(&mut packed);
+            assert_eq!(TileKind::unpack_from_slice(&packed).unwrap(), kind);
+        }
+
+        let result = TileKind::unpack_from_slice(&[3]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_debt_pack() {
+        let debts = vec![
+            Debt {
+                debtor: 0,
+                creditor: None,
+                amount: 200,
+            },
+            Debt {
+                debtor: 7,
+                creditor: Some(3),
+                amount: u64::MAX,
+            },
+        ];
+
+        for debt in debts {
+            let mut packed = vec![0; Debt::LEN];
+            debt.pack_into_slice(&mut packed);
+            assert_eq!(Debt::unpack_from_slice(&packed).unwrap(), debt);
+        }
+    }
+
+    #[test]
+    fn test_pending_action_pack() {
+        let actions = vec![
+            PendingAction::PayRent { property_index: 39, owner: 7, card_rent: true },
+            PendingAction::DrawCard { deck_type: DeckType::CommunityChest },
+            PendingAction::DrawCard { deck_type: DeckType::Chance },
+            PendingAction::BuyProperty { property_index: 1 },
+        ];
+
+        for action in actions {
+            let mut packed = vec![0; PendingAction::LEN];
+            action.pack_into_slice(&mut packed);
+            assert_eq!(PendingAction::unpack_from_slice(&packed).unwrap(), action);
+        }
+
+        let result = PendingAction::unpack_from_slice(&[3; PendingAction::LEN]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+        let result = PendingAction::unpack_from_slice(&[1, 2, 0, 0]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    #[test]
+    fn test_trade_terms_pack() {
+        let terms = vec![
+            TradeTerms::default(),
+            TradeTerms {
+                properties: vec![u8::MAX; MAX_TRADE_PROPERTIES],
+                cash: u64::MAX,
+                jail_cards: u8::MAX,
+            },
+        ];
+
+        for terms in terms {
+            let mut packed = vec![0; TradeTerms::LEN];
+            terms.pack_into_slice(&mut packed);
+            assert_eq!(TradeTerms::unpack_from_slice(&packed).unwrap(), terms);
+        }
+
+        // Property count larger than the slot is rejected
+        let mut packed = vec![0; TradeTerms::LEN];
+        packed[0..4].copy_from_slice(&(MAX_TRADE_PROPERTIES as u32 + 1).to_le_bytes());
+        let result = TradeTerms::unpack_from_slice(&packed);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
     #[test]
     fn test_card_pack() {
-        // TODO: Implement test
+        let cards = vec![
+            Card::CollectMoney(u64::MAX),
+            Card::PayMoney(15),
+            Card::Move(39),
+            Card::GetOutOfJail,
+            Card::GoToJail,
+            Card::MoveBack(3),
+            Card::AdvanceToNearest(TileKind::Railroad),
+            Card::AdvanceToNearest(TileKind::Utility),
+            Card::PayPerHouseAndHotel { house: 40, hotel: 115 },
+            Card::CollectFromEachPlayer(10),
+            Card::PayEachPlayer(50),
+        ];
+
+        for card in cards {
+            let mut packed = vec![0; Card::LEN];
+            card.pack_into_slice(&mut packed);
+            assert_eq!(Card::unpack_from_slice(&packed).unwrap(), card);
+        }
+
+        let result = Card::unpack_from_slice(&[10; Card::LEN]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
+        let result = Card::unpack_from_slice(&[6, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(result.unwrap_err(), ProgramError::InvalidAccountData);
     }
 
     #[test]
@@ -999,6 +2541,28 @@ mod tests {
             current_player: 0,
             free_parking: 0,
             initialized: false,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
         };
         assert!(!game.is_initialized(), "Uninitialized game should return false");
 
@@ -1008,6 +2572,28 @@ mod tests {
             current_player: 0,
             free_parking: 0,
             initialized: true,
+            creator: Pubkey::default(),
+            status: GameStatus::Started,
+            max_players: 8,
+            auction: None,
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::Seeded { seed: 0 },
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
         };
         assert!(game.is_initialized(), "Initialized game should return true");
     }
@@ -1016,11 +2602,18 @@ mod tests {
     fn test_player_is_initialized() {
         let player = Player {
             name: String::from("Test Player"),
+            wallet: Pubkey::default(),
+            delegate: None,
             cash: 1500,
             position: 0,
             jail_turns: 0,
             properties: Vec::new(),
             get_out_of_jail_cards: 0,
+            community_chest_jail_card: false,
+            chance_jail_card: false,
+            eliminated: false,
+            dice_commitment: None,
+            dice_secret: None,
         };
         assert!(player.is_initialized(), "Player should always be initialized");
     }
@@ -1030,47 +2623,359 @@ mod tests {
         let property = Property {
             name: String::from("Test Property"),
             color: Color::Blue,
+            kind: TileKind::Street,
             cost: 200,
-            rent: 10,
+            rent: vec![10],
             house_cost: 100,
+            hotel_cost: 100,
             house_rent: vec![10, 20, 30, 40],
             houses: 0,
+            mortgaged: false,
             owner: None,
         };
         assert!(property.is_initialized(), "Property should always be initialized");
     }
-}
 
-impl Pack for Card {
-    const LEN: usize = 9; // 1 byte for type + 8 bytes for value
+    fn sample_game() -> Game {
+        let wallet = Pubkey::new_unique();
+        let mut board = create_board();
+        if let TileType::Property(property) = &mut board[1] {
+            property.owner = Some(0);
+        }
+        Game {
+            board,
+            players: vec![Player {
+                name: String::from("Alice"),
+                wallet,
+                delegate: Some(Pubkey::new_unique()),
+                cash: 1500,
+                position: 1,
+                jail_turns: 0,
+                properties: vec![1],
+                get_out_of_jail_cards: 0,
+                community_chest_jail_card: false,
+                chance_jail_card: false,
+                eliminated: false,
+                dice_commitment: Some([1; 32]),
+                dice_secret: None,
+            }],
+            current_player: 0,
+            free_parking: 0,
+            initialized: true,
+            creator: wallet,
+            status: GameStatus::Started,
+            max_players: 4,
+            auction: Some(Auction { property_index: 1, highest_bid: 10, highest_bidder: Some(0), deadline_slot: 5 }),
+            debt: None,
+            last_roll: 0,
+            houses_remaining: BANK_HOUSES,
+            hotels_remaining: BANK_HOTELS,
+            doubles_rolled: 0,
+            dice_round: None,
+            rolled_dice: None,
+            randomness: RandomnessKind::SlotHash,
+            randomness_nonce: 0,
+            last_dice: (0, 0),
+            last_doubles: false,
+            turn_phase: TurnPhase::AwaitingRoll,
+            pending_action: None,
+            rules: GameRules::default(),
+            auction_queue: vec![],
+            randomness_request: None,
+            community_chest_deck: None,
+            chance_deck: None,
+        }
+    }
 
-    fn pack_into_slice(&self, dst: &mut [u8]) {
+    // Every prefix of a valid encoding shorter than `len` must be rejected
+    fn assert_rejects_truncation<T: Pack>(packed: &[u8], len: usize) {
+        for cut in 0..len {
+            assert_eq!(
+                T::unpack_from_slice(&packed[..cut]).err(),
+                Some(ProgramError::InvalidAccountData),
+                "unpacking {} of {} bytes should fail", cut, len
+            );
+        }
+    }
+
+    fn packed<T: Pack>(value: &T) -> Vec<u8> {
+        let mut packed = vec![0; T::LEN];
+        value.pack_into_slice(&mut packed);
+        packed
+    }
+
+    #[test]
+    fn test_unpack_truncated() {
+        let game = sample_game();
+        let player = game.players[0].clone();
+        let trade_terms = TradeTerms { properties: vec![1, 3], cash: 100, jail_cards: 1 };
+
+        assert_rejects_truncation::<Game>(&packed(&game), Game::LEN);
+        assert_rejects_truncation::<Player>(&packed(&player), Player::LEN);
+        assert_rejects_truncation::<TradeTerms>(&packed(&trade_terms), TradeTerms::LEN);
+        assert_rejects_truncation::<Auction>(&packed(game.auction.as_ref().unwrap()), Auction::LEN);
+        assert_rejects_truncation::<GameRules>(&packed(&game.rules), GameRules::LEN);
+        assert_rejects_truncation::<TileState>(&packed(&TileState::default()), TileState::LEN);
+        assert_rejects_truncation::<Debt>(&packed(&Debt { debtor: 0, creditor: None, amount: 1 }), Debt::LEN);
+        assert_rejects_truncation::<DiceRound>(&packed(&DiceRound { phase: DicePhase::Commit, deadline_slot: 1 }), DiceRound::LEN);
+        assert_rejects_truncation::<Card>(&packed(&Card::PayPerHouseAndHotel { house: 40, hotel: 115 }), Card::LEN);
+
+        // Test length prefixes larger than the slot they describe are rejected
+        let mut data = packed(&player);
+        data[0] = MAX_NAME_LEN as u8 + 1;
+        assert_eq!(Player::unpack_from_slice(&data).unwrap_err(), ProgramError::InvalidAccountData);
+        let mut data = packed(&trade_terms);
+        data[..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(TradeTerms::unpack_from_slice(&data).unwrap_err(), ProgramError::InvalidAccountData);
+        let mut data = packed(&game);
+        data[0] = MAX_PLAYERS + 1;
+        assert_eq!(Game::unpack_from_slice(&data).unwrap_err(), ProgramError::InvalidAccountData);
+    }
+
+    mod fuzz {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn pubkey() -> impl Strategy<Value = Pubkey> {
+            any::<[u8; 32]>().prop_map(Pubkey::from)
+        }
+
+        fn tile_kind() -> impl Strategy<Value = TileKind> {
+            prop_oneof![Just(TileKind::Street), Just(TileKind::Railroad), Just(TileKind::Utility)]
+        }
+
+        fn card() -> impl Strategy<Value = Card> {
+            prop_oneof![
+                any::<u64>().prop_map(Card::CollectMoney),
+                any::<u64>().prop_map(Card::PayMoney),
+                any::<u8>().prop_map(Card::Move),
+                Just(Card::GetOutOfJail),
+                Just(Card::GoToJail),
+                any::<u8>().prop_map(Card::MoveBack),
+                tile_kind().prop_map(Card::AdvanceToNearest),
+                (any::<u64>(), any::<u64>()).prop_map(|(house, hotel)| Card::PayPerHouseAndHotel { house, hotel }),
+                any::<u64>().prop_map(Card::CollectFromEachPlayer),
+                any::<u64>().prop_map(Card::PayEachPlayer),
+            ]
+        }
+
+        // Decoding either succeeds or reports bad account data; it never panics
+        fn check_unpack<T: Pack>(data: &[u8]) -> Result<(), TestCaseError> {
+            if let Err(err) = T::unpack_from_slice(data) {
+                prop_assert_eq!(err, ProgramError::InvalidAccountData);
+            }
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn fuzz_unpack_arbitrary_bytes(data in proptest::collection::vec(any::<u8>(), 0..Game::LEN + 64)) {
+                check_unpack::<Color>(&data)?;
+                check_unpack::<TileKind>(&data)?;
+                check_unpack::<Property>(&data)?;
+                check_unpack::<SpecialTile>(&data)?;
+                check_unpack::<TileType>(&data)?;
+                check_unpack::<TileState>(&data)?;
+                check_unpack::<Player>(&data)?;
+                check_unpack::<GameStatus>(&data)?;
+                check_unpack::<TurnPhase>(&data)?;
+                check_unpack::<Auction>(&data)?;
+                check_unpack::<DiceRound>(&data)?;
+                check_unpack::<RandomnessKind>(&data)?;
+                check_unpack::<GameRules>(&data)?;
+                check_unpack::<Debt>(&data)?;
+                check_unpack::<PendingAction>(&data)?;
+                check_unpack::<TradeTerms>(&data)?;
+                check_unpack::<Game>(&data)?;
+                check_unpack::<Card>(&data)?;
+            }
+
+            #[test]
+            fn fuzz_unpack_corrupted_game(index in 0..Game::LEN, byte in any::<u8>()) {
+                let mut data = packed(&sample_game());
+                data[index] = byte;
+                check_unpack::<Game>(&data)?;
+            }
+
+            #[test]
+            fn fuzz_property_round_trip(
+                name in "[a-zA-Z ]{0,32}",
+                kind in tile_kind(),
+                cost in any::<u64>(),
+                rent in proptest::collection::vec(any::<u64>(), 0..=MAX_RENT_LEVELS),
+                house_rent in proptest::collection::vec(any::<u64>(), 0..=MAX_HOUSE_RENT_LEVELS),
+                owner in proptest::option::of(0..MAX_PLAYERS),
+                houses in 0u8..=5,
+                mortgaged in any::<bool>(),
+            ) {
+                let property = Property {
+                    name,
+                    color: Color::Green,
+                    kind,
+                    cost,
+                    rent,
+                    house_cost: cost / 2,
+                    hotel_cost: cost,
+                    house_rent,
+                    owner,
+                    houses,
+                    mortgaged,
+                };
+                let packed = packed(&property);
+                let unpacked = Property::unpack_from_slice(&packed).unwrap();
+                prop_assert_eq!(&unpacked.name, &property.name);
+                prop_assert_eq!(unpacked.kind, property.kind);
+                prop_assert_eq!(&unpacked.rent, &property.rent);
+                prop_assert_eq!(&unpacked.house_rent, &property.house_rent);
+                prop_assert_eq!(unpacked.owner, property.owner);
+                prop_assert_eq!(unpacked.houses, property.houses);
+                prop_assert_eq!(unpacked.mortgaged, property.mortgaged);
+
+                // Name, color and kind, cost, rents, costs, owner, houses and mortgage flag
+                let len = 4 + property.name.len() + 2 + 8 + 4 + 8 * property.rent.len() + 16 +
+                    4 + 8 * property.house_rent.len() + 1 + 2;
+                assert_rejects_truncation::<Property>(&packed, len);
+            }
+
+            #[test]
+            fn fuzz_player_round_trip(
+                name in "[a-zA-Z ]{0,32}",
+                wallet in pubkey(),
+                delegate in proptest::option::of(pubkey()),
+                cash in any::<u64>(),
+                position in 0u8..40,
+                properties in proptest::collection::vec(0u8..40, 0..=MAX_PLAYER_PROPERTIES),
+                dice_commitment in proptest::option::of(any::<[u8; 32]>()),
+                dice_secret in proptest::option::of(any::<[u8; 32]>()),
+            ) {
+                let player = Player {
+                    name,
+                    wallet,
+                    delegate,
+                    cash,
+                    position,
+                    jail_turns: 0,
+                    properties,
+                    get_out_of_jail_cards: 2,
+                    community_chest_jail_card: false,
+                    chance_jail_card: false,
+                    eliminated: false,
+                    dice_commitment,
+                    dice_secret,
+                };
+                let packed = packed(&player);
+                let unpacked = Player::unpack_from_slice(&packed).unwrap();
+                prop_assert_eq!(&unpacked.name, &player.name);
+                prop_assert_eq!(unpacked.delegate, player.delegate);
+                prop_assert_eq!(unpacked.cash, player.cash);
+                prop_assert_eq!(&unpacked.properties, &player.properties);
+                prop_assert_eq!(unpacked.dice_commitment, player.dice_commitment);
+                prop_assert_eq!(unpacked.dice_secret, player.dice_secret);
+            }
+
+            #[test]
+            fn fuzz_small_types_round_trip(
+                card in card(),
+                properties in proptest::collection::vec(any::<u8>(), 0..=MAX_TRADE_PROPERTIES),
+                cash in any::<u64>(),
+                bidder in proptest::option::of(any::<u8>()),
+                oracle in pubkey(),
+                seed in any::<u64>(),
+            ) {
+                prop_assert_eq!(Card::unpack_from_slice(&packed(&card)).unwrap(), card);
+
+                let trade_terms = TradeTerms { properties, cash, jail_cards: 1 };
+                prop_assert_eq!(TradeTerms::unpack_from_slice(&packed(&trade_terms)).unwrap(), trade_terms);
+
+                let auction = Auction { property_index: 1, highest_bid: cash, highest_bidder: bidder, deadline_slot: seed };
+                prop_assert_eq!(Auction::unpack_from_slice(&packed(&auction)).unwrap(), auction);
+
+                let debt = Debt { debtor: 0, creditor: bidder, amount: cash };
+                prop_assert_eq!(Debt::unpack_from_slice(&packed(&debt)).unwrap(), debt);
+
+                for randomness in [RandomnessKind::SlotHash, RandomnessKind::Oracle { oracle }, RandomnessKind::Seeded { seed }] {
+                    prop_assert_eq!(RandomnessKind::unpack_from_slice(&packed(&randomness)).unwrap(), randomness);
+                }
+            }
+        }
+    }
+}
+
+impl Card {
+    /// Write the card as its tag followed by only the values that variant carries
+    pub fn write(&self, writer: &mut Writer) {
         match self {
             Card::CollectMoney(amount) => {
-                dst[0] = 0;
-                dst[1..9].copy_from_slice(&amount.to_le_bytes());
+                writer.write_u8(0);
+                writer.write_u64(*amount);
             }
             Card::PayMoney(amount) => {
-                dst[0] = 1;
-                dst[1..9].copy_from_slice(&amount.to_le_bytes());
+                writer.write_u8(1);
+                writer.write_u64(*amount);
             }
             Card::Move(position) => {
-                dst[0] = 2;
-                dst[1] = *position;
+                writer.write_u8(2);
+                writer.write_u8(*position);
             }
             Card::GetOutOfJail => {
-                dst[0] = 3;
+                writer.write_u8(3);
+            }
+            Card::GoToJail => {
+                writer.write_u8(4);
+            }
+            Card::MoveBack(spaces) => {
+                writer.write_u8(5);
+                writer.write_u8(*spaces);
+            }
+            Card::AdvanceToNearest(kind) => {
+                writer.write_u8(6);
+                writer.write_pack(kind);
+            }
+            Card::PayPerHouseAndHotel { house, hotel } => {
+                writer.write_u8(7);
+                writer.write_u64(*house);
+                writer.write_u64(*hotel);
+            }
+            Card::CollectFromEachPlayer(amount) => {
+                writer.write_u8(8);
+                writer.write_u64(*amount);
+            }
+            Card::PayEachPlayer(amount) => {
+                writer.write_u8(9);
+                writer.write_u64(*amount);
             }
         }
     }
 
-    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        Ok(match src[0] {
-            0 => Card::CollectMoney(u64::from_le_bytes(src[1..9].try_into().unwrap())),
-            1 => Card::PayMoney(u64::from_le_bytes(src[1..9].try_into().unwrap())),
-            2 => Card::Move(src[1]),
+    /// Read a card written by `Card::write`
+    pub fn read(reader: &mut Reader) -> Result<Self, ProgramError> {
+        Ok(match reader.read_u8()? {
+            0 => Card::CollectMoney(reader.read_u64()?),
+            1 => Card::PayMoney(reader.read_u64()?),
+            2 => Card::Move(reader.read_u8()?),
             3 => Card::GetOutOfJail,
+            4 => Card::GoToJail,
+            5 => Card::MoveBack(reader.read_u8()?),
+            6 => Card::AdvanceToNearest(reader.read_pack()?),
+            7 => Card::PayPerHouseAndHotel {
+                house: reader.read_u64()?,
+                hotel: reader.read_u64()?,
+            },
+            8 => Card::CollectFromEachPlayer(reader.read_u64()?),
+            9 => Card::PayEachPlayer(reader.read_u64()?),
             _ => return Err(ProgramError::InvalidAccountData),
         })
     }
 }
+
+impl Pack for Card {
+    const LEN: usize = 17; // 1 byte for type + up to two 8 byte values
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        self.write(&mut Writer::new(dst));
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Card::read(&mut Reader::new(src))
+    }
+}
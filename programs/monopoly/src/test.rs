@@ -1,22 +1,36 @@
 use solana_program::{
-    account_info::AccountInfo,
+    account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
     system_instruction,
+    rent::Rent,
+    system_program,
+    sysvar::{clock, rent},
 };
 use solana_program_test::{processor, ProgramTest, BanksClient};
 use solana_sdk::{
+    account::Account,
     signature::{Keypair, Signer},
     transaction::Transaction,
     hash::Hash,
 };
 
 use crate::{
-    account::{GameAccount, PlayerAccount},
+    account::{GameAccount, PlayerAccount, CardDeck, DeckType, AccountKind, RandomnessRequest},
+    account_v1,
     instruction::MonopolyInstruction,
     processor::process_instruction,
+    board::create_board,
+    state::{
+        Card, RandomnessKind, GameRules, Game, Player, GameStatus, TurnPhase, TileType, PendingAction, DiceRound,
+        DicePhase, BANK_HOUSES, BANK_HOTELS,
+    },
+    utils::{dice_commitment, roll_dice, OracleOutput},
     id,
 };
 
@@ -40,8 +54,10 @@ async fn test_full_game_session() {
     // Initialize test environment
     let (mut banks_client, payer, recent_blockhash) = setup_test().await;
     
-    // Create game account
+    // Create game account and open the lobby as player 1
     let game_account = Keypair::new();
+    let player1 = Keypair::new();
+    let player2 = Keypair::new();
     let rent = banks_client.get_rent().await.unwrap();
     let game_account_rent = rent.minimum_balance(GameAccount::LEN);
     
@@ -54,19 +70,36 @@ async fn test_full_game_session() {
                 GameAccount::LEN as u64,
                 &crate::id(),
             ),
-            MonopolyInstruction::InitGame {
-                player_names: vec!["Alice".to_string(), "Bob".to_string()],
+            MonopolyInstruction::CreateGame {
+                player_name: "Alice".to_string(),
+                max_players: 2,
+                // A seeded game may roll in MovePlayer without a commit–reveal round
+                randomness: RandomnessKind::Seeded { seed: 0 },
+                rules: GameRules::default(),
+            }
+            .to_instruction(&crate::id(), &[&game_account.pubkey(), &player1.pubkey()]),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &game_account, &player1], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // Player 2 joins and player 1 starts the game
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            MonopolyInstruction::JoinGame {
+                player_name: "Bob".to_string(),
             }
-            .to_instruction(&crate::id(), &[&game_account.pubkey()]),
+            .to_instruction(&crate::id(), &[&game_account.pubkey(), &player2.pubkey()]),
+            MonopolyInstruction::StartGame
+                .to_instruction(&crate::id(), &[&game_account.pubkey(), &player1.pubkey()]),
         ],
         Some(&payer.pubkey()),
     );
-    transaction.sign(&[&payer, &game_account], recent_blockhash);
+    transaction.sign(&[&payer, &player1, &player2], recent_blockhash);
     banks_client.process_transaction(transaction).await.unwrap();
 
     // Create player accounts
-    let player1 = Keypair::new();
-    let player2 = Keypair::new();
     let player_rent = rent.minimum_balance(PlayerAccount::LEN);
 
     // Initialize players
@@ -89,7 +122,7 @@ async fn test_full_game_session() {
     for round in 0..10 {
         println!("Round {}", round + 1);
 
-        for player in [&player1, &player2] {
+        for (seat, player) in [&player1, &player2].into_iter().enumerate() {
             // Roll and move
             let transaction = Transaction::new_with_payer(
                 &[MonopolyInstruction::MovePlayer
@@ -131,7 +164,7 @@ async fn test_full_game_session() {
             // Try to build houses on owned properties
             for (idx, tile) in game_state.game.board.iter().enumerate() {
                 if let Some(property) = tile.as_property() {
-                    if property.owner == Some(player.pubkey()) {
+                    if property.owner == Some(seat as u8) {
                         let transaction = Transaction::new_with_payer(
                             &[MonopolyInstruction::BuildHouse { property_index: idx as u8 }
                                 .to_instruction(&crate::id(), &[&game_account.pubkey(), &player.pubkey()])],
@@ -177,3 +210,405 @@ async fn test_full_game_session() {
     transaction.sign(&[&payer], recent_blockhash);
     banks_client.process_transaction(transaction).await.unwrap();
 }
+
+// Compute units a transaction would use, without committing it
+async fn units_consumed(banks_client: &mut BanksClient, transaction: Transaction) -> u64 {
+    let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+    assert!(simulation.result.unwrap().is_ok());
+    simulation.simulation_details.unwrap().units_consumed
+}
+
+// A started two-player game on the standard board, with Alice to move
+fn bench_game(alice: Pubkey, bob: Pubkey) -> Game {
+    let player = |name: &str, wallet: Pubkey| Player {
+        name: name.to_string(),
+        wallet,
+        delegate: None,
+        cash: 1500,
+        position: 0,
+        jail_turns: 0,
+        properties: vec![],
+        get_out_of_jail_cards: 0,
+        community_chest_jail_card: false,
+        chance_jail_card: false,
+        eliminated: false,
+        dice_commitment: None,
+        dice_secret: None,
+    };
+    Game {
+        board: create_board(),
+        players: vec![player("Alice", alice), player("Bob", bob)],
+        current_player: 0,
+        free_parking: 0,
+        initialized: true,
+        creator: alice,
+        status: GameStatus::Started,
+        max_players: 2,
+        auction: None,
+        debt: None,
+        last_roll: 0,
+        houses_remaining: BANK_HOUSES,
+        hotels_remaining: BANK_HOTELS,
+        doubles_rolled: 0,
+        dice_round: None,
+        rolled_dice: None,
+        randomness: RandomnessKind::Seeded { seed: 0 },
+        randomness_nonce: 0,
+        last_dice: (0, 0),
+        last_doubles: false,
+        turn_phase: TurnPhase::AwaitingRoll,
+        pending_action: None,
+        rules: GameRules::default(),
+        auction_queue: vec![],
+        randomness_request: None,
+        community_chest_deck: None,
+        chance_deck: None,
+    }
+}
+
+// Compute units each instruction played on every turn may use. These borrow the game account in
+// place; unpacking the whole game again would blow through this.
+const HOT_INSTRUCTION_COMPUTE_BUDGET: u64 = 25_000;
+
+// Runs each hot instruction against a prepared game and checks it stays within the budget.
+// Run with `--nocapture` to see the units each one used.
+#[tokio::test]
+async fn test_hot_instruction_compute_units() {
+    let program_id = id();
+    let mut program_test = ProgramTest::new("monopoly", program_id, None);
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let secret = [7; 32];
+
+    let mut cases = Vec::new();
+    let mut add_case = |name: &'static str, game: Game, instruction: MonopolyInstruction, extra: Vec<Pubkey>| {
+        let game_key = Pubkey::new_unique();
+        let mut data = vec![0; GameAccount::LEN];
+        GameAccount { is_initialized: true, game }.pack_into_slice(&mut data);
+        program_test.add_account(
+            game_key,
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+        let mut keys = vec![game_key, alice.pubkey()];
+        keys.extend(extra);
+        let keys: Vec<&Pubkey> = keys.iter().collect();
+        cases.push((name, instruction.to_instruction(&program_id, &keys)));
+    };
+    let dice_accounts = || vec![clock::ID, clock::ID];
+
+    add_case("MovePlayer", bench_game(alice.pubkey(), bob), MonopolyInstruction::MovePlayer, vec![]);
+
+    let mut game = bench_game(alice.pubkey(), bob);
+    game.players[0].position = 1;
+    game.turn_phase = TurnPhase::ResolvingTile;
+    game.pending_action = Some(PendingAction::BuyProperty { property_index: 1 });
+    add_case("BuyProperty", game, MonopolyInstruction::BuyProperty { property_index: 1 }, vec![]);
+
+    let mut game = bench_game(alice.pubkey(), bob);
+    game.players[0].position = 1;
+    game.players[1].properties = vec![1];
+    if let TileType::Property(property) = &mut game.board[1] {
+        property.owner = Some(1);
+    }
+    game.turn_phase = TurnPhase::ResolvingTile;
+    game.pending_action = Some(PendingAction::PayRent { property_index: 1, owner: 1, card_rent: false });
+    add_case(
+        "PayRent",
+        game,
+        MonopolyInstruction::PayRent { property_index: 1 },
+        vec![bob],
+    );
+
+    let mut game = bench_game(alice.pubkey(), bob);
+    game.turn_phase = TurnPhase::PostRoll;
+    add_case("NextTurn", game, MonopolyInstruction::NextTurn, vec![]);
+
+    // Bob has committed too, so Alice's reveal leaves the round open
+    let mut game = bench_game(alice.pubkey(), bob);
+    game.players[0].dice_commitment = Some(dice_commitment(&secret, &alice.pubkey()));
+    game.players[1].dice_commitment = Some([1; 32]);
+    game.dice_round = Some(DiceRound { phase: DicePhase::Reveal, deadline_slot: u64::MAX });
+    add_case("RevealDice", game, MonopolyInstruction::RevealDice { secret }, dice_accounts());
+
+    // Bob never revealed and is fined as the expired round settles
+    let mut game = bench_game(alice.pubkey(), bob);
+    game.players[0].dice_commitment = Some(dice_commitment(&secret, &alice.pubkey()));
+    game.players[0].dice_secret = Some(secret);
+    game.players[1].dice_commitment = Some([1; 32]);
+    game.dice_round = Some(DiceRound { phase: DicePhase::Reveal, deadline_slot: 0 });
+    add_case("SettleDice", game, MonopolyInstruction::SettleDice, dice_accounts());
+
+    let mut context = program_test.start_with_context().await;
+    context.warp_to_slot(10).unwrap();
+
+    for (name, instruction) in cases {
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&context.payer.pubkey()));
+        transaction.sign(&[&context.payer, &alice], blockhash);
+        let units = units_consumed(&mut context.banks_client, transaction).await;
+        println!("{}: {} CU", name, units);
+        assert!(
+            units <= HOT_INSTRUCTION_COMPUTE_BUDGET,
+            "{} used {} CU, over the {} CU budget",
+            name,
+            units,
+            HOT_INSTRUCTION_COMPUTE_BUDGET,
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_migrate_v1_account() {
+    let program_id = id();
+    let mut program_test = ProgramTest::new("monopoly", program_id, None);
+
+    // A version 1 deck as it was packed: flag, card count, cards, game, deck type
+    let game = Pubkey::new_unique();
+    let mut v1_data = vec![0; account_v1::CARD_DECK_LEN];
+    v1_data[0] = 1;
+    v1_data[1..5].copy_from_slice(&2u32.to_le_bytes());
+    v1_data[5] = 0;
+    v1_data[6..14].copy_from_slice(&100u64.to_le_bytes());
+    v1_data[14] = 3;
+    v1_data[15..47].copy_from_slice(game.as_ref());
+    v1_data[47] = 1;
+    let deck_key = Pubkey::new_unique();
+    program_test.add_account(
+        deck_key,
+        Account {
+            lamports: Rent::default().minimum_balance(v1_data.len()),
+            data: v1_data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    // Only the upgrade authority named in the program's ProgramData account may migrate
+    let authority = Keypair::new();
+    program_test.add_account(
+        authority.pubkey(),
+        Account::new(1_000_000_000, 0, &system_program::ID),
+    );
+    let (program_data_key, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(authority.pubkey()),
+    })
+    .unwrap();
+    program_test.add_account(
+        program_data_key,
+        Account {
+            lamports: Rent::default().minimum_balance(program_data.len()),
+            data: program_data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    // Anyone else is refused
+    let outsider = MonopolyInstruction::MigrateAccount { kind: AccountKind::CardDeck }
+        .to_instruction(&program_id, &[&deck_key, &payer.pubkey(), &program_data_key, &rent::ID, &system_program::ID]);
+    let mut transaction = Transaction::new_with_payer(&[outsider], Some(&payer.pubkey()));
+    transaction.sign(&[&payer], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_err());
+
+    let migrate = MonopolyInstruction::MigrateAccount { kind: AccountKind::CardDeck }
+        .to_instruction(&program_id, &[&deck_key, &authority.pubkey(), &program_data_key, &rent::ID, &system_program::ID]);
+    let mut transaction = Transaction::new_with_payer(&[migrate.clone()], Some(&payer.pubkey()));
+    transaction.sign(&[&payer, &authority], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // The account takes the current size, stays rent exempt and keeps its cards in order
+    let account = banks_client.get_account(deck_key).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), CardDeck::LEN);
+    assert!(account.lamports >= Rent::default().minimum_balance(CardDeck::LEN));
+    let deck = CardDeck::unpack(&account.data).unwrap();
+    assert_eq!(deck.cards, vec![Card::CollectMoney(100), Card::GetOutOfJail]);
+    assert_eq!(deck.game, game);
+    assert_eq!(deck.deck_type, DeckType::Chance);
+
+    // A migrated account cannot be migrated again
+    let mut transaction = Transaction::new_with_payer(&[migrate], Some(&payer.pubkey()));
+    transaction.sign(&[&payer, &authority], banks_client.get_latest_blockhash().await.unwrap());
+    assert!(banks_client.process_transaction(transaction).await.is_err());
+}
+
+// Stand-in for an oracle service: its PDA is the game's oracle authority, and it answers a
+// request by signing FulfillRandomness with that PDA. Instruction data is the output to post.
+fn mock_oracle_process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], output: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let game_account = next_account_info(accounts_iter)?;
+    let request_account = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+    let monopoly_program = next_account_info(accounts_iter)?;
+
+    let (expected_authority, bump) = Pubkey::find_program_address(&[b"oracle"], program_id);
+    if authority.key != &expected_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let output = output.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let fulfill = MonopolyInstruction::FulfillRandomness { output }
+        .to_instruction(monopoly_program.key, &[game_account.key, request_account.key, &expected_authority]);
+    invoke_signed(
+        &fulfill,
+        &[game_account.clone(), request_account.clone(), authority.clone(), monopoly_program.clone()],
+        &[&[b"oracle", &[bump]]],
+    )
+}
+
+#[tokio::test]
+async fn test_oracle_randomness_flow() {
+    let program_id = id();
+    let oracle_program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("monopoly", program_id, None);
+    program_test.add_program("mock_oracle", oracle_program_id, processor!(mock_oracle_process_instruction));
+    let (oracle, _) = Pubkey::find_program_address(&[b"oracle"], &oracle_program_id);
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    let rent = banks_client.get_rent().await.unwrap();
+
+    let game_account = Keypair::new();
+    let request_account = Keypair::new();
+    let player1 = Keypair::new();
+    let player2 = Keypair::new();
+
+    // Open a two-player game that draws from the mock oracle
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &game_account.pubkey(),
+                rent.minimum_balance(GameAccount::LEN),
+                GameAccount::LEN as u64,
+                &program_id,
+            ),
+            MonopolyInstruction::CreateGame {
+                player_name: "Alice".to_string(),
+                max_players: 2,
+                randomness: RandomnessKind::Oracle { oracle },
+                rules: GameRules::default(),
+            }
+            .to_instruction(&program_id, &[&game_account.pubkey(), &player1.pubkey()]),
+            MonopolyInstruction::JoinGame { player_name: "Bob".to_string() }
+                .to_instruction(&program_id, &[&game_account.pubkey(), &player2.pubkey()]),
+            MonopolyInstruction::StartGame
+                .to_instruction(&program_id, &[&game_account.pubkey(), &player1.pubkey()]),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &game_account, &player1, &player2], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // Request: a player opens a randomness request for the next draw
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &request_account.pubkey(),
+                rent.minimum_balance(RandomnessRequest::LEN),
+                RandomnessRequest::LEN as u64,
+                &program_id,
+            ),
+            MonopolyInstruction::RequestRandomness
+                .to_instruction(&program_id, &[&game_account.pubkey(), &request_account.pubkey(), &player1.pubkey()]),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &request_account, &player1], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // The draw has its request; no second one can be opened to pick between outputs
+    let second_request = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &second_request.pubkey(),
+                rent.minimum_balance(RandomnessRequest::LEN),
+                RandomnessRequest::LEN as u64,
+                &program_id,
+            ),
+            MonopolyInstruction::RequestRandomness
+                .to_instruction(&program_id, &[&game_account.pubkey(), &second_request.pubkey(), &player2.pubkey()]),
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &second_request, &player2], recent_blockhash);
+    assert!(banks_client.process_transaction(transaction).await.is_err());
+
+    // Fulfill: the oracle program posts its output, signing as the game's oracle authority
+    let output = [42; 32];
+    let fulfill = Instruction {
+        program_id: oracle_program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(game_account.pubkey(), false),
+            AccountMeta::new(request_account.pubkey(), false),
+            AccountMeta::new_readonly(oracle, false),
+            AccountMeta::new_readonly(program_id, false),
+        ],
+        data: output.to_vec(),
+    };
+    let mut transaction = Transaction::new_with_payer(&[fulfill.clone()], Some(&payer.pubkey()));
+    transaction.sign(&[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let request = banks_client.get_account(request_account.pubkey()).await.unwrap().unwrap();
+    let request = RandomnessRequest::unpack(&request.data).unwrap();
+    assert!(request.fulfilled);
+    assert_eq!(request.output, output);
+
+    // A request is only answered once
+    let mut transaction = Transaction::new_with_payer(&[fulfill], Some(&payer.pubkey()));
+    transaction.sign(&[&payer], banks_client.get_latest_blockhash().await.unwrap());
+    assert!(banks_client.process_transaction(transaction).await.is_err());
+
+    // Both players commit and reveal; the last reveal settles the dice from their secrets and the output
+    let secrets = [[1; 32], [2; 32]];
+    for (player, secret) in [&player1, &player2].into_iter().zip(&secrets) {
+        let mut transaction = Transaction::new_with_payer(
+            &[MonopolyInstruction::CommitDice { commitment: dice_commitment(secret, &player.pubkey()) }
+                .to_instruction(&program_id, &[&game_account.pubkey(), &player.pubkey(), &clock::ID])],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, player], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+    }
+    for (player, secret) in [&player1, &player2].into_iter().zip(&secrets) {
+        let mut transaction = Transaction::new_with_payer(
+            &[MonopolyInstruction::RevealDice { secret: *secret }.to_instruction(
+                &program_id,
+                &[&game_account.pubkey(), &player.pubkey(), &clock::ID, &request_account.pubkey()],
+            )],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, player], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+    }
+
+    // MovePlayer: the current player moves by exactly the settled dice
+    let mut transaction = Transaction::new_with_payer(
+        &[MonopolyInstruction::MovePlayer
+            .to_instruction(&program_id, &[&game_account.pubkey(), &player1.pubkey()])],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer, &player1], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let game = banks_client.get_account(game_account.pubkey()).await.unwrap().unwrap();
+    let game = GameAccount::unpack(&game.data).unwrap().game;
+    let (die1, die2) = roll_dice(&secrets, &OracleOutput { output }, 0);
+    assert_eq!(game.last_dice, (die1, die2));
+    assert_eq!(game.players[0].position, die1 + die2);
+    assert_eq!(game.randomness_nonce, 1);
+    assert_eq!(game.randomness_request, None);
+}
@@ -1,153 +1,342 @@
 use sha3::{Digest, Keccak256};
 use solana_program::{
-    clock::Clock,
-    sysvar::Sysvar,
     account_info::AccountInfo,
     program_error::ProgramError,
     pubkey::Pubkey,
+    slot_hashes::MAX_ENTRIES,
+    sysvar::slot_hashes,
 };
 
-use crate::state::{TileType, Color, Property};
+use crate::error::MonopolyError;
+use crate::state::{Board, TileType, TileKind, Color, Property, PropertyView};
 
-/// Generate a random number using on-chain data
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::SpecialTile;
 
     #[test]
-    fn test_generate_random_number() {
-        use solana_program::clock::Clock;
-        use std::cell::RefCell;
-        
-        // Create mock Clock sysvar account
+    fn test_roll_dice() {
+        let reveals = [[1u8; 32], [2u8; 32]];
+        let source = SeededRandomness { seed: 7 };
+
+        // Test multiple nonces to verify range
+        for nonce in 0..50 {
+            let (dice1, dice2) = roll_dice(&reveals, &source, nonce);
+
+            // Each die should be between 1 and 6
+            assert!((1..=6).contains(&dice1), "First die value {} should be between 1 and 6", dice1);
+            assert!((1..=6).contains(&dice2), "Second die value {} should be between 1 and 6", dice2);
+        }
+
+        // Test the roll is deterministic and depends on every reveal
+        assert_eq!(roll_dice(&reveals, &source, 0), roll_dice(&reveals, &source, 0));
+        let changed = (0..50u8).any(|i| roll_dice(&[[1u8; 32], [i; 32]], &source, 0) != roll_dice(&reveals, &source, 0));
+        assert!(changed, "Changing one reveal should be able to change the roll");
+    }
+
+    #[test]
+    fn test_randomness_sources() {
+        let sources: Vec<Box<dyn RandomnessSource>> = vec![
+            Box::new(SlotHashEntropy { slot_hash: [1u8; 32] }),
+            Box::new(OracleOutput { output: [1u8; 32] }),
+            Box::new(SeededRandomness { seed: 1 }),
+        ];
+
+        for source in &sources {
+            // Test draws are repeatable, vary with the nonce and stay in range
+            assert_eq!(source.random_bytes(3), source.random_bytes(3));
+            assert_ne!(source.random_bytes(3), source.random_bytes(4));
+            assert!((0..100).all(|nonce| source.random_below(nonce, 6) < 6));
+        }
+
+        // Test the seeded source replays exactly and differs between seeds
+        let draws = |seed: u64| -> Vec<u64> {
+            let source = SeededRandomness { seed };
+            (0..16).map(|nonce| source.random_below(nonce, 16)).collect()
+        };
+        assert_eq!(draws(42), draws(42));
+        assert_ne!(draws(42), draws(43));
+
+        // Test every card index of a 16-card deck is reachable
+        let source = SeededRandomness { seed: 42 };
+        let mut seen = [false; 16];
+        for nonce in 0..500 {
+            seen[source.random_below(nonce, 16) as usize] = true;
+        }
+        assert!(seen.iter().all(|&hit| hit));
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let source = SeededRandomness { seed: 7 };
+        let shuffled = |nonce: u64| -> Vec<u8> {
+            let mut items: Vec<u8> = (0..16).collect();
+            shuffle(&mut items, &source, nonce);
+            items
+        };
+
+        // Test a shuffle is a permutation that replays for the same nonce
+        let mut sorted = shuffled(0);
+        sorted.sort();
+        assert_eq!(sorted, (0..16).collect::<Vec<u8>>());
+        assert_eq!(shuffled(0), shuffled(0));
+        assert!((1..10).any(|nonce| shuffled(nonce) != shuffled(0)));
+
+        // Test the top card can end up anywhere in the deck
+        let mut seen = [false; 16];
+        for nonce in 0..500 {
+            let position = shuffled(nonce).iter().position(|&card| card == 0).unwrap();
+            seen[position] = true;
+        }
+        assert!(seen.iter().all(|&hit| hit));
+
+        // Test empty and single-card decks are left alone
+        let mut empty: Vec<u8> = vec![];
+        shuffle(&mut empty, &source, 0);
+        let mut single = vec![9u8];
+        shuffle(&mut single, &source, 0);
+        assert_eq!(single, vec![9]);
+    }
+
+    #[test]
+    fn test_dice_commitment() {
+        let wallet = Pubkey::new_unique();
+        let secret = [5u8; 32];
+
+        assert_eq!(dice_commitment(&secret, &wallet), dice_commitment(&secret, &wallet));
+        assert_ne!(dice_commitment(&secret, &wallet), dice_commitment(&[6u8; 32], &wallet));
+        assert_ne!(dice_commitment(&secret, &wallet), dice_commitment(&secret, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_recent_slot_hash() {
         let program_id = Pubkey::new_unique();
-        let key = Pubkey::new_unique();
         let mut lamports = 0;
-        let mut data = vec![0; std::mem::size_of::<Clock>()];
-        let clock = Clock {
-            slot: 100,
-            epoch_start_timestamp: 1000,
-            epoch: 1,
-            leader_schedule_epoch: 1,
-            unix_timestamp: 1000,
-        };
-        // Serialize clock data manually since pack_into_slice is not available
-        let mut offset = 0;
-        data[offset..offset + 8].copy_from_slice(&clock.slot.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.epoch_start_timestamp.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.epoch.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.leader_schedule_epoch.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.unix_timestamp.to_le_bytes());
-        
-        let clock_account = AccountInfo::new(
-            &key,
+        let mut data = vec![0u8; 8 + 40 * 2];
+        data[0..8].copy_from_slice(&2u64.to_le_bytes());
+        data[8..16].copy_from_slice(&100u64.to_le_bytes());
+        data[16..48].copy_from_slice(&[7u8; 32]);
+        data[48..56].copy_from_slice(&99u64.to_le_bytes());
+        data[56..88].copy_from_slice(&[8u8; 32]);
+
+        let slot_hashes_account = AccountInfo::new(
+            &slot_hashes::ID,
+            false,
             false,
-            true,
             &mut lamports,
-            &mut data.as_mut_slice(),
+            &mut data,
             &program_id,
             false,
             0,
         );
 
-        // Test range boundaries
-        for i in 0..10 {
-            let result = generate_random_number(&clock_account, i, 10).unwrap();
-            assert!(result >= 1 && result <= 10, "Generated number {} should be between 1 and 10", result);
-        }
-        
-        // Test min == max
-        let result = generate_random_number(&clock_account, 100, 5).unwrap();
-        assert!(result >= 1 && result <= 5, "Result should be between 1 and 5");
+        // Test the newest entry is returned
+        assert_eq!(recent_slot_hash(&slot_hashes_account).unwrap(), [7u8; 32]);
+
+        // Test an empty sysvar is rejected
+        slot_hashes_account.data.borrow_mut()[0..8].copy_from_slice(&0u64.to_le_bytes());
+        assert_eq!(recent_slot_hash(&slot_hashes_account).unwrap_err(), ProgramError::InvalidAccountData);
+
+        // Test any other account is rejected
+        let key = Pubkey::new_unique();
+        let mut other_lamports = 0;
+        let mut other_data = vec![0u8; 48];
+        let other_account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut other_lamports,
+            &mut other_data,
+            &program_id,
+            false,
+            0,
+        );
+        assert_eq!(recent_slot_hash(&other_account).unwrap_err(), ProgramError::InvalidArgument);
     }
 
     #[test]
-    fn test_roll_dice() {
-        use solana_program::clock::Clock;
-        use std::cell::RefCell;
-        
-        // Create mock Clock sysvar account
+    fn test_slot_hash_at() {
         let program_id = Pubkey::new_unique();
-        let key = Pubkey::new_unique();
         let mut lamports = 0;
-        let mut data = vec![0; std::mem::size_of::<Clock>()];
-        let clock = Clock {
-            slot: 100,
-            epoch_start_timestamp: 1000,
-            epoch: 1,
-            leader_schedule_epoch: 1,
-            unix_timestamp: 1000,
-        };
-        // Serialize clock data manually since pack_into_slice is not available
-        let mut offset = 0;
-        data[offset..offset + 8].copy_from_slice(&clock.slot.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.epoch_start_timestamp.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.epoch.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.leader_schedule_epoch.to_le_bytes());
-        offset += 8;
-        data[offset..offset + 8].copy_from_slice(&clock.unix_timestamp.to_le_bytes());
-        
-        let clock_account = AccountInfo::new(
-            &key,
+        let mut data = vec![0u8; 8 + 40 * MAX_ENTRIES];
+        let slot_hashes_account = AccountInfo::new(
+            &slot_hashes::ID,
+            false,
             false,
-            true,
             &mut lamports,
-            &mut data.as_mut_slice(),
+            &mut data,
             &program_id,
             false,
             0,
         );
+        let set_entries = |entries: &[(u64, u8)]| {
+            let mut data = slot_hashes_account.data.borrow_mut();
+            data[0..8].copy_from_slice(&(entries.len() as u64).to_le_bytes());
+            for (i, (slot, hash)) in entries.iter().enumerate() {
+                data[8 + i * 40..16 + i * 40].copy_from_slice(&slot.to_le_bytes());
+                data[16 + i * 40..48 + i * 40].copy_from_slice(&[*hash; 32]);
+            }
+        };
 
-        // Test multiple rolls to verify range
-        for i in 0..10 {
-            let (dice1, dice2) = roll_dice(&clock_account, i).unwrap();
-            
-            // Each die should be between 1 and 6
-            assert!(dice1 >= 1 && dice1 <= 6, "First die value {} should be between 1 and 6", dice1);
-            assert!(dice2 >= 1 && dice2 <= 6, "Second die value {} should be between 1 and 6", dice2);
-            
-            // Total should be between 2 and 12
-            let total = dice1 + dice2;
-            assert!(total >= 2 && total <= 12, "Total {} should be between 2 and 12", total);
-        }
+        // Slots 100, 99 and 97; slot 98 was skipped
+        set_entries(&[(100, 7), (99, 8), (97, 9)]);
+
+        // Test a recorded slot gives its own hash, and a skipped one the next slot's
+        assert_eq!(slot_hash_at(&slot_hashes_account, 99).unwrap(), Some([8u8; 32]));
+        assert_eq!(slot_hash_at(&slot_hashes_account, 97).unwrap(), Some([9u8; 32]));
+        assert_eq!(slot_hash_at(&slot_hashes_account, 98).unwrap(), Some([8u8; 32]));
+
+        // Test a slot without a hash yet is unavailable
+        assert_eq!(slot_hash_at(&slot_hashes_account, 101).unwrap_err(), MonopolyError::RandomnessUnavailable.into());
+
+        // Test a slot older than a short sysvar falls to its oldest entry, but one dropped from a full sysvar is gone
+        assert_eq!(slot_hash_at(&slot_hashes_account, 50).unwrap(), Some([9u8; 32]));
+        let full: Vec<(u64, u8)> = (0..MAX_ENTRIES as u64).map(|i| (600 - i, 1)).collect();
+        set_entries(&full);
+        assert_eq!(slot_hash_at(&slot_hashes_account, 89).unwrap(), Some([1u8; 32]));
+        assert_eq!(slot_hash_at(&slot_hashes_account, 50).unwrap(), None);
+
+        // Test an empty sysvar is rejected
+        slot_hashes_account.data.borrow_mut()[0..8].copy_from_slice(&0u64.to_le_bytes());
+        assert_eq!(slot_hash_at(&slot_hashes_account, 99).unwrap_err(), ProgramError::InvalidAccountData);
     }
 
     #[test]
     fn test_calculate_rent() {
-        // Test base rent with no houses
-        assert_eq!(calculate_rent(100, 0), 100);
-        
-        // Test rent with 1-4 houses
-        assert_eq!(calculate_rent(100, 1), 500);  // 5x base rent
-        assert_eq!(calculate_rent(100, 2), 1500); // 15x base rent
-        assert_eq!(calculate_rent(100, 3), 4500); // 45x base rent
-        assert_eq!(calculate_rent(100, 4), 8000); // 80x base rent
-        
-        // Test hotel (5 houses)
-        assert_eq!(calculate_rent(100, 5), 12500); // 125x base rent
-        
-        // Test invalid number of houses (should return base rent)
-        assert_eq!(calculate_rent(100, 6), 100);
+        let mut property = Property {
+            name: "Park Place".to_string(),
+            color: Color::DarkBlue,
+            kind: TileKind::Street,
+            cost: 350,
+            rent: vec![35, 175, 500, 1100, 1300, 1500],
+            house_cost: 200,
+            hotel_cost: 200,
+            house_rent: vec![175, 500, 1100, 1300, 1500],
+            owner: None,
+            houses: 0,
+            mortgaged: false,
+        };
+
+        // Test base rent, doubled for a full color set
+        assert_eq!(calculate_rent(&property.view(), false), 35);
+        assert_eq!(calculate_rent(&property.view(), true), 70);
+
+        // Test rent read from the house table, with no doubling once built on
+        for (houses, expected) in [(1, 175), (2, 500), (3, 1100), (4, 1300), (5, 1500)] {
+            property.houses = houses;
+            assert_eq!(calculate_rent(&property.view(), true), expected);
+        }
+
+        // Test houses beyond the table use the last entry
+        property.houses = 6;
+        assert_eq!(calculate_rent(&property.view(), false), 1500);
+    }
+
+    #[test]
+    fn test_board_rent_tables() {
+        use crate::board::create_board;
+
+        // Published rent for every street: unimproved, 1-4 houses, hotel
+        let streets: [(usize, [u64; 6]); 22] = [
+            (1, [2, 10, 30, 90, 160, 250]),
+            (3, [4, 20, 60, 180, 320, 450]),
+            (6, [6, 30, 90, 270, 400, 550]),
+            (8, [6, 30, 90, 270, 400, 550]),
+            (9, [8, 40, 100, 300, 450, 600]),
+            (11, [10, 50, 150, 450, 625, 750]),
+            (13, [10, 50, 150, 450, 625, 750]),
+            (14, [12, 60, 180, 500, 700, 900]),
+            (16, [14, 70, 200, 550, 750, 950]),
+            (18, [14, 70, 200, 550, 750, 950]),
+            (19, [16, 80, 220, 600, 800, 1000]),
+            (21, [18, 90, 250, 700, 875, 1050]),
+            (23, [18, 90, 250, 700, 875, 1050]),
+            (24, [20, 100, 300, 750, 925, 1100]),
+            (26, [22, 110, 330, 800, 975, 1150]),
+            (27, [22, 110, 330, 800, 975, 1150]),
+            (29, [24, 120, 360, 850, 1025, 1200]),
+            (31, [26, 130, 390, 900, 1100, 1275]),
+            (32, [26, 130, 390, 900, 1100, 1275]),
+            (34, [28, 150, 450, 1000, 1200, 1400]),
+            (37, [35, 175, 500, 1100, 1300, 1500]),
+            (39, [50, 200, 600, 1400, 1700, 2000]),
+        ];
+
+        let owner = 0;
+        let set_owner = |board: &mut [TileType], index: usize| {
+            if let TileType::Property(prop) = &mut board[index] {
+                prop.owner = Some(owner);
+            }
+        };
+
+        for (index, published) in streets {
+            let mut board = create_board();
+            set_owner(&mut board, index);
+            let color = match &board[index] {
+                TileType::Property(prop) => prop.color.clone(),
+                _ => panic!("Expected street at position {}", index),
+            };
+            assert_eq!(calculate_tile_rent(&board, index as u8, 7), published[0], "base rent at {}", index);
+
+            // Complete the color set to double the unimproved rent
+            let group: Vec<usize> = board.iter().enumerate()
+                .filter(|(_, tile)| matches!(tile, TileType::Property(p) if p.kind == TileKind::Street && p.color == color))
+                .map(|(i, _)| i)
+                .collect();
+            for &i in &group {
+                set_owner(&mut board, i);
+            }
+            assert_eq!(calculate_tile_rent(&board, index as u8, 7), published[0] * 2, "full set rent at {}", index);
+
+            for houses in 1..=5 {
+                if let TileType::Property(prop) = &mut board[index] {
+                    prop.houses = houses;
+                }
+                assert_eq!(
+                    calculate_tile_rent(&board, index as u8, 7),
+                    published[houses as usize],
+                    "rent with {} houses at {}", houses, index
+                );
+            }
+        }
+
+        // Railroads charge 25/50/100/200 by how many the owner holds
+        let mut board = create_board();
+        for (held, (index, expected)) in [(5, 25), (15, 50), (25, 100), (35, 200)].into_iter().enumerate() {
+            set_owner(&mut board, index);
+            for railroad in [5, 15, 25, 35].iter().take(held + 1) {
+                assert_eq!(calculate_tile_rent(&board, *railroad, 7), expected);
+            }
+        }
+
+        // Utilities charge 4x the dice with one held, 10x with both
+        let mut board = create_board();
+        set_owner(&mut board, 12);
+        assert_eq!(calculate_tile_rent(&board, 12, 9), 36);
+        set_owner(&mut board, 28);
+        assert_eq!(calculate_tile_rent(&board, 12, 9), 90);
+        assert_eq!(calculate_tile_rent(&board, 28, 9), 90);
+
+        // Every other tile charges nothing
+        let board = create_board();
+        for index in [0, 2, 4, 7, 10, 17, 20, 22, 30, 33, 36, 38] {
+            assert!(matches!(board[index], TileType::Special(_)));
+            assert_eq!(calculate_tile_rent(&board, index as u8, 7), 0);
+        }
     }
 
     #[test]
     fn test_owns_color_group() {
-        use solana_program::pubkey::Pubkey;
-        
         // Test case: Player owns all properties in color group
-        let owner = Pubkey::new_unique();
+        let owner = 0;
         let board = vec![
             TileType::Property(Property {
                 owner: Some(owner),
                 color: Color::Brown,
+                kind: TileKind::Street,
                 houses: 0,
+                mortgaged: false,
                 name: "Mediterranean Avenue".to_string(),
                 cost: 60,
                 rent: vec![2, 10, 30, 90, 160, 250],
@@ -158,7 +347,9 @@ mod tests {
             TileType::Property(Property {
                 owner: Some(owner),
                 color: Color::Brown,
+                kind: TileKind::Street,
                 houses: 0,
+                mortgaged: false,
                 name: "Baltic Avenue".to_string(),
                 cost: 60,
                 rent: vec![4, 20, 60, 180, 320, 450],
@@ -181,35 +372,39 @@ mod tests {
 
         // Test case: Empty board
         let empty_board: Vec<TileType> = vec![];
-        assert!(!owns_color_group(&empty_properties, &empty_board, Color::Purple));
+        assert!(!owns_color_group(&empty_properties, &empty_board, Color::Pink));
     }
 
     #[test]
     fn test_can_build_house() {
-        use solana_program::pubkey::Pubkey;
-        
         // Setup test board with properties
-        let owner = Pubkey::new_unique();
+        let owner = 0;
         let board = vec![
             TileType::Property(Property {
                 owner: Some(owner),
                 color: Color::DarkBlue,
+                kind: TileKind::Street,
                 houses: 0,
+                mortgaged: false,
                 name: "Park Place".to_string(),
                 cost: 350,
                 rent: vec![35, 175, 500, 1100, 1300, 1500],
                 house_cost: 200,
                 hotel_cost: 200,
+                house_rent: vec![175, 500, 1100, 1300, 1500],
             }),
             TileType::Property(Property {
                 owner: Some(owner),
                 color: Color::DarkBlue,
+                kind: TileKind::Street,
                 houses: 0,
+                mortgaged: false,
                 name: "Boardwalk".to_string(),
                 cost: 400,
                 rent: vec![50, 200, 600, 1400, 1700, 2000],
                 house_cost: 200,
                 hotel_cost: 200,
+                house_rent: vec![200, 600, 1400, 1700, 2000],
             })
         ];
 
@@ -228,78 +423,440 @@ mod tests {
         assert!(!can_build_house(2, &player_properties, &mixed_board));
 
         // Test case: Cannot build more than 5 houses (hotel)
-        let mut hotel_board = vec![
+        let hotel_board = vec![
             TileType::Property(Property {
                 owner: Some(owner),
                 color: Color::DarkBlue,
+                kind: TileKind::Street,
                 houses: 5,
+                mortgaged: false,
                 name: "Park Place".to_string(),
                 cost: 350,
                 rent: vec![35, 175, 500, 1100, 1300, 1500],
                 house_cost: 200,
                 hotel_cost: 200,
+                house_rent: vec![175, 500, 1100, 1300, 1500],
             }),
             TileType::Property(Property {
                 owner: Some(owner),
                 color: Color::DarkBlue,
+                kind: TileKind::Street,
                 houses: 5,
+                mortgaged: false,
                 name: "Boardwalk".to_string(),
                 cost: 400,
                 rent: vec![50, 200, 600, 1400, 1700, 2000],
                 house_cost: 200,
                 hotel_cost: 200,
+                house_rent: vec![200, 600, 1400, 1700, 2000],
             })
         ];
         assert!(!can_build_house(0, &player_properties, &hotel_board));
 
+        // Test case: Must build evenly across the color group
+        let mut uneven_board = board.clone();
+        if let TileType::Property(prop) = &mut uneven_board[0] {
+            prop.houses = 1;
+        }
+        assert!(!can_build_house(0, &player_properties, &uneven_board));
+        assert!(can_build_house(1, &player_properties, &uneven_board));
+
+        // Test case: Cannot build while the color group has a mortgage
+        let mut mortgaged_board = board.clone();
+        if let TileType::Property(prop) = &mut mortgaged_board[1] {
+            prop.mortgaged = true;
+        }
+        assert!(!can_build_house(0, &player_properties, &mortgaged_board));
+
         // Test case: Empty board
         let empty_board: Vec<TileType> = vec![];
         let empty_properties: Vec<u8> = vec![];
         assert!(!can_build_house(0, &empty_properties, &empty_board));
     }
+
+    #[test]
+    fn test_hotel_upgrade_cost() {
+        use crate::board::create_board;
+
+        // Every street's hotel costs one more house on top of four
+        for tile in create_board() {
+            if let TileType::Property(prop) = tile {
+                if prop.kind == TileKind::Street {
+                    assert_eq!(hotel_upgrade_cost(&prop), prop.house_cost, "{}", prop.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_sell_house() {
+        use crate::board::create_board;
+
+        let mut board = create_board();
+        let set_houses = |board: &mut [TileType], index: usize, houses: u8| {
+            if let TileType::Property(prop) = &mut board[index] {
+                prop.houses = houses;
+            }
+        };
+
+        // Test case: Nothing to sell on an empty street
+        assert!(!can_sell_house(1, &board));
+
+        // Test case: Must sell from the most built-up street first
+        set_houses(&mut board, 1, 2);
+        set_houses(&mut board, 3, 1);
+        assert!(can_sell_house(1, &board));
+        assert!(!can_sell_house(3, &board));
+
+        // Test case: Even groups can sell from any street
+        set_houses(&mut board, 1, 1);
+        assert!(can_sell_house(1, &board));
+        assert!(can_sell_house(3, &board));
+
+        // Test case: Railroads and special tiles have nothing to sell
+        assert!(!can_sell_house(5, &board));
+        assert!(!can_sell_house(0, &board));
+        assert!(!can_sell_house(200, &board));
+    }
+
+    #[test]
+    fn test_calculate_tile_rent() {
+        use crate::board::create_board;
+
+        let owner = 0;
+        let other = 1;
+        let mut board = create_board();
+        let set_owner = |board: &mut [TileType], index: usize, seat: u8| {
+            if let TileType::Property(prop) = &mut board[index] {
+                prop.owner = Some(seat);
+            }
+        };
+
+        // Test case: Unowned property charges nothing
+        assert_eq!(calculate_tile_rent(&board, 5, 7), 0);
+
+        // Test case: Railroad rent doubles with each railroad held
+        set_owner(&mut board, 5, owner);
+        assert_eq!(calculate_tile_rent(&board, 5, 7), 25);
+        set_owner(&mut board, 15, owner);
+        assert_eq!(calculate_tile_rent(&board, 5, 7), 50);
+        set_owner(&mut board, 25, other);
+        assert_eq!(calculate_tile_rent(&board, 5, 7), 50);
+        set_owner(&mut board, 25, owner);
+        set_owner(&mut board, 35, owner);
+        assert_eq!(calculate_tile_rent(&board, 35, 7), 200);
+
+        // Test case: Utility rent is 4x the dice with one, 10x with both
+        set_owner(&mut board, 12, owner);
+        assert_eq!(calculate_tile_rent(&board, 12, 7), 28);
+        set_owner(&mut board, 28, other);
+        assert_eq!(calculate_tile_rent(&board, 28, 5), 20);
+        set_owner(&mut board, 28, owner);
+        assert_eq!(calculate_tile_rent(&board, 12, 7), 70);
+
+        // Test case: Street rent still depends on houses
+        set_owner(&mut board, 1, other);
+        assert_eq!(calculate_tile_rent(&board, 1, 7), 2);
+        if let TileType::Property(prop) = &mut board[1] {
+            prop.houses = 2;
+        }
+        assert_eq!(calculate_tile_rent(&board, 1, 7), 30);
+
+        // Test case: Mortgaged property charges nothing
+        if let TileType::Property(prop) = &mut board[12] {
+            prop.mortgaged = true;
+        }
+        assert_eq!(calculate_tile_rent(&board, 12, 7), 0);
+
+        // Test case: Cannot build on railroads or utilities
+        let all_railroads = vec![5, 15, 25, 35];
+        assert!(!can_build_house(5, &all_railroads, &board));
+    }
+
+    #[test]
+    fn test_card_helpers() {
+        use crate::board::create_board;
+
+        let owner = 0;
+        let mut board = create_board();
+        let set_owner = |board: &mut [TileType], index: usize, houses: u8| {
+            if let TileType::Property(prop) = &mut board[index] {
+                prop.owner = Some(owner);
+                prop.houses = houses;
+            }
+        };
+
+        // Test case: The nearest railroad and utility are found ahead, wrapping past GO
+        assert_eq!(nearest_of_kind(&board, 7, TileKind::Railroad), Some(15));
+        assert_eq!(nearest_of_kind(&board, 36, TileKind::Railroad), Some(5));
+        assert_eq!(nearest_of_kind(&board, 5, TileKind::Railroad), Some(15));
+        assert_eq!(nearest_of_kind(&board, 22, TileKind::Utility), Some(28));
+        assert_eq!(nearest_of_kind(&board, 36, TileKind::Utility), Some(12));
+
+        // Test case: Card rent doubles railroads and charges utilities ten times the dice
+        assert_eq!(calculate_card_rent(&board, 5, 7), 0);
+        set_owner(&mut board, 5, 0);
+        assert_eq!(calculate_card_rent(&board, 5, 7), 50);
+        set_owner(&mut board, 12, 0);
+        assert_eq!(calculate_card_rent(&board, 12, 7), 70);
+        set_owner(&mut board, 1, 0);
+        assert_eq!(calculate_card_rent(&board, 1, 7), calculate_tile_rent(&board, 1, 7));
+
+        // Test case: Buildings count four houses apart from a hotel
+        assert_eq!(count_buildings(&board, owner), (0, 0));
+        set_owner(&mut board, 1, 3);
+        set_owner(&mut board, 3, 5);
+        assert_eq!(count_buildings(&board, owner), (3, 1));
+        assert_eq!(count_buildings(&board, 1), (0, 0));
+    }
+
+    #[test]
+    fn test_mortgage_costs() {
+        assert_eq!(mortgage_value(60), 30);
+        assert_eq!(unmortgage_cost(60), 33);
+        assert_eq!(mortgage_value(400), 200);
+        assert_eq!(unmortgage_cost(400), 220);
+    }
+}
+
+/// Supplies the entropy behind dice and card draws. Each game picks a backend at creation;
+/// `nonce` separates successive draws that share the same underlying entropy.
+pub trait RandomnessSource {
+    fn random_bytes(&self, nonce: u64) -> [u8; 32];
+
+    /// Draw a value in `0..range`
+    fn random_below(&self, nonce: u64, range: u64) -> u64 {
+        let bytes = self.random_bytes(nonce);
+        u64::from_le_bytes(bytes[0..8].try_into().unwrap()) % range
+    }
+}
+
+/// Entropy from the newest entry of the SlotHashes sysvar
+pub struct SlotHashEntropy {
+    pub slot_hash: [u8; 32],
+}
+
+impl SlotHashEntropy {
+    pub fn from_account(slot_hashes_info: &AccountInfo) -> Result<Self, ProgramError> {
+        Ok(SlotHashEntropy { slot_hash: recent_slot_hash(slot_hashes_info)? })
+    }
 }
 
-pub fn generate_random_number(
-    clock_sysvar_info: &AccountInfo,
-    seed: u64,
-    range: u8,
-) -> Result<u8, ProgramError> {
-    let clock = Clock::from_account_info(clock_sysvar_info)?;
-    
-    // Combine multiple sources of entropy
+impl RandomnessSource for SlotHashEntropy {
+    fn random_bytes(&self, nonce: u64) -> [u8; 32] {
+        keccak_with_nonce(&self.slot_hash, nonce)
+    }
+}
+
+/// Entropy from an output posted by the game's trusted oracle; taken on trust, with no proof
+pub struct OracleOutput {
+    pub output: [u8; 32],
+}
+
+impl RandomnessSource for OracleOutput {
+    fn random_bytes(&self, nonce: u64) -> [u8; 32] {
+        keccak_with_nonce(&self.output, nonce)
+    }
+}
+
+/// Deterministic entropy from a fixed seed, so tests can replay exact games
+pub struct SeededRandomness {
+    pub seed: u64,
+}
+
+impl RandomnessSource for SeededRandomness {
+    fn random_bytes(&self, nonce: u64) -> [u8; 32] {
+        keccak_with_nonce(&self.seed.to_le_bytes(), nonce)
+    }
+}
+
+fn keccak_with_nonce(entropy: &[u8], nonce: u64) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(entropy);
+    hasher.update(nonce.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Fisher–Yates shuffle driven by a single draw from `source`, so an oracle game needs only one request
+pub fn shuffle<T>(items: &mut [T], source: &dyn RandomnessSource, nonce: u64) {
+    let entropy = source.random_bytes(nonce);
+    for i in (1..items.len()).rev() {
+        let bytes = keccak_with_nonce(&entropy, i as u64);
+        let j = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) % (i as u64 + 1);
+        items.swap(i, j as usize);
+    }
+}
+
+/// Roll two dice from every secret revealed in a dice round plus the game's randomness source.
+/// No single player can steer the result without knowing every other secret in advance.
+pub fn roll_dice(reveals: &[[u8; 32]], source: &dyn RandomnessSource, nonce: u64) -> (u8, u8) {
     let mut hasher = Keccak256::new();
-    hasher.update(&clock.slot.to_le_bytes());
-    hasher.update(&clock.unix_timestamp.to_le_bytes());
-    hasher.update(&seed.to_le_bytes());
-    
+    for secret in reveals {
+        hasher.update(secret);
+    }
+    hasher.update(source.random_bytes(nonce));
     let hash = hasher.finalize();
-    let random_value = u8::from_le_bytes([hash[0]]);
-    
-    // Map to range [1, range]
-    Ok((random_value % range) + 1)
+
+    // Reduce 64 bits per die so the modulo bias is negligible
+    let die = |bytes: &[u8]| (u64::from_le_bytes(bytes.try_into().unwrap()) % 6) as u8 + 1;
+    (die(&hash[0..8]), die(&hash[8..16]))
+}
+
+/// Hash a player commits to before revealing `secret`; binding the wallet stops
+/// one player from copying another's commitment
+pub fn dice_commitment(secret: &[u8; 32], wallet: &Pubkey) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(secret);
+    hasher.update(wallet.as_ref());
+    hasher.finalize().into()
+}
+
+/// Read the newest hash from the SlotHashes sysvar without deserializing the whole account
+pub fn recent_slot_hash(slot_hashes_info: &AccountInfo) -> Result<[u8; 32], ProgramError> {
+    if *slot_hashes_info.key != slot_hashes::ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // Layout: u64 entry count, then (slot: u64, hash: [u8; 32]) entries, newest first
+    let data = slot_hashes_info.try_borrow_data()?;
+    if data.len() < 48 || u64::from_le_bytes(data[0..8].try_into().unwrap()) == 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(data[16..48].try_into().unwrap())
+}
+
+/// Read the hash of `slot` from the SlotHashes sysvar, or of the first slot recorded after it if
+/// `slot` was skipped. Fails while `slot` has no hash yet, and gives `None` once it has aged out.
+pub fn slot_hash_at(slot_hashes_info: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>, ProgramError> {
+    if *slot_hashes_info.key != slot_hashes::ID {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let data = slot_hashes_info.try_borrow_data()?;
+    let count = data.get(0..8).map_or(0, |count| u64::from_le_bytes(count.try_into().unwrap())) as usize;
+    if count == 0 || data.len() < 8 + count * 40 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Entries run newest first, so the last one at or after `slot` is the one wanted
+    let mut found = None;
+    for entry in data[8..8 + count * 40].chunks_exact(40) {
+        let entry_slot = u64::from_le_bytes(entry[0..8].try_into().unwrap());
+        if entry_slot < slot {
+            return found.map(Some).ok_or_else(|| MonopolyError::RandomnessUnavailable.into());
+        }
+        found = Some(entry[8..40].try_into().unwrap());
+        if entry_slot == slot {
+            return Ok(found);
+        }
+    }
+
+    // Every entry is newer than `slot`: a full sysvar may have dropped it, a short one never had it
+    if count >= MAX_ENTRIES {
+        Ok(None)
+    } else {
+        Ok(found)
+    }
 }
 
-/// Roll two dice using on-chain randomization
-pub fn roll_dice(
-    clock_sysvar_info: &AccountInfo,
-    seed: u64,
-) -> Result<(u8, u8), ProgramError> {
-    let dice1 = generate_random_number(clock_sysvar_info, seed, 6)?;
-    let dice2 = generate_random_number(clock_sysvar_info, seed.wrapping_add(1), 6)?;
-    Ok((dice1, dice2))
+/// Look up a street's rent from its own tables: `rent[0]` when unimproved (doubled when the
+/// owner holds the full color set), otherwise `house_rent` for the number of houses built
+pub fn calculate_rent(property: &PropertyView, full_set: bool) -> u64 {
+    match property.houses {
+        0 => {
+            let base = property.rent.first().copied().unwrap_or(0);
+            if full_set {
+                base * 2
+            } else {
+                base
+            }
+        }
+        houses => property.house_rent.get(houses as usize - 1)
+            .or(property.house_rent.last())
+            .copied()
+            .unwrap_or(0),
+    }
 }
 
-/// Calculate rent for a property based on number of houses
-pub fn calculate_rent(base_rent: u64, houses: u8) -> u64 {
-    match houses {
-        0 => base_rent,
-        1 => base_rent * 5,
-        2 => base_rent * 15,
-        3 => base_rent * 45,
-        4 => base_rent * 80,
-        5 => base_rent * 125,  // Hotel
-        _ => base_rent,
+/// Every ownable tile on the board, in board order
+fn properties<B: Board + ?Sized>(board: &B) -> impl Iterator<Item = PropertyView<'_>> {
+    (0..board.tile_count()).filter_map(move |index| board.property(index as u8))
+}
+
+/// Count how many properties of a kind are held by `owner`
+pub fn count_owned_of_kind<B: Board + ?Sized>(board: &B, owner: u8, kind: TileKind) -> usize {
+    properties(board)
+        .filter(|prop| prop.kind == kind && prop.owner == Some(owner))
+        .count()
+}
+
+/// Calculate the rent owed for landing on an owned property.
+/// Streets charge from their rent tables, railroads by how many railroads the owner holds,
+/// and utilities a multiple of the dice total.
+pub fn calculate_tile_rent<B: Board + ?Sized>(board: &B, property_index: u8, dice_total: u8) -> u64 {
+    let prop = match board.property(property_index) {
+        Some(prop) => prop,
+        None => return 0,
+    };
+    let owner = match prop.owner {
+        Some(owner) => owner,
+        None => return 0,
+    };
+
+    // Mortgaged properties collect no rent
+    if prop.mortgaged || prop.rent.is_empty() {
+        return 0;
     }
+
+    match prop.kind {
+        TileKind::Street => {
+            let full_set = properties(board)
+                .filter(|p| p.kind == TileKind::Street && p.color == prop.color)
+                .all(|p| p.owner == Some(owner));
+            calculate_rent(&prop, full_set)
+        }
+        TileKind::Railroad | TileKind::Utility => {
+            let owned = count_owned_of_kind(board, owner, prop.kind).clamp(1, prop.rent.len());
+            let rent = prop.rent[owned - 1];
+            if prop.kind == TileKind::Utility {
+                rent * dice_total as u64
+            } else {
+                rent
+            }
+        }
+    }
+}
+
+/// Rent charged by an advance-to-nearest card: twice the usual rent for a railroad,
+/// and ten times the dice total for a utility however many the owner holds.
+pub fn calculate_card_rent<B: Board + ?Sized>(board: &B, property_index: u8, dice_total: u8) -> u64 {
+    let rent = calculate_tile_rent(board, property_index, dice_total);
+    match board.property(property_index) {
+        Some(prop) if rent > 0 => match prop.kind {
+            TileKind::Railroad => rent * 2,
+            TileKind::Utility => 10 * dice_total as u64,
+            TileKind::Street => rent,
+        },
+        _ => rent,
+    }
+}
+
+/// Board index of the first tile of `kind` ahead of `from`, wrapping past GO
+pub fn nearest_of_kind(board: &[TileType], from: u8, kind: TileKind) -> Option<u8> {
+    (1..=board.len())
+        .map(|step| ((from as usize + step) % board.len()) as u8)
+        .find(|&index| matches!(&board[index as usize], TileType::Property(prop) if prop.kind == kind))
+}
+
+/// Houses and hotels standing on the streets `owner` holds
+pub fn count_buildings(board: &[TileType], owner: u8) -> (u64, u64) {
+    board.iter()
+        .filter_map(|tile| tile.as_property())
+        .filter(|prop| prop.owner == Some(owner))
+        .fold((0, 0), |(houses, hotels), prop| match prop.houses {
+            5 => (houses, hotels + 1),
+            built => (houses + built as u64, hotels),
+        })
 }
 
 /// Check if a player owns all properties of a color group
@@ -311,7 +868,7 @@ pub fn owns_color_group(
     let color_properties: Vec<usize> = board.iter().enumerate()
         .filter_map(|(i, tile)| {
             if let TileType::Property(prop) = tile {
-                if prop.color == color {
+                if prop.kind == TileKind::Street && prop.color == color {
                     Some(i)
                 } else {
                     None
@@ -330,6 +887,25 @@ pub fn owns_color_group(
         .all(|&idx| player_properties.contains(&(idx as u8)))
 }
 
+/// Check if any property of a color group is mortgaged
+pub fn color_group_has_mortgage(board: &[TileType], color: &Color) -> bool {
+    board.iter().any(|tile| match tile {
+        TileType::Property(prop) => prop.kind == TileKind::Street && prop.color == *color && prop.mortgaged,
+        _ => false,
+    })
+}
+
+/// Cash the bank pays out for mortgaging a property
+pub fn mortgage_value(cost: u64) -> u64 {
+    cost / 2
+}
+
+/// Cash needed to lift a mortgage: the mortgage value plus 10% interest
+pub fn unmortgage_cost(cost: u64) -> u64 {
+    let value = mortgage_value(cost);
+    value + value / 10
+}
+
 /// Check if a player can build a house on a property
 pub fn can_build_house(
     property_index: u8,
@@ -342,14 +918,63 @@ pub fn can_build_house(
     }
 
     if let TileType::Property(prop) = &board[idx] {
+        // Only streets can be built on
+        if prop.kind != TileKind::Street {
+            return false;
+        }
+
         // Can't build more than 5 houses (hotel)
         if prop.houses >= 5 {
             return false;
         }
 
+        // Can't build while any property of the color is mortgaged
+        if color_group_has_mortgage(board, &prop.color) {
+            return false;
+        }
+
+        // Must build evenly: no street in the group may lag behind this one
+        if color_group_houses(board, &prop.color).any(|houses| houses < prop.houses) {
+            return false;
+        }
+
         // Must own all properties of the same color
         owns_color_group(player_properties, board, prop.color.clone())
     } else {
         false
     }
 }
+
+/// Price of turning four houses into a hotel. `hotel_cost` is the value of a fully
+/// developed street, so the upgrade costs whatever the four houses don't already cover.
+pub fn hotel_upgrade_cost(property: &Property) -> u64 {
+    property.hotel_cost.saturating_sub(property.house_cost * 4)
+}
+
+/// What the bank pays for the top building on a street: half of what it cost to put up.
+pub fn building_sell_price(property: &Property) -> u64 {
+    if property.houses == 5 {
+        hotel_upgrade_cost(property) / 2
+    } else {
+        property.house_cost / 2
+    }
+}
+
+/// Check if a house (or hotel) can be sold from a street.
+/// Buildings must come down evenly, so no street in the group may hold more.
+pub fn can_sell_house(property_index: u8, board: &[TileType]) -> bool {
+    match board.get(property_index as usize) {
+        Some(TileType::Property(prop)) if prop.kind == TileKind::Street && prop.houses > 0 => {
+            color_group_houses(board, &prop.color).all(|houses| houses <= prop.houses)
+        }
+        _ => false,
+    }
+}
+
+/// Houses built on each street of a color group (5 for a hotel)
+fn color_group_houses<'a>(board: &'a [TileType], color: &'a Color) -> impl Iterator<Item = u8> + 'a {
+    board.iter().filter_map(move |tile| match tile {
+        TileType::Property(prop) if prop.kind == TileKind::Street && &prop.color == color => Some(prop.houses),
+        _ => None,
+    })
+}